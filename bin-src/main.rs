@@ -1,9 +1,8 @@
-use std::ffi::{CStr, CString};
+use std::ffi::CString;
 use std::fs::read_to_string;
+use std::io::BufRead;
 use std::time::Instant;
-use rs_abieos::{Abieos, NameLike};
-
-pub const EOSIO_TOKEN_HEX_ABI: &str = "0e656f73696f3a3a6162692f312e30010c6163636f756e745f6e616d65046e616d6505087472616e7366657200040466726f6d0c6163636f756e745f6e616d6502746f0c6163636f756e745f6e616d65087175616e74697479056173736574046d656d6f06737472696e67066372656174650002066973737565720c6163636f756e745f6e616d650e6d6178696d756d5f737570706c79056173736574056973737565000302746f0c6163636f756e745f6e616d65087175616e74697479056173736574046d656d6f06737472696e67076163636f756e7400010762616c616e63650561737365740e63757272656e63795f7374617473000306737570706c790561737365740a6d61785f737570706c79056173736574066973737565720c6163636f756e745f6e616d6503000000572d3ccdcd087472616e73666572000000000000a531760569737375650000000000a86cd445066372656174650002000000384f4d113203693634010863757272656e6379010675696e743634076163636f756e740000000000904dc603693634010863757272656e6379010675696e7436340e63757272656e63795f7374617473000000";
+use rs_abieos::{builtin_abis, AbiDef, Abieos, Conformance, Name};
 
 fn measure_call(f: &mut dyn FnMut(), name: &str) {
     let start = Instant::now();
@@ -12,10 +11,122 @@ fn measure_call(f: &mut dyn FnMut(), name: &str) {
     println!("⏱️ {name} took: {:?}", duration);
 }
 
+const CONFORMANCE_USAGE: &str = "usage: rs_abieos conformance <abi.json|abi.bin> <type> [--json JSON | --hex HEX | --file PATH]
+
+Payloads from --file (or stdin) are read one per line, as JSON when they start with {, [ or \"
+and as hex otherwise. Empty lines and lines starting with # are skipped. Exits with 1 if any
+divergence is found, and with 3 when built with pure-rust, where the backend is the reference
+codec and no divergence can be found.";
+
+/// Format of the conformance payloads
+#[derive(Clone, Copy)]
+enum PayloadFormat {
+    Json,
+    Hex,
+    /// Guessed from the first character of each payload
+    Guess,
+}
+
+/// Check payloads against the reference encoder, returning the process exit code
+fn conformance(args: &[String]) -> i32 {
+    let (abi_path, datatype) = match args {
+        [abi_path, datatype, ..] => (abi_path, datatype),
+        _ => {
+            eprintln!("{CONFORMANCE_USAGE}");
+            return 2;
+        }
+    };
+    let (format, payloads): (PayloadFormat, Vec<String>) = match &args[2..] {
+        [flag, payload] if flag == "--json" => (PayloadFormat::Json, vec![payload.clone()]),
+        [flag, payload] if flag == "--hex" => (PayloadFormat::Hex, vec![payload.clone()]),
+        [flag, path] if flag == "--file" => match read_to_string(path) {
+            Ok(content) => (PayloadFormat::Guess, content.lines().map(str::to_string).collect()),
+            Err(e) => {
+                eprintln!("❌ Failed to read {path}: {e}");
+                return 2;
+            }
+        },
+        [] => (PayloadFormat::Guess, std::io::stdin().lock().lines().map_while(Result::ok).collect()),
+        _ => {
+            eprintln!("{CONFORMANCE_USAGE}");
+            return 2;
+        }
+    };
+
+    let abi = match std::fs::read(abi_path) {
+        Ok(content) if content.trim_ascii_start().starts_with(b"{") => {
+            AbiDef::from_json(&String::from_utf8_lossy(&content))
+        }
+        Ok(content) => AbiDef::from_bin(&content),
+        Err(e) => {
+            eprintln!("❌ Failed to read {abi_path}: {e}");
+            return 2;
+        }
+    };
+    let checker = match abi.and_then(|abi| Conformance::new(&abi)) {
+        Ok(checker) => checker,
+        Err(e) => {
+            eprintln!("❌ Failed to load ABI: {e}");
+            return 2;
+        }
+    };
+
+    if !checker.is_differential() {
+        eprintln!("⚠️ Built with pure-rust: the backend is the reference codec, payloads are not compared with abieos");
+    }
+
+    let mut checked = 0;
+    let mut diverging = 0;
+    for (line, payload) in payloads.iter().enumerate() {
+        let payload = payload.trim();
+        if payload.is_empty() || payload.starts_with('#') {
+            continue;
+        }
+        let is_json = match format {
+            PayloadFormat::Json => true,
+            PayloadFormat::Hex => false,
+            PayloadFormat::Guess => payload.starts_with(['{', '[', '"']),
+        };
+        let divergences = if is_json {
+            checker.check_json(datatype, payload)
+        } else {
+            match checker.check_hex(datatype, payload) {
+                Ok(divergences) => divergences,
+                Err(e) => {
+                    eprintln!("❌ Payload {}: {e}", line + 1);
+                    return 2;
+                }
+            }
+        };
+        checked += 1;
+        if !divergences.is_empty() {
+            diverging += 1;
+            for divergence in divergences {
+                println!("❌ Payload {}: {divergence}", line + 1);
+            }
+        }
+    }
+    if !checker.is_differential() {
+        println!("{checked} payloads checked, not differential");
+        return 3;
+    }
+    println!("{checked} payloads checked, {diverging} diverging");
+    i32::from(diverging > 0)
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|command| command == "conformance") {
+        std::process::exit(conformance(&args[2..]));
+    }
 
     // create a new instance of abieos
-    let abieos: Abieos = Abieos::new();
+    let mut abieos: Abieos = Abieos::new();
+
+    // names are validated and converted in pure rust
+    let eosio: Name = "eosio".parse().expect("Invalid name");
+    let token: Name = "eosio.token".parse().expect("Invalid name");
+    let transfer: Name = "transfer".parse().expect("Invalid name");
 
     // loading an abi from a file
     let abi_content = read_to_string("abis/eosio.abi").expect("Failed to read ABI file");
@@ -34,7 +145,7 @@ fn main() {
 
     // loading an abi as binary
     println!("\n⚡ Testing loading abi as binary...");
-    let loading_status = abieos.set_abi_bin("eosio", &abi_bin).unwrap();
+    let loading_status = abieos.set_abi_bin(eosio, &abi_bin).unwrap();
     println!("☑️ Binary Load: {}", loading_status);
 
     // converting an abi from binary to json
@@ -50,20 +161,21 @@ fn main() {
         }
     }
 
-    // loading an abi hex
-    println!("\n⚡ Testing loading abi as hex...");
-    let loading_status = abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+    // loading a builtin abi in binary
+    println!("\n⚡ Testing loading builtin abi as binary...");
+    let token_abi = abieos.abi_json_to_bin(builtin_abis::EOSIO_TOKEN.json()).unwrap();
+    let loading_status = abieos.set_abi_bin(token, &token_abi).unwrap();
     if loading_status {
-        println!("☑️ HEX Abi Loaded successfully");
+        println!("☑️ Binary Abi Loaded successfully");
     } else {
-        println!("❌ Failed to load HEX Abi");
+        println!("❌ Failed to load binary Abi");
     }
 
     // name conversion test
     println!("\n⚡ Testing name conversion...");
     let original_name = "alice";
     let mut native_name: u64 = 0;
-    let mut name_as_string: &str = "";
+    let mut name_as_string = String::new();
 
     // measure the time taken to convert the name
     measure_call(&mut || {
@@ -80,7 +192,7 @@ fn main() {
     println!("\n⚡ Testing name conversion (C-String)...");
     let original_name = c"eosio";
     let mut native_name: u64 = 0;
-    let mut name_as_string: &CStr = c"";
+    let mut name_as_string = CString::default();
 
     // measure the time taken to convert the name
     measure_call(&mut || {
@@ -88,7 +200,7 @@ fn main() {
         name_as_string = abieos.name_to_cstr(native_name);
     }, "name conversion (C-string)");
 
-    if name_as_string.eq(original_name) {
+    if name_as_string.as_c_str().eq(original_name) {
         println!("☑️ {:?} => {native_name} => {:?}", original_name, name_as_string);
     } else {
         println!("❌ Name conversion failed");
@@ -96,7 +208,7 @@ fn main() {
 
 
     println!("\n⚡ Testing action type conversion...");
-    match abieos.get_type_for_action("eosio.token", "transfer") {
+    match abieos.get_type_for_action(token, transfer) {
         Ok(x) => println!("transfer_action_datatype: {x}"),
         Err(_) => {
             println!("❌ Failed to get transfer action datatype");
@@ -104,7 +216,7 @@ fn main() {
     };
 
     println!("\n⚡ Testing table type conversion...");
-    match abieos.get_type_for_table("eosio.token", "accounts") {
+    match abieos.get_type_for_table(token, "accounts".parse::<Name>().expect("Invalid name")) {
         Ok(x) => println!("account_table_datatype: {x}"),
         Err(_) => {
             println!("❌ Failed to get account table datatype");
@@ -130,6 +242,13 @@ fn main() {
         }
     };
 
+    println!("\n⚡ Testing action encoding...");
+    // the data type is resolved from the ABI actions, it does not have to match the action name
+    match abieos.encode_action(token, transfer, json).and_then(|data| abieos.decode_action(token, transfer, &data)) {
+        Ok(x) => println!("decode_action: {x}"),
+        Err(e) => println!("❌ Failed to encode action: {e}"),
+    };
+
 
     {
         let runs = 1000;
@@ -159,12 +278,12 @@ fn main() {
         let start = Instant::now();
         let account = c"eosio.token";
         let action = c"transfer";
-        let mut last_json = c"";
-        let bin_c = CString::new(bin).unwrap();
-        let mut bin: &CStr = bin_c.as_c_str();
+        let mut last_json = CString::default();
+        let mut bin: CString = CString::new(bin).unwrap();
         for _ in 0..runs {
-            let json_out = abieos.hex_to_json_c(account, action, bin);
-            let bin_out = abieos.json_to_hex_c(account, action, json_out);
+            // results borrow the context mutably, so they must be copied before the next call
+            let json_out = abieos.hex_to_json_c(account, action, &bin).to_owned();
+            let bin_out = abieos.json_to_hex_c(account, action, &json_out).to_owned();
             if !last_json.is_empty() {
                 assert_eq!(json_out, last_json);
                 assert_eq!(bin, bin_out);
@@ -180,7 +299,7 @@ fn main() {
     {
         println!("\n⚡ Testing loading abi as json...");
         let abi_content_c = CString::new(abi_content).unwrap();
-        match abieos.set_abi_json_c("eosio", abi_content_c.as_ref()) {
+        match abieos.set_abi_json_c(eosio, abi_content_c.as_ref()) {
             Ok(_) => {
                 println!("☑️ JSON Abi Loaded successfully")
             }
@@ -261,7 +380,7 @@ fn main() {
         }"#;
 
     match Abieos::new()
-        .contract(NameLike::StringRef("eosio"))
+        .contract(eosio)
         .load_json_file("abis/transaction.abi.json")
         .unwrap()
         .json_to_hex(
@@ -277,21 +396,21 @@ fn main() {
     };
 
     measure_call(&mut || {
-        abieos.contract(NameLike::StringRef("eosio")).load_json_file("abis/eosio.abi").unwrap();
+        abieos.contract(eosio).load_json_file("abis/eosio.abi").unwrap();
     }, "loading eosio abi from file (oneshot)");
 
     measure_call(&mut || {
         let abi_content = read_to_string("abis/eosio.abi").unwrap();
-        abieos.set_abi_json("eosio", &abi_content).unwrap();
+        abieos.set_abi_json(eosio, &abi_content).unwrap();
     }, "loading eosio abi from file (procedural)");
 
     measure_call(&mut || {
-        abieos.contract(NameLike::StringRef("eosio")).load_json_file("abis/transaction.abi.json").unwrap();
+        abieos.contract(eosio).load_json_file("abis/transaction.abi.json").unwrap();
     }, "loading transaction abi from file");
 
     let json_data = read_to_string("abis/sample.json").unwrap();
 
     measure_call(&mut || {
-        abieos.contract(NameLike::StringRef("eosio")).json_to_hex("delegatebw", &json_data).unwrap();
+        abieos.contract(eosio).json_to_hex("delegatebw", &json_data).unwrap();
     }, "serializing sample action");
 }
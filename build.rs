@@ -55,6 +55,11 @@ fn main() {
         return;
     }
 
+    if std::env::var("CARGO_FEATURE_PURE_RUST").is_ok() {
+        // The pure-rust backend does not need the C++ library
+        return;
+    }
+
     match sys_info::os_type() {
         Ok(os_type) => {
             match os_type.as_str() {
@@ -0,0 +1,75 @@
+//! # ABI cache
+//!
+//!  Rust-side record of the ABIs loaded into a context, in the format they were given
+
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[cfg(not(feature = "pure-rust"))]
+use crate::serializer::AbiSerializer;
+use crate::{AbiLike, Name};
+
+struct Entry {
+    source: Arc<AbiLike>,
+    hash: OnceCell<[u8; 32]>,
+    /// Pure-Rust serializer for typed values, parsed on first use
+    #[cfg(not(feature = "pure-rust"))]
+    parsed: OnceCell<Result<AbiSerializer, String>>,
+}
+
+/// Contract ABIs loaded through the `set_abi_*` methods
+#[derive(Default)]
+pub(crate) struct AbiCache {
+    entries: BTreeMap<Name, Entry>,
+}
+
+impl AbiCache {
+    pub(crate) fn insert(&mut self, contract: Name, source: AbiLike) {
+        let entry = Entry {
+            source: Arc::new(source),
+            hash: OnceCell::new(),
+            #[cfg(not(feature = "pure-rust"))]
+            parsed: OnceCell::new(),
+        };
+        self.entries.insert(contract, entry);
+    }
+
+    pub(crate) fn remove(&mut self, contract: Name) {
+        self.entries.remove(&contract);
+    }
+
+    pub(crate) fn contains(&self, contract: Name) -> bool {
+        self.entries.contains_key(&contract)
+    }
+
+    /// Contracts with the ABI they were loaded from
+    pub(crate) fn sources(&self) -> Vec<(Name, Arc<AbiLike>)> {
+        self.entries.iter().map(|(contract, entry)| (*contract, entry.source.clone())).collect()
+    }
+
+    /// Contracts with the hash of their ABI, computed with `hash` on first use
+    pub(crate) fn hashes(&self, hash: impl Fn(&AbiLike) -> [u8; 32]) -> Vec<(Name, [u8; 32])> {
+        self.entries
+            .iter()
+            .map(|(contract, entry)| (*contract, *entry.hash.get_or_init(|| hash(&entry.source))))
+            .collect()
+    }
+
+    /// Serializer of a contract, parsing its ABI if needed
+    #[cfg(not(feature = "pure-rust"))]
+    pub(crate) fn serializer(&self, contract: Name) -> Result<&AbiSerializer, String> {
+        let entry = self.entries.get(&contract).ok_or_else(|| format!("contract \"{}\" is not loaded", contract))?;
+        let parsed = entry.parsed.get_or_init(|| {
+            let abi = match entry.source.as_ref() {
+                AbiLike::Json(abi_json) => crate::AbiDef::from_json(abi_json),
+                AbiLike::Hex(abi_hex) => crate::stream::from_hex(abi_hex)
+                    .map_err(crate::AbieosError::InvalidAbi)
+                    .and_then(|abi_bin| crate::AbiDef::from_bin(&abi_bin)),
+                AbiLike::Bin(abi_bin) => crate::AbiDef::from_bin(abi_bin),
+            };
+            abi.map_err(|e| e.to_string()).and_then(AbiSerializer::new)
+        });
+        parsed.as_ref().map_err(String::clone)
+    }
+}
@@ -0,0 +1,496 @@
+//! # ABI Definition
+//!
+//!  Pure-Rust model of an Antelope ABI, parsed from and serialized to both the JSON form
+//!  and the binary form produced by [`Abieos::abi_json_to_bin`](crate::Abieos::abi_json_to_bin)
+
+use std::collections::BTreeMap;
+
+use crate::json::JsonValue;
+use crate::stream::{self, InputStream};
+use crate::{AbieosError, Name};
+
+/// Type alias (`types` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeDef {
+    pub new_type_name: String,
+    pub type_: String,
+}
+
+/// Struct field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldDef {
+    pub name: String,
+    pub type_: String,
+}
+
+/// Struct definition (`structs` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StructDef {
+    pub name: String,
+    pub base: String,
+    pub fields: Vec<FieldDef>,
+}
+
+/// Action definition (`actions` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionDef {
+    pub name: Name,
+    pub type_: String,
+    pub ricardian_contract: String,
+}
+
+/// Multi-index table definition (`tables` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableDef {
+    pub name: Name,
+    pub index_type: String,
+    pub key_names: Vec<String>,
+    pub key_types: Vec<String>,
+    pub type_: String,
+}
+
+/// Ricardian clause (`ricardian_clauses` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClausePair {
+    pub id: String,
+    pub body: String,
+}
+
+/// Error message (`error_messages` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorMessage {
+    pub error_code: u64,
+    pub error_msg: String,
+}
+
+/// ABI extension (`abi_extensions` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbiExtension {
+    pub type_: u16,
+    pub data: Vec<u8>,
+}
+
+/// Variant definition (`variants` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariantDef {
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+/// Action return value definition (`action_results` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionResultDef {
+    pub name: Name,
+    pub result_type: String,
+}
+
+/// Primary index of a key-value table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrimaryKeyIndexDef {
+    pub name: Name,
+    pub type_: String,
+}
+
+/// Key-value table definition (`kv_tables` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KvTableDef {
+    pub name: Name,
+    pub type_: String,
+    pub primary_index: PrimaryKeyIndexDef,
+    pub secondary_indices: BTreeMap<Name, String>,
+}
+
+/// Antelope ABI definition
+///
+/// ```rust
+/// use rs_abieos::AbiDef;
+///
+/// let abi = AbiDef::from_json(r#"{
+///     "version": "eosio::abi/1.1",
+///     "structs": [{"name": "hi", "base": "", "fields": [{"name": "user", "type": "name"}]}],
+///     "actions": [{"name": "hi", "type": "hi", "ricardian_contract": ""}]
+/// }"#).unwrap();
+/// assert_eq!(abi.get_action("hi".parse().unwrap()).unwrap().type_, "hi");
+///
+/// let bin = abi.to_bin().unwrap();
+/// assert_eq!(AbiDef::from_bin(&bin).unwrap(), abi);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbiDef {
+    pub version: String,
+    pub types: Vec<TypeDef>,
+    pub structs: Vec<StructDef>,
+    pub actions: Vec<ActionDef>,
+    pub tables: Vec<TableDef>,
+    pub ricardian_clauses: Vec<ClausePair>,
+    pub error_messages: Vec<ErrorMessage>,
+    pub abi_extensions: Vec<AbiExtension>,
+    pub variants: Vec<VariantDef>,
+    pub action_results: Vec<ActionResultDef>,
+    pub kv_tables: Vec<KvTableDef>,
+}
+
+fn invalid(e: String) -> AbieosError {
+    AbieosError::InvalidAbi(e)
+}
+
+impl AbiDef {
+    /// Find a struct by name
+    pub fn get_struct(&self, name: &str) -> Option<&StructDef> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    /// Find an action by name
+    pub fn get_action(&self, name: Name) -> Option<&ActionDef> {
+        self.actions.iter().find(|a| a.name == name)
+    }
+
+    /// Find a table by name
+    pub fn get_table(&self, name: Name) -> Option<&TableDef> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    /// Find a variant by name
+    pub fn get_variant(&self, name: &str) -> Option<&VariantDef> {
+        self.variants.iter().find(|v| v.name == name)
+    }
+
+    /// Find an action result by action name
+    pub fn get_action_result(&self, name: Name) -> Option<&ActionResultDef> {
+        self.action_results.iter().find(|r| r.name == name)
+    }
+
+    // --- binary ---
+
+    /// Parse the binary ABI form
+    pub fn from_bin(bin: &[u8]) -> Result<AbiDef, AbieosError> {
+        let mut s = InputStream::new(bin);
+        let abi = Self::read_bin(&mut s).map_err(invalid)?;
+        if s.remaining() > 0 {
+            return Err(invalid(s.error("unexpected trailing data")));
+        }
+        Ok(abi)
+    }
+
+    fn read_bin(s: &mut InputStream) -> Result<AbiDef, String> {
+        fn vec<T>(s: &mut InputStream, f: impl Fn(&mut InputStream) -> Result<T, String>) -> Result<Vec<T>, String> {
+            let len = s.read_length(1)?;
+            (0..len).map(|_| f(s)).collect()
+        }
+        fn name(s: &mut InputStream) -> Result<Name, String> {
+            s.read_u64().map(Name::new)
+        }
+        fn strings(s: &mut InputStream) -> Result<Vec<String>, String> {
+            vec(s, |s| s.read_string())
+        }
+
+        let mut abi = AbiDef { version: s.read_string()?, ..Default::default() };
+        abi.types = vec(s, |s| Ok(TypeDef { new_type_name: s.read_string()?, type_: s.read_string()? }))?;
+        abi.structs = vec(s, |s| {
+            Ok(StructDef {
+                name: s.read_string()?,
+                base: s.read_string()?,
+                fields: vec(s, |s| Ok(FieldDef { name: s.read_string()?, type_: s.read_string()? }))?,
+            })
+        })?;
+        abi.actions = vec(s, |s| {
+            Ok(ActionDef { name: name(s)?, type_: s.read_string()?, ricardian_contract: s.read_string()? })
+        })?;
+        abi.tables = vec(s, |s| {
+            Ok(TableDef {
+                name: name(s)?,
+                index_type: s.read_string()?,
+                key_names: strings(s)?,
+                key_types: strings(s)?,
+                type_: s.read_string()?,
+            })
+        })?;
+        abi.ricardian_clauses = vec(s, |s| Ok(ClausePair { id: s.read_string()?, body: s.read_string()? }))?;
+        abi.error_messages = vec(s, |s| Ok(ErrorMessage { error_code: s.read_u64()?, error_msg: s.read_string()? }))?;
+        abi.abi_extensions = vec(s, |s| Ok(AbiExtension { type_: s.read_u16()?, data: s.read_byte_vec()? }))?;
+
+        // the remaining sections may not exist in older ABIs
+        if s.remaining() == 0 {
+            return Ok(abi);
+        }
+        abi.variants = vec(s, |s| Ok(VariantDef { name: s.read_string()?, types: strings(s)? }))?;
+        if s.remaining() == 0 {
+            return Ok(abi);
+        }
+        abi.action_results = vec(s, |s| Ok(ActionResultDef { name: name(s)?, result_type: s.read_string()? }))?;
+        if s.remaining() == 0 {
+            return Ok(abi);
+        }
+        abi.kv_tables = vec(s, |s| {
+            let table_name = name(s)?;
+            let type_ = s.read_string()?;
+            let primary_index = PrimaryKeyIndexDef { name: name(s)?, type_: s.read_string()? };
+            let secondary_indices = vec(s, |s| Ok((name(s)?, s.read_string()?)))?.into_iter().collect();
+            Ok(KvTableDef { name: table_name, type_, primary_index, secondary_indices })
+        })?;
+        Ok(abi)
+    }
+
+    /// Serialize to the binary ABI form
+    ///
+    /// The output is byte-identical to [`Abieos::abi_json_to_bin`](crate::Abieos::abi_json_to_bin):
+    /// `variants` and `action_results` are always written, even for older ABIs that omit them,
+    /// and `kv_tables` is only written when not empty, sorted by table name.
+    pub fn to_bin(&self) -> Result<Vec<u8>, AbieosError> {
+        self.write_bin().map_err(invalid)
+    }
+
+    fn write_bin(&self) -> Result<Vec<u8>, String> {
+        fn vec<T>(out: &mut Vec<u8>, items: &[T], f: impl Fn(&mut Vec<u8>, &T) -> Result<(), String>) -> Result<(), String> {
+            stream::write_length(out, items.len())?;
+            items.iter().try_for_each(|item| f(out, item))
+        }
+        fn strings(out: &mut Vec<u8>, items: &[String]) -> Result<(), String> {
+            vec(out, items, |out, s| stream::write_string(out, s))
+        }
+        use stream::write_string as string;
+
+        let mut out = Vec::new();
+        string(&mut out, &self.version)?;
+        vec(&mut out, &self.types, |out, t| {
+            string(out, &t.new_type_name)?;
+            string(out, &t.type_)
+        })?;
+        vec(&mut out, &self.structs, |out, s| {
+            string(out, &s.name)?;
+            string(out, &s.base)?;
+            vec(out, &s.fields, |out, f| {
+                string(out, &f.name)?;
+                string(out, &f.type_)
+            })
+        })?;
+        vec(&mut out, &self.actions, |out, a| {
+            out.extend_from_slice(&a.name.as_u64().to_le_bytes());
+            string(out, &a.type_)?;
+            string(out, &a.ricardian_contract)
+        })?;
+        vec(&mut out, &self.tables, |out, t| {
+            out.extend_from_slice(&t.name.as_u64().to_le_bytes());
+            string(out, &t.index_type)?;
+            strings(out, &t.key_names)?;
+            strings(out, &t.key_types)?;
+            string(out, &t.type_)
+        })?;
+        vec(&mut out, &self.ricardian_clauses, |out, c| {
+            string(out, &c.id)?;
+            string(out, &c.body)
+        })?;
+        vec(&mut out, &self.error_messages, |out, e| {
+            out.extend_from_slice(&e.error_code.to_le_bytes());
+            string(out, &e.error_msg)
+        })?;
+        vec(&mut out, &self.abi_extensions, |out, e| {
+            out.extend_from_slice(&e.type_.to_le_bytes());
+            stream::write_bytes(out, &e.data)
+        })?;
+        vec(&mut out, &self.variants, |out, v| {
+            string(out, &v.name)?;
+            strings(out, &v.types)
+        })?;
+        vec(&mut out, &self.action_results, |out, r| {
+            out.extend_from_slice(&r.name.as_u64().to_le_bytes());
+            string(out, &r.result_type)
+        })?;
+        if !self.kv_tables.is_empty() {
+            // abieos keeps kv tables in a map, they are written in name order whatever the JSON key order
+            let mut kv_tables: Vec<&KvTableDef> = self.kv_tables.iter().collect();
+            kv_tables.sort_by_key(|t| t.name);
+            vec(&mut out, &kv_tables, |out, t| {
+                out.extend_from_slice(&t.name.as_u64().to_le_bytes());
+                string(out, &t.type_)?;
+                out.extend_from_slice(&t.primary_index.name.as_u64().to_le_bytes());
+                string(out, &t.primary_index.type_)?;
+                stream::write_length(out, t.secondary_indices.len())?;
+                t.secondary_indices.iter().try_for_each(|(name, type_)| {
+                    out.extend_from_slice(&name.as_u64().to_le_bytes());
+                    string(out, type_)
+                })
+            })?;
+        }
+        Ok(out)
+    }
+
+    // --- JSON ---
+
+    /// Parse the JSON ABI form
+    ///
+    /// Missing sections default to empty.
+    pub fn from_json(json: &str) -> Result<AbiDef, AbieosError> {
+        let value = JsonValue::parse(json).map_err(invalid)?;
+        Self::from_json_value(&value).map_err(invalid)
+    }
+
+    pub(crate) fn from_json_value(value: &JsonValue) -> Result<AbiDef, String> {
+        fn field<'v>(obj: &'v JsonValue, key: &str) -> Option<&'v JsonValue> {
+            obj.get(key).filter(|v| !matches!(v, JsonValue::Null))
+        }
+        fn string(obj: &JsonValue, key: &str) -> Result<String, String> {
+            match field(obj, key) {
+                None => Ok(String::new()),
+                Some(v) => v.as_str().map(str::to_string).ok_or_else(|| format!("\"{}\" must be a string", key)),
+            }
+        }
+        fn name(obj: &JsonValue, key: &str) -> Result<Name, String> {
+            string(obj, key)?.parse().map_err(|e: AbieosError| format!("\"{}\": {}", key, e))
+        }
+        fn vec<T>(obj: &JsonValue, key: &str, f: impl Fn(&JsonValue) -> Result<T, String>) -> Result<Vec<T>, String> {
+            match field(obj, key) {
+                None => Ok(Vec::new()),
+                Some(v) => v.as_array().ok_or_else(|| format!("\"{}\" must be an array", key))?.iter().map(f).collect(),
+            }
+        }
+        fn strings(obj: &JsonValue, key: &str) -> Result<Vec<String>, String> {
+            vec(obj, key, |v| v.as_str().map(str::to_string).ok_or_else(|| format!("\"{}\" must contain strings", key)))
+        }
+        fn integer<T: std::str::FromStr>(v: &JsonValue, key: &str) -> Result<T, String> {
+            let text = match v {
+                JsonValue::Number(n) => n.as_str(),
+                JsonValue::String(s) => s.as_str(),
+                _ => return Err(format!("\"{}\" must be an integer", key)),
+            };
+            text.parse().map_err(|_| format!("\"{}\" is not a valid integer: {}", key, text))
+        }
+
+        if value.as_object().is_none() {
+            return Err("ABI must be a JSON object".to_string());
+        }
+        let mut abi = AbiDef { version: string(value, "version")?, ..Default::default() };
+        abi.types = vec(value, "types", |t| Ok(TypeDef { new_type_name: string(t, "new_type_name")?, type_: string(t, "type")? }))?;
+        abi.structs = vec(value, "structs", |s| {
+            Ok(StructDef {
+                name: string(s, "name")?,
+                base: string(s, "base")?,
+                fields: vec(s, "fields", |f| Ok(FieldDef { name: string(f, "name")?, type_: string(f, "type")? }))?,
+            })
+        })?;
+        abi.actions = vec(value, "actions", |a| {
+            Ok(ActionDef { name: name(a, "name")?, type_: string(a, "type")?, ricardian_contract: string(a, "ricardian_contract")? })
+        })?;
+        abi.tables = vec(value, "tables", |t| {
+            Ok(TableDef {
+                name: name(t, "name")?,
+                index_type: string(t, "index_type")?,
+                key_names: strings(t, "key_names")?,
+                key_types: strings(t, "key_types")?,
+                type_: string(t, "type")?,
+            })
+        })?;
+        abi.ricardian_clauses = vec(value, "ricardian_clauses", |c| Ok(ClausePair { id: string(c, "id")?, body: string(c, "body")? }))?;
+        abi.error_messages = vec(value, "error_messages", |e| {
+            let code = field(e, "error_code").map_or(Ok(0), |v| integer(v, "error_code"))?;
+            Ok(ErrorMessage { error_code: code, error_msg: string(e, "error_msg")? })
+        })?;
+        abi.abi_extensions = vec(value, "abi_extensions", |e| {
+            // accepts both the [type, data] pair form and the {"type", "data"} object form
+            let (type_, data) = match e.as_array() {
+                Some([t, d]) => (t, d),
+                _ => match (e.get("type"), e.get("data")) {
+                    (Some(t), Some(d)) => (t, d),
+                    _ => return Err("invalid abi extension".to_string()),
+                },
+            };
+            let data = data.as_str().ok_or("abi extension data must be a hex string")?;
+            Ok(AbiExtension { type_: integer(type_, "type")?, data: stream::from_hex(data)? })
+        })?;
+        abi.variants = vec(value, "variants", |v| Ok(VariantDef { name: string(v, "name")?, types: strings(v, "types")? }))?;
+        abi.action_results = vec(value, "action_results", |r| {
+            Ok(ActionResultDef { name: name(r, "name")?, result_type: string(r, "result_type")? })
+        })?;
+        if let Some(kv) = field(value, "kv_tables") {
+            let tables = kv.as_object().ok_or("\"kv_tables\" must be an object")?;
+            for (table_name, t) in tables {
+                let primary = field(t, "primary_index").ok_or("kv table is missing \"primary_index\"")?;
+                let mut secondary_indices = BTreeMap::new();
+                if let Some(indices) = field(t, "secondary_indices") {
+                    for (index_name, index) in indices.as_object().ok_or("\"secondary_indices\" must be an object")? {
+                        let index_name: Name = index_name.parse().map_err(|e: AbieosError| e.to_string())?;
+                        secondary_indices.insert(index_name, string(index, "type")?);
+                    }
+                }
+                abi.kv_tables.push(KvTableDef {
+                    name: table_name.parse().map_err(|e: AbieosError| e.to_string())?,
+                    type_: string(t, "type")?,
+                    primary_index: PrimaryKeyIndexDef { name: name(primary, "name")?, type_: string(primary, "type")? },
+                    secondary_indices,
+                });
+            }
+        }
+        Ok(abi)
+    }
+
+    /// Serialize to the JSON ABI form
+    ///
+    /// `kv_tables` is only written when not empty.
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_json()
+    }
+
+    pub(crate) fn to_json_value(&self) -> JsonValue {
+        fn s(v: &str) -> JsonValue {
+            JsonValue::String(v.to_string())
+        }
+        fn n(v: Name) -> JsonValue {
+            JsonValue::String(v.to_string())
+        }
+        fn obj(members: Vec<(&str, JsonValue)>) -> JsonValue {
+            JsonValue::Object(members.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+        }
+        fn arr<T>(items: &[T], f: impl Fn(&T) -> JsonValue) -> JsonValue {
+            JsonValue::Array(items.iter().map(f).collect())
+        }
+        fn strings(items: &[String]) -> JsonValue {
+            arr(items, |v| s(v))
+        }
+
+        let mut members = vec![
+            ("version", s(&self.version)),
+            ("types", arr(&self.types, |t| obj(vec![("new_type_name", s(&t.new_type_name)), ("type", s(&t.type_))]))),
+            ("structs", arr(&self.structs, |st| {
+                obj(vec![
+                    ("name", s(&st.name)),
+                    ("base", s(&st.base)),
+                    ("fields", arr(&st.fields, |f| obj(vec![("name", s(&f.name)), ("type", s(&f.type_))]))),
+                ])
+            })),
+            ("actions", arr(&self.actions, |a| {
+                obj(vec![("name", n(a.name)), ("type", s(&a.type_)), ("ricardian_contract", s(&a.ricardian_contract))])
+            })),
+            ("tables", arr(&self.tables, |t| {
+                obj(vec![
+                    ("name", n(t.name)),
+                    ("index_type", s(&t.index_type)),
+                    ("key_names", strings(&t.key_names)),
+                    ("key_types", strings(&t.key_types)),
+                    ("type", s(&t.type_)),
+                ])
+            })),
+            ("ricardian_clauses", arr(&self.ricardian_clauses, |c| obj(vec![("id", s(&c.id)), ("body", s(&c.body))]))),
+            ("error_messages", arr(&self.error_messages, |e| {
+                obj(vec![("error_code", s(&e.error_code.to_string())), ("error_msg", s(&e.error_msg))])
+            })),
+            ("abi_extensions", arr(&self.abi_extensions, |e| {
+                JsonValue::Array(vec![JsonValue::Number(e.type_.to_string()), s(&stream::to_hex(&e.data))])
+            })),
+            ("variants", arr(&self.variants, |v| obj(vec![("name", s(&v.name)), ("types", strings(&v.types))]))),
+            ("action_results", arr(&self.action_results, |r| obj(vec![("name", n(r.name)), ("result_type", s(&r.result_type))]))),
+        ];
+        if !self.kv_tables.is_empty() {
+            let tables = self.kv_tables.iter().map(|t| {
+                let indices = t.secondary_indices.iter().map(|(k, v)| (k.to_string(), obj(vec![("type", s(v))]))).collect();
+                let table = obj(vec![
+                    ("type", s(&t.type_)),
+                    ("primary_index", obj(vec![("name", n(t.primary_index.name)), ("type", s(&t.primary_index.type_))])),
+                    ("secondary_indices", JsonValue::Object(indices)),
+                ]);
+                (t.name.to_string(), table)
+            });
+            members.push(("kv_tables", JsonValue::Object(tables.collect())));
+        }
+        obj(members)
+    }
+}
@@ -7,6 +7,7 @@ pub enum AbieosError {
     StringToName,
     NameToString,
     NameTooLong,
+    InvalidName(String),
     AbiNotLoaded,
     FileRead,
     SetAbi(String),
@@ -19,12 +20,26 @@ pub enum AbieosError {
     AbiBinToJson(String),
     AbiJsonToBin(String),
     BinToJson(String),
+    InvalidAbi(String),
+    Serialize(String),
+    Deserialize(String),
+    InvalidSymbol(String),
+    InvalidAsset(String),
+    UnknownAction(String),
+    UnknownActionResult(String),
+    UnknownTable(String),
+    InvalidSecondaryKey(String),
+    InvalidPackedTransaction(String),
+    InvalidKey(String),
+    InvalidSignature(String),
+    InvalidPolicyDiff(String),
 }
 
 impl Display for AbieosError {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         match self {
             AbieosError::NameTooLong => write!(f, "Name is too long"),
+            AbieosError::InvalidName(e) => write!(f, "Invalid name: {}", e),
             AbieosError::StringToName => write!(f, "Failed to convert string to name"),
             AbieosError::NameToString => write!(f, "Failed to convert name to string"),
             AbieosError::SetAbi(e) => write!(f, "Failed to set ABI: {}", e),
@@ -39,6 +54,19 @@ impl Display for AbieosError {
             AbieosError::AbiNotLoaded => write!(f, "ABI not loaded in this contract"),
             AbieosError::FileRead => write!(f, "Failed to read file"),
             AbieosError::BinToJson(e) => write!(f, "Failed to convert binary to JSON: {}", e),
+            AbieosError::InvalidAbi(e) => write!(f, "Invalid ABI: {}", e),
+            AbieosError::Serialize(e) => write!(f, "Failed to serialize value: {}", e),
+            AbieosError::Deserialize(e) => write!(f, "Failed to deserialize value: {}", e),
+            AbieosError::InvalidSymbol(e) => write!(f, "Invalid symbol: {}", e),
+            AbieosError::InvalidAsset(e) => write!(f, "Invalid asset: {}", e),
+            AbieosError::UnknownAction(e) => write!(f, "Unknown action: {}", e),
+            AbieosError::UnknownActionResult(e) => write!(f, "No result type for action: {}", e),
+            AbieosError::UnknownTable(e) => write!(f, "Unknown table: {}", e),
+            AbieosError::InvalidSecondaryKey(e) => write!(f, "Invalid secondary key: {}", e),
+            AbieosError::InvalidPackedTransaction(e) => write!(f, "Invalid packed transaction: {}", e),
+            AbieosError::InvalidKey(e) => write!(f, "Invalid key: {}", e),
+            AbieosError::InvalidSignature(e) => write!(f, "Invalid signature: {}", e),
+            AbieosError::InvalidPolicyDiff(e) => write!(f, "Invalid policy diff: {}", e),
             AbieosError::Unknown => write!(f, "Unknown error occurred"),
         }
     }
@@ -0,0 +1,231 @@
+//! # Symbols and assets
+//!
+//!  Token symbols and amounts, converted in pure Rust
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::builtin;
+use crate::{AbieosError, Name};
+
+/// A symbol code of up to 7 uppercase letters (`EOS`, `WAX`...)
+///
+/// ```rust
+/// use rs_abieos::SymbolCode;
+///
+/// let code: SymbolCode = "EOS".parse().unwrap();
+/// assert_eq!(code.as_u64(), 0x534f45);
+/// assert!("eos".parse::<SymbolCode>().is_err());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SymbolCode(u64);
+
+impl SymbolCode {
+    /// Create a symbol code from its raw `u64` value
+    pub const fn new(value: u64) -> SymbolCode {
+        SymbolCode(value)
+    }
+
+    /// Get the raw `u64` value
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for SymbolCode {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        builtin::parse_symbol_code(s).map(SymbolCode).map_err(AbieosError::InvalidSymbol)
+    }
+}
+
+impl Display for SymbolCode {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&builtin::format_symbol_code(self.0))
+    }
+}
+
+impl Debug for SymbolCode {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "SymbolCode({})", self)
+    }
+}
+
+/// A token symbol: precision and symbol code, formatted as `4,EOS`
+///
+/// ```rust
+/// use rs_abieos::Symbol;
+///
+/// let symbol: Symbol = "4,EOS".parse().unwrap();
+/// assert_eq!(symbol.precision(), 4);
+/// assert_eq!(symbol.code().to_string(), "EOS");
+/// assert_eq!(symbol.as_u64(), 0x534f4504);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u64);
+
+impl Symbol {
+    /// Create a symbol from its precision and code
+    pub const fn new(precision: u8, code: SymbolCode) -> Symbol {
+        Symbol((code.0 << 8) | precision as u64)
+    }
+
+    /// Create a symbol from its raw `u64` value
+    pub const fn from_u64(value: u64) -> Symbol {
+        Symbol(value)
+    }
+
+    /// Get the raw `u64` value
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Number of decimal places
+    pub const fn precision(&self) -> u8 {
+        self.0 as u8
+    }
+
+    pub const fn code(&self) -> SymbolCode {
+        SymbolCode(self.0 >> 8)
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        builtin::parse_symbol(s).map(Symbol).map_err(AbieosError::InvalidSymbol)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&builtin::format_symbol(self.0))
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Symbol({})", self)
+    }
+}
+
+/// A token amount, formatted as `1.0000 EOS`
+///
+/// The amount is stored in the smallest unit of the symbol precision.
+///
+/// ```rust
+/// use rs_abieos::Asset;
+///
+/// let asset: Asset = "-1.0000 EOS".parse().unwrap();
+/// assert_eq!(asset.amount, -10000);
+/// assert_eq!(asset.symbol.to_string(), "4,EOS");
+/// assert_eq!(asset.to_string(), "-1.0000 EOS");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Asset {
+    pub amount: i64,
+    pub symbol: Symbol,
+}
+
+impl Asset {
+    pub const fn new(amount: i64, symbol: Symbol) -> Asset {
+        Asset { amount, symbol }
+    }
+}
+
+impl FromStr for Asset {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, symbol) = builtin::parse_asset(s).map_err(AbieosError::InvalidAsset)?;
+        Ok(Asset { amount, symbol: Symbol(symbol) })
+    }
+}
+
+impl Display for Asset {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&builtin::format_asset(self.amount, self.symbol.0))
+    }
+}
+
+impl Debug for Asset {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Asset({})", self)
+    }
+}
+
+/// An asset along with the contract issuing it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExtendedAsset {
+    pub quantity: Asset,
+    pub contract: Name,
+}
+
+// Symbols and assets are written as strings in human-readable formats, and with their
+// wire encoding otherwise, like `Name`.
+
+impl Serialize for SymbolCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SymbolCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+        } else {
+            u64::deserialize(deserializer).map(SymbolCode)
+        }
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+        } else {
+            u64::deserialize(deserializer).map(Symbol)
+        }
+    }
+}
+
+impl Serialize for Asset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.amount, self.symbol.0).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Asset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+        } else {
+            let (amount, symbol) = <(i64, u64)>::deserialize(deserializer)?;
+            Ok(Asset { amount, symbol: Symbol(symbol) })
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! # Async facade
+//!
+//!  [`AsyncAbieos`], running the codecs on dedicated worker threads, enabled by the `tokio` feature
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use tokio::sync::oneshot;
+
+use crate::{AbiDef, AbiRegistry, Abieos, AbieosError, Name};
+
+type Job = Box<dyn FnOnce(&AbiRegistry) + Send>;
+
+/// Async interface to a pool of worker threads, each with its own [`Abieos`] context
+///
+/// Serialization runs on the workers, so decoding large blocks does not stall the async runtime.
+/// ABIs are shared by all workers through an [`AbiRegistry`], and errors are the same
+/// [`AbieosError`] values the synchronous methods return.
+///
+/// The handle is cheap to clone, the workers stop once every clone is dropped.
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), rs_abieos::AbieosError> {
+/// use rs_abieos::{AsyncAbieos, Name};
+///
+/// let abieos = AsyncAbieos::new(4);
+/// let token = Name::try_from("eosio.token").unwrap();
+/// abieos.set_abi_json(token, &std::fs::read_to_string("eosio.token.abi").unwrap()).await?;
+/// let json = abieos.hex_to_json("eosio.token", "transfer", "0000000000855C34...").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncAbieos {
+    jobs: Sender<Job>,
+}
+
+impl AsyncAbieos {
+    /// Start `threads` worker threads (at least one)
+    pub fn new(threads: usize) -> AsyncAbieos {
+        let (sender, receiver) = channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let registry = AbiRegistry::new();
+        for i in 0..threads.max(1) {
+            let receiver = receiver.clone();
+            let registry = registry.clone();
+            thread::Builder::new()
+                .name(format!("abieos-worker-{}", i))
+                .spawn(move || worker(&receiver, &registry))
+                .expect("failed to spawn abieos worker thread");
+        }
+        AsyncAbieos { jobs: sender }
+    }
+
+    /// Run `f` with the context of a worker thread
+    ///
+    /// The context has all the ABIs loaded through this handle. Fails with
+    /// [`AbieosError::Unknown`] if the worker panics.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, AbieosError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Abieos) -> Result<T, AbieosError> + Send + 'static,
+    {
+        self.dispatch(move |registry| registry.with_context(f)).await
+    }
+
+    async fn dispatch<T, F>(&self, f: F) -> Result<T, AbieosError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AbiRegistry) -> Result<T, AbieosError> + Send + 'static,
+    {
+        let (reply, result) = oneshot::channel();
+        let job: Job = Box::new(move |registry| {
+            // the caller may have stopped waiting
+            let _ = reply.send(f(registry));
+        });
+        if self.jobs.send(job).is_err() {
+            return Err(AbieosError::Unknown);
+        }
+        result.await.unwrap_or(Err(AbieosError::Unknown))
+    }
+
+    /// Load a contract ABI into all workers (JSON format)
+    pub async fn set_abi_json(&self, contract: impl Into<Name>, abi_json: &str) -> Result<bool, AbieosError> {
+        let (contract, abi_json) = (contract.into(), abi_json.to_string());
+        self.dispatch(move |registry| registry.set_abi_json(contract, &abi_json)).await
+    }
+
+    /// Load a contract ABI into all workers (HEX format)
+    pub async fn set_abi_hex(&self, contract: impl Into<Name>, abi_hex: &str) -> Result<bool, AbieosError> {
+        let (contract, abi_hex) = (contract.into(), abi_hex.to_string());
+        self.dispatch(move |registry| registry.set_abi_hex(contract, &abi_hex)).await
+    }
+
+    /// Load a contract ABI into all workers (binary format)
+    pub async fn set_abi_bin(&self, contract: impl Into<Name>, abi_bin: &[u8]) -> Result<bool, AbieosError> {
+        let (contract, abi_bin) = (contract.into(), abi_bin.to_vec());
+        self.dispatch(move |registry| registry.set_abi_bin(contract, &abi_bin)).await
+    }
+
+    /// Load a contract ABI into all workers from a parsed [`AbiDef`]
+    pub async fn set_abi_def(&self, contract: impl Into<Name>, abi: &AbiDef) -> Result<bool, AbieosError> {
+        self.set_abi_bin(contract, &abi.to_bin()?).await
+    }
+
+    /// Delete a contract from all workers
+    ///
+    /// Returns `false` if no ABI was loaded. Like the `set_abi_*` methods, the deletion is queued
+    /// with the other jobs, so it is ordered with the ABI changes requested before it.
+    pub async fn delete_contract(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
+        let contract = contract.into();
+        self.dispatch(move |registry| registry.delete_contract(contract)).await
+    }
+
+    /// Returns `true` if an ABI is loaded for the contract
+    ///
+    /// The check is queued after the ABI changes requested before it.
+    pub async fn has_contract(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
+        let contract = contract.into();
+        self.dispatch(move |registry| Ok(registry.has_contract(contract))).await
+    }
+
+    /// Serialize JSON into binary (output as HEX)
+    pub async fn json_to_hex(&self, account: &str, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        let (account, datatype, json) = (account.to_string(), datatype.to_string(), json.to_string());
+        self.run(move |abieos| abieos.json_to_hex(&account, &datatype, &json)).await
+    }
+
+    /// Serialize JSON into binary (output as binary)
+    pub async fn json_to_bin(&self, account: &str, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let (account, datatype, json) = (account.to_string(), datatype.to_string(), json.to_string());
+        self.run(move |abieos| abieos.json_to_bin(&account, &datatype, &json)).await
+    }
+
+    /// Deserialize HEX string into JSON
+    pub async fn hex_to_json(&self, account: &str, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        let (account, datatype, hex) = (account.to_string(), datatype.to_string(), hex.to_string());
+        self.run(move |abieos| abieos.hex_to_json(&account, &datatype, &hex)).await
+    }
+
+    /// Deserialize Binary into JSON
+    ///
+    /// The data is copied to the worker, use [`AsyncAbieos::bin_to_json_owned`] to move it instead.
+    pub async fn bin_to_json(&self, account: &str, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.bin_to_json_owned(account, datatype, bin.to_vec()).await
+    }
+
+    /// Deserialize Binary into JSON, moving the data to the worker
+    pub async fn bin_to_json_owned(&self, account: &str, datatype: &str, bin: Vec<u8>) -> Result<String, AbieosError> {
+        let (account, datatype) = (account.to_string(), datatype.to_string());
+        self.run(move |abieos| abieos.bin_to_json(&account, &datatype, &bin)).await
+    }
+
+    /// Get the type for an action
+    pub async fn get_type_for_action(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        let (contract, action) = (contract.into(), action.into());
+        self.run(move |abieos| abieos.get_type_for_action(contract, action)).await
+    }
+
+    /// Get the type for a table
+    pub async fn get_type_for_table(&self, contract: impl Into<Name>, table: impl Into<Name>) -> Result<String, AbieosError> {
+        let (contract, table) = (contract.into(), table.into());
+        self.run(move |abieos| abieos.get_type_for_table(contract, table)).await
+    }
+}
+
+/// Run jobs until every handle is dropped
+fn worker(jobs: &Mutex<Receiver<Job>>, registry: &AbiRegistry) {
+    loop {
+        let job = match jobs.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        // a panicking job drops its reply, the worker keeps running
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| job(registry)));
+    }
+}
@@ -0,0 +1,150 @@
+//! # BLS keys and signatures
+//!
+//!  [`BlsPublicKey`] and [`BlsSignature`] of Savanna finalizers, in the `PUB_BLS_` and `SIG_BLS_`
+//!  formats of Spring
+//!
+//! Both hold BLS12-381 points in the affine, little-endian and non-Montgomery form Spring stores
+//! and serializes: public keys are G1 points (`x` then `y`), signatures and proofs of possession
+//! are G2 points (`x.c0`, `x.c1`, `y.c0`, `y.c1`). Their binary encoding is the point prefixed
+//! with its size as a varuint32, like Spring serializes them, with no type prefix.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::stream::{self, InputStream};
+use crate::{keys, AbieosError};
+
+/// Size of an affine G1 point
+pub(crate) const PUBLIC_KEY_SIZE: usize = 96;
+/// Size of an affine G2 point
+pub(crate) const SIGNATURE_SIZE: usize = 192;
+
+/// BLS public key of a finalizer, a G1 point
+///
+/// Parsed from `PUB_BLS_` strings, whose checksums are verified. Whether the point is on the curve
+/// is only checked by [`BlsPublicKey::is_valid`], with the `bls` feature.
+///
+/// ```rust
+/// use rs_abieos::BlsPublicKey;
+///
+/// let text = "PUB_BLS_qVbh4IjYZpRGo8U_0spBUM-u-r_G0fMo4MzLZRsKWmm5uyeQTp74YFaMN9IDWPoVVT5rj_Tw1gvps6K9_OZ6sabkJJzug3uGfjA6qiaLbLh5Fnafwv-nVgzzzBlU2kwRrcHc8Q";
+/// let key: BlsPublicKey = text.parse().unwrap();
+/// assert_eq!(key.to_bin().len(), 1 + 96);
+/// assert_eq!(key.to_string(), text);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlsPublicKey([u8; PUBLIC_KEY_SIZE]);
+
+/// BLS signature or proof of possession, a G2 point
+///
+/// Parsed from `SIG_BLS_` strings, whose checksums are verified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlsSignature([u8; SIGNATURE_SIZE]);
+
+/// Codecs shared by the BLS types, which only differ by size, string prefix and error
+macro_rules! bls_codec {
+    ($type:ident, $size:expr, $prefix:literal, $error:ident, $what:literal) => {
+        impl $type {
+            /// Wrap the point data, in Spring's affine little-endian form
+            pub const fn from_bytes(bytes: [u8; $size]) -> $type {
+                $type(bytes)
+            }
+
+            /// Point data, in Spring's affine little-endian form
+            pub fn as_bytes(&self) -> &[u8; $size] {
+                &self.0
+            }
+
+            #[doc = concat!("Decode a ", $what, " from binary, which must hold exactly one ", $what)]
+            pub fn from_bin(bin: &[u8]) -> Result<$type, AbieosError> {
+                let mut s = InputStream::new(bin);
+                let len = s.read_varuint32().map_err(AbieosError::$error)?;
+                if len != $size as u32 {
+                    return Err(AbieosError::$error(format!("{} must be {} bytes, got {}", $what, $size, len)));
+                }
+                let bytes = s.read_array().map_err(AbieosError::$error)?;
+                if s.remaining() > 0 {
+                    return Err(AbieosError::$error(s.error(concat!("extra data after ", $what))));
+                }
+                Ok($type(bytes))
+            }
+
+            #[doc = concat!("Encode the ", $what, " in binary, its size then the point")]
+            pub fn to_bin(&self) -> Vec<u8> {
+                let mut out = Vec::with_capacity(2 + $size);
+                stream::write_varuint32(&mut out, $size as u32);
+                out.extend_from_slice(&self.0);
+                out
+            }
+        }
+
+        #[doc = concat!("Parses `", $prefix, "_BLS_` strings")]
+        impl FromStr for $type {
+            type Err = AbieosError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                keys::bls_from_string(s, $prefix).map($type).map_err(AbieosError::$error)
+            }
+        }
+
+        impl Display for $type {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str(&keys::to_bls_string($prefix, &self.0))
+            }
+        }
+
+        /// Serialized as a string in human-readable formats, and as the point bytes otherwise
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    return serializer.collect_str(self);
+                }
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    return String::deserialize(deserializer)?.parse().map_err(D::Error::custom);
+                }
+
+                struct PointVisitor;
+
+                impl<'de> Visitor<'de> for PointVisitor {
+                    type Value = $type;
+
+                    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                        f.write_str(concat!("a BLS ", $what))
+                    }
+
+                    fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> Result<$type, E> {
+                        let bytes = bytes.try_into().map_err(|_| {
+                            E::custom(format!("{} must be {} bytes, got {}", $what, $size, bytes.len()))
+                        })?;
+                        Ok($type(bytes))
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$type, A::Error> {
+                        let mut bytes = [0u8; $size];
+                        for byte in bytes.iter_mut() {
+                            *byte = seq.next_element()?.ok_or_else(|| A::Error::custom(concat!("missing ", $what, " data")))?;
+                        }
+                        if seq.next_element::<u8>()?.is_some() {
+                            return Err(A::Error::custom(concat!("extra ", $what, " data")));
+                        }
+                        Ok($type(bytes))
+                    }
+                }
+
+                deserializer.deserialize_bytes(PointVisitor)
+            }
+        }
+    };
+}
+
+bls_codec!(BlsPublicKey, PUBLIC_KEY_SIZE, "PUB", InvalidKey, "public key");
+bls_codec!(BlsSignature, SIGNATURE_SIZE, "SIG", InvalidSignature, "signature");
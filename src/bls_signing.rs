@@ -0,0 +1,149 @@
+//! # BLS signing
+//!
+//!  [`BlsPrivateKey`], signing and verification of BLS signatures and proofs of possession with
+//!  the ciphersuites of Spring finalizers
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use blst::min_pk;
+use blst::BLST_ERROR;
+
+use crate::bls::{PUBLIC_KEY_SIZE, SIGNATURE_SIZE};
+use crate::{keys, AbieosError, BlsPublicKey, BlsSignature};
+
+/// Domain separation tag of signatures
+const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// Domain separation tag of proofs of possession
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// Size of a base field element
+const FP_SIZE: usize = 48;
+
+/// Convert a point between Spring's layout and the one of blst: every field element is reversed
+/// from little to big-endian, and the two halves of G2 coordinates are swapped (`c1` first)
+fn swap_layout<const N: usize>(point: &[u8; N]) -> [u8; N] {
+    let mut out = [0u8; N];
+    for (i, element) in point.chunks(FP_SIZE).enumerate() {
+        let j = if N == SIGNATURE_SIZE { i ^ 1 } else { i };
+        for (to, from) in out[j * FP_SIZE..][..FP_SIZE].iter_mut().zip(element.iter().rev()) {
+            *to = *from;
+        }
+    }
+    out
+}
+
+fn to_blst_key(key: &BlsPublicKey) -> Option<min_pk::PublicKey> {
+    let key = min_pk::PublicKey::deserialize(&swap_layout(key.as_bytes())).ok()?;
+    key.validate().ok().map(|_| key)
+}
+
+fn from_blst_key(key: &min_pk::PublicKey) -> BlsPublicKey {
+    BlsPublicKey::from_bytes(swap_layout::<PUBLIC_KEY_SIZE>(&key.serialize()))
+}
+
+fn from_blst_signature(signature: &min_pk::Signature) -> BlsSignature {
+    BlsSignature::from_bytes(swap_layout::<SIGNATURE_SIZE>(&signature.serialize()))
+}
+
+fn verify(key: &BlsPublicKey, message: &[u8], signature: &BlsSignature, dst: &[u8]) -> bool {
+    let Some(key) = to_blst_key(key) else { return false };
+    let Ok(signature) = min_pk::Signature::deserialize(&swap_layout(signature.as_bytes())) else { return false };
+    signature.verify(true, message, dst, &[], &key, false) == BLST_ERROR::BLST_SUCCESS
+}
+
+impl BlsPublicKey {
+    /// Whether the key is a point of the G1 subgroup, other than the point at infinity
+    pub fn is_valid(&self) -> bool {
+        to_blst_key(self).is_some()
+    }
+
+    /// Verify a signature of `message` made with this key
+    pub fn verify(&self, message: &[u8], signature: &BlsSignature) -> bool {
+        verify(self, message, signature, SIGNATURE_DST)
+    }
+
+    /// Verify a proof of possession of this key, as given to `regfinkey`
+    pub fn verify_proof_of_possession(&self, proof: &BlsSignature) -> bool {
+        verify(self, self.as_bytes(), proof, POP_DST)
+    }
+}
+
+/// BLS private key of a finalizer
+///
+/// Parsed from and displayed as `PVT_BLS_` strings. `Debug` only shows the public key.
+///
+/// ```rust
+/// use rs_abieos::BlsPrivateKey;
+///
+/// let key = BlsPrivateKey::from_seed(&[7; 32]).unwrap();
+/// let public_key = key.public_key();
+/// assert!(public_key.verify(b"message", &key.sign(b"message")));
+/// assert!(public_key.verify_proof_of_possession(&key.proof_of_possession()));
+/// ```
+#[derive(Clone)]
+pub struct BlsPrivateKey(min_pk::SecretKey);
+
+fn invalid(message: impl Into<String>) -> AbieosError {
+    AbieosError::InvalidKey(message.into())
+}
+
+impl BlsPrivateKey {
+    /// Derive a key from at least 32 bytes of key material, with the `KeyGen` of the IETF BLS
+    /// signature draft
+    pub fn from_seed(seed: &[u8]) -> Result<BlsPrivateKey, AbieosError> {
+        min_pk::SecretKey::key_gen(seed, &[])
+            .map(BlsPrivateKey)
+            .map_err(|_| invalid("BLS key seed must be at least 32 bytes"))
+    }
+
+    /// Key from its scalar, little-endian like in `PVT_BLS_` strings
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<BlsPrivateKey, AbieosError> {
+        let mut big_endian = *bytes;
+        big_endian.reverse();
+        min_pk::SecretKey::from_bytes(&big_endian)
+            .map(BlsPrivateKey)
+            .map_err(|_| invalid("BLS private key is out of range"))
+    }
+
+    /// Scalar of the key, little-endian
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = self.0.to_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    pub fn public_key(&self) -> BlsPublicKey {
+        from_blst_key(&self.0.sk_to_pk())
+    }
+
+    /// Sign a message, finalizers sign the finality digest of blocks
+    pub fn sign(&self, message: &[u8]) -> BlsSignature {
+        from_blst_signature(&self.0.sign(message, SIGNATURE_DST, &[]))
+    }
+
+    /// Proof of possession of the key, a signature of the public key
+    pub fn proof_of_possession(&self) -> BlsSignature {
+        from_blst_signature(&self.0.sign(self.public_key().as_bytes(), POP_DST, &[]))
+    }
+}
+
+/// Parses `PVT_BLS_` strings
+impl FromStr for BlsPrivateKey {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BlsPrivateKey::from_bytes(&keys::bls_from_string(s, "PVT").map_err(invalid)?)
+    }
+}
+
+impl Display for BlsPrivateKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&keys::to_bls_string("PVT", &self.to_bytes()))
+    }
+}
+
+impl Debug for BlsPrivateKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_tuple("BlsPrivateKey").field(&self.public_key().to_string()).finish()
+    }
+}
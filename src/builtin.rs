@@ -0,0 +1,543 @@
+//! Antelope builtin types, converted between JSON and the wire format
+//!
+//! The JSON forms follow abieos: 64 and 128-bit integers are quoted, bytes and checksums
+//! are uppercase hex and time points use millisecond precision.
+
+use crate::json::JsonValue;
+use crate::keys;
+use crate::stream::{self, InputStream};
+use crate::Name;
+
+/// Builtin ABI types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Builtin {
+    Bool,
+    Int8,
+    Uint8,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Int64,
+    Uint64,
+    Int128,
+    Uint128,
+    VarUint32,
+    VarInt32,
+    Float32,
+    Float64,
+    Float128,
+    TimePoint,
+    TimePointSec,
+    BlockTimestamp,
+    Name,
+    Bytes,
+    Bitset,
+    String,
+    Checksum160,
+    Checksum256,
+    Checksum512,
+    PublicKey,
+    PrivateKey,
+    Signature,
+    Symbol,
+    SymbolCode,
+    Asset,
+    ExtendedAsset,
+}
+
+impl Builtin {
+    pub(crate) fn from_name(name: &str) -> Option<Builtin> {
+        Some(match name {
+            "bool" => Builtin::Bool,
+            "int8" => Builtin::Int8,
+            "uint8" => Builtin::Uint8,
+            "int16" => Builtin::Int16,
+            "uint16" => Builtin::Uint16,
+            "int32" => Builtin::Int32,
+            "uint32" => Builtin::Uint32,
+            "int64" => Builtin::Int64,
+            "uint64" => Builtin::Uint64,
+            "int128" => Builtin::Int128,
+            "uint128" => Builtin::Uint128,
+            "varuint32" => Builtin::VarUint32,
+            "varint32" => Builtin::VarInt32,
+            "float32" => Builtin::Float32,
+            "float64" => Builtin::Float64,
+            "float128" => Builtin::Float128,
+            "time_point" => Builtin::TimePoint,
+            "time_point_sec" => Builtin::TimePointSec,
+            "block_timestamp_type" => Builtin::BlockTimestamp,
+            "name" => Builtin::Name,
+            "bytes" => Builtin::Bytes,
+            "bitset" => Builtin::Bitset,
+            "string" => Builtin::String,
+            "checksum160" => Builtin::Checksum160,
+            "checksum256" => Builtin::Checksum256,
+            "checksum512" => Builtin::Checksum512,
+            "public_key" => Builtin::PublicKey,
+            "private_key" => Builtin::PrivateKey,
+            "signature" => Builtin::Signature,
+            "symbol" => Builtin::Symbol,
+            "symbol_code" => Builtin::SymbolCode,
+            "asset" => Builtin::Asset,
+            "extended_asset" => Builtin::ExtendedAsset,
+            _ => return None,
+        })
+    }
+
+    /// ABI type name
+    pub(crate) fn type_name(self) -> &'static str {
+        match self {
+            Builtin::Bool => "bool",
+            Builtin::Int8 => "int8",
+            Builtin::Uint8 => "uint8",
+            Builtin::Int16 => "int16",
+            Builtin::Uint16 => "uint16",
+            Builtin::Int32 => "int32",
+            Builtin::Uint32 => "uint32",
+            Builtin::Int64 => "int64",
+            Builtin::Uint64 => "uint64",
+            Builtin::Int128 => "int128",
+            Builtin::Uint128 => "uint128",
+            Builtin::VarUint32 => "varuint32",
+            Builtin::VarInt32 => "varint32",
+            Builtin::Float32 => "float32",
+            Builtin::Float64 => "float64",
+            Builtin::Float128 => "float128",
+            Builtin::TimePoint => "time_point",
+            Builtin::TimePointSec => "time_point_sec",
+            Builtin::BlockTimestamp => "block_timestamp_type",
+            Builtin::Name => "name",
+            Builtin::Bytes => "bytes",
+            Builtin::Bitset => "bitset",
+            Builtin::String => "string",
+            Builtin::Checksum160 => "checksum160",
+            Builtin::Checksum256 => "checksum256",
+            Builtin::Checksum512 => "checksum512",
+            Builtin::PublicKey => "public_key",
+            Builtin::PrivateKey => "private_key",
+            Builtin::Signature => "signature",
+            Builtin::Symbol => "symbol",
+            Builtin::SymbolCode => "symbol_code",
+            Builtin::Asset => "asset",
+            Builtin::ExtendedAsset => "extended_asset",
+        }
+    }
+
+    /// Encode a JSON value, appending it to `out`
+    pub(crate) fn json_to_bin(self, value: &JsonValue, out: &mut Vec<u8>) -> Result<(), String> {
+        match self {
+            Builtin::Bool => match value {
+                JsonValue::Bool(b) => out.push(*b as u8),
+                _ => return Err(format!("expected bool, got {}", value.kind())),
+            },
+            Builtin::Int8 => out.extend_from_slice(&int::<i8>(value)?.to_le_bytes()),
+            Builtin::Uint8 => out.extend_from_slice(&int::<u8>(value)?.to_le_bytes()),
+            Builtin::Int16 => out.extend_from_slice(&int::<i16>(value)?.to_le_bytes()),
+            Builtin::Uint16 => out.extend_from_slice(&int::<u16>(value)?.to_le_bytes()),
+            Builtin::Int32 => out.extend_from_slice(&int::<i32>(value)?.to_le_bytes()),
+            Builtin::Uint32 => out.extend_from_slice(&int::<u32>(value)?.to_le_bytes()),
+            Builtin::Int64 => out.extend_from_slice(&int::<i64>(value)?.to_le_bytes()),
+            Builtin::Uint64 => out.extend_from_slice(&int::<u64>(value)?.to_le_bytes()),
+            Builtin::Int128 => out.extend_from_slice(&int::<i128>(value)?.to_le_bytes()),
+            Builtin::Uint128 => out.extend_from_slice(&int::<u128>(value)?.to_le_bytes()),
+            Builtin::VarUint32 => stream::write_varuint32(out, int::<u32>(value)?),
+            Builtin::VarInt32 => stream::write_varint32(out, int::<i32>(value)?),
+            Builtin::Float32 => {
+                let v = float(value)?;
+                // `as` would round finite values beyond the f32 range to infinity
+                if v.is_finite() && !(v as f32).is_finite() {
+                    return Err(format!("float32 is out of range: {}", v));
+                }
+                out.extend_from_slice(&(v as f32).to_le_bytes());
+            }
+            Builtin::Float64 => out.extend_from_slice(&float(value)?.to_le_bytes()),
+            Builtin::Float128 => {
+                let s = string(value)?;
+                out.extend_from_slice(&fixed_hex(s.strip_prefix("0x").unwrap_or(s), 16)?);
+            }
+            Builtin::TimePoint => out.extend_from_slice(&parse_time_point(string(value)?)?.to_le_bytes()),
+            Builtin::TimePointSec => {
+                let seconds = parse_time_point(string(value)?)?.div_euclid(1_000_000);
+                let seconds = u32::try_from(seconds).map_err(|_| "time_point_sec is out of range".to_string())?;
+                out.extend_from_slice(&seconds.to_le_bytes());
+            }
+            Builtin::BlockTimestamp => {
+                let ms = parse_time_point(string(value)?)?.div_euclid(1000) - BLOCK_TIMESTAMP_EPOCH_MS;
+                let slot = u32::try_from(ms / BLOCK_INTERVAL_MS).map_err(|_| "block_timestamp_type is out of range".to_string())?;
+                out.extend_from_slice(&slot.to_le_bytes());
+            }
+            Builtin::Name => {
+                let name: Name = string(value)?.parse().map_err(|e: crate::AbieosError| e.to_string())?;
+                out.extend_from_slice(&name.as_u64().to_le_bytes());
+            }
+            Builtin::Bytes => stream::write_bytes(out, &stream::from_hex(string(value)?)?)?,
+            Builtin::Bitset => write_bitset(out, &parse_bitset(string(value)?)?)?,
+            Builtin::String => stream::write_string(out, string(value)?)?,
+            Builtin::Checksum160 => out.extend_from_slice(&fixed_hex(string(value)?, 20)?),
+            Builtin::Checksum256 => out.extend_from_slice(&fixed_hex(string(value)?, 32)?),
+            Builtin::Checksum512 => out.extend_from_slice(&fixed_hex(string(value)?, 64)?),
+            Builtin::PublicKey => out.extend_from_slice(&keys::public_key_from_string(string(value)?)?),
+            Builtin::PrivateKey => out.extend_from_slice(&keys::private_key_from_string(string(value)?)?),
+            Builtin::Signature => out.extend_from_slice(&keys::signature_from_string(string(value)?)?),
+            Builtin::Symbol => out.extend_from_slice(&parse_symbol(string(value)?)?.to_le_bytes()),
+            Builtin::SymbolCode => out.extend_from_slice(&parse_symbol_code(string(value)?)?.to_le_bytes()),
+            Builtin::Asset => {
+                let (amount, symbol) = parse_asset(string(value)?)?;
+                out.extend_from_slice(&amount.to_le_bytes());
+                out.extend_from_slice(&symbol.to_le_bytes());
+            }
+            Builtin::ExtendedAsset => {
+                if value.as_object().is_none() {
+                    return Err(format!("expected object, got {}", value.kind()));
+                }
+                let quantity = value.get("quantity").ok_or("missing field \"quantity\"")?;
+                let contract = value.get("contract").ok_or("missing field \"contract\"")?;
+                Builtin::Asset.json_to_bin(quantity, out)?;
+                Builtin::Name.json_to_bin(contract, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode a value from the stream
+    pub(crate) fn bin_to_json(self, s: &mut InputStream) -> Result<JsonValue, String> {
+        fn number(n: impl ToString) -> JsonValue {
+            JsonValue::Number(n.to_string())
+        }
+        fn quoted(n: impl ToString) -> JsonValue {
+            JsonValue::String(n.to_string())
+        }
+        Ok(match self {
+            Builtin::Bool => match s.read_u8()? {
+                0 => JsonValue::Bool(false),
+                1 => JsonValue::Bool(true),
+                _ => return Err(s.error("invalid bool")),
+            },
+            Builtin::Int8 => number(s.read_u8()? as i8),
+            Builtin::Uint8 => number(s.read_u8()?),
+            Builtin::Int16 => number(s.read_u16()? as i16),
+            Builtin::Uint16 => number(s.read_u16()?),
+            Builtin::Int32 => number(s.read_u32()? as i32),
+            Builtin::Uint32 => number(s.read_u32()?),
+            Builtin::Int64 => quoted(s.read_u64()? as i64),
+            Builtin::Uint64 => quoted(s.read_u64()?),
+            Builtin::Int128 => quoted(i128::from_le_bytes(s.read_array()?)),
+            Builtin::Uint128 => quoted(u128::from_le_bytes(s.read_array()?)),
+            Builtin::VarUint32 => number(s.read_varuint32()?),
+            Builtin::VarInt32 => number(s.read_varint32()?),
+            Builtin::Float32 => float_json(f32::from_le_bytes(s.read_array()?) as f64),
+            Builtin::Float64 => float_json(f64::from_le_bytes(s.read_array()?)),
+            Builtin::Float128 => JsonValue::String(format!("0x{}", stream::to_hex(s.read_bytes(16)?))),
+            Builtin::TimePoint => {
+                let us = s.read_u64()? as i64;
+                JsonValue::String(format_time_ms(us.div_euclid(1000)))
+            }
+            Builtin::TimePointSec => JsonValue::String(format_time_ms(s.read_u32()? as i64 * 1000)),
+            Builtin::BlockTimestamp => {
+                let slot = s.read_u32()? as i64;
+                JsonValue::String(format_time_ms(slot * BLOCK_INTERVAL_MS + BLOCK_TIMESTAMP_EPOCH_MS))
+            }
+            Builtin::Name => JsonValue::String(Name::new(s.read_u64()?).to_string()),
+            Builtin::Bytes => JsonValue::String(stream::to_hex(&s.read_byte_vec()?)),
+            Builtin::Bitset => JsonValue::String(format_bitset(&read_bitset(s)?)),
+            Builtin::String => JsonValue::String(s.read_string()?),
+            Builtin::Checksum160 => JsonValue::String(stream::to_hex(s.read_bytes(20)?)),
+            Builtin::Checksum256 => JsonValue::String(stream::to_hex(s.read_bytes(32)?)),
+            Builtin::Checksum512 => JsonValue::String(stream::to_hex(s.read_bytes(64)?)),
+            Builtin::PublicKey => JsonValue::String(keys::read_public_key(s)?),
+            Builtin::PrivateKey => JsonValue::String(keys::read_private_key(s)?),
+            Builtin::Signature => JsonValue::String(keys::read_signature(s)?),
+            Builtin::Symbol => JsonValue::String(format_symbol(s.read_u64()?)),
+            Builtin::SymbolCode => JsonValue::String(format_symbol_code(s.read_u64()?)),
+            Builtin::Asset => {
+                let amount = s.read_u64()? as i64;
+                JsonValue::String(format_asset(amount, s.read_u64()?))
+            }
+            Builtin::ExtendedAsset => {
+                let quantity = Builtin::Asset.bin_to_json(s)?;
+                let contract = Builtin::Name.bin_to_json(s)?;
+                JsonValue::Object(vec![("quantity".to_string(), quantity), ("contract".to_string(), contract)])
+            }
+        })
+    }
+}
+
+fn string(value: &JsonValue) -> Result<&str, String> {
+    value.as_str().ok_or_else(|| format!("expected string, got {}", value.kind()))
+}
+
+/// Integers are accepted both as JSON numbers and as quoted strings
+fn int<T: TryFrom<i128> + TryFrom<u128>>(value: &JsonValue) -> Result<T, String> {
+    let text = match value {
+        JsonValue::Number(n) => n.as_str(),
+        JsonValue::String(s) => s.as_str(),
+        _ => return Err(format!("expected integer, got {}", value.kind())),
+    };
+    let out_of_range = || format!("number is out of range: {}", text);
+    if let Ok(v) = text.parse::<u128>() {
+        T::try_from(v).map_err(|_| out_of_range())
+    } else if let Ok(v) = text.parse::<i128>() {
+        T::try_from(v).map_err(|_| out_of_range())
+    } else if !text.is_empty() && text.trim_start_matches('-').bytes().all(|c| c.is_ascii_digit()) {
+        Err(out_of_range())
+    } else {
+        Err(format!("expected integer: \"{}\"", text))
+    }
+}
+
+fn float(value: &JsonValue) -> Result<f64, String> {
+    let text = match value {
+        JsonValue::Number(n) => n.as_str(),
+        JsonValue::String(s) => match s.as_str() {
+            "NaN" => return Ok(f64::NAN),
+            "Infinity" | "inf" => return Ok(f64::INFINITY),
+            "-Infinity" | "-inf" => return Ok(f64::NEG_INFINITY),
+            s => s,
+        },
+        _ => return Err(format!("expected number, got {}", value.kind())),
+    };
+    text.parse().map_err(|_| format!("expected number: \"{}\"", text))
+}
+
+/// Format a float like abieos: shortest digits, written out in full for moderate exponents and in
+/// scientific notation (`1e+300`, `1.5e-7`) otherwise, like `fpconv_dtoa`
+pub(crate) fn format_float(f: f64) -> String {
+    if f.is_nan() {
+        return "NaN".to_string();
+    } else if f.is_infinite() {
+        return if f > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    let scientific = format!("{:e}", f.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("scientific notation");
+    let digits = mantissa.replace('.', "");
+    let ndigits = digits.len() as i32;
+    // value is digits * 10^k, and exponent that of the first digit
+    let exponent: i32 = exponent.parse().expect("exponent");
+    let k = exponent - (ndigits - 1);
+    let sign = if f.is_sign_negative() { "-" } else { "" };
+    if f == 0.0 {
+        format!("{}0", sign)
+    } else if k >= 0 && exponent < ndigits + 7 {
+        format!("{}{}{}", sign, digits, "0".repeat(k as usize))
+    } else if k < 0 && (k > -7 || exponent.abs() < 4) {
+        let point = ndigits + k;
+        if point > 0 {
+            format!("{}{}.{}", sign, &digits[..point as usize], &digits[point as usize..])
+        } else {
+            format!("{}0.{}{}", sign, "0".repeat(-point as usize), digits)
+        }
+    } else {
+        let fraction = if ndigits > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+        let exponent_sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}{}{}e{}{}", sign, &digits[..1], fraction, exponent_sign, exponent.abs())
+    }
+}
+
+/// Floats are written like abieos, which formats `float32` values as doubles
+fn float_json(f: f64) -> JsonValue {
+    if f.is_finite() {
+        JsonValue::Number(format_float(f))
+    } else {
+        JsonValue::String(format_float(f))
+    }
+}
+
+fn fixed_hex(hex: &str, size: usize) -> Result<Vec<u8>, String> {
+    let bytes = stream::from_hex(hex)?;
+    if bytes.len() != size {
+        return Err(format!("expected {} hex digits, got {}", size * 2, hex.len()));
+    }
+    Ok(bytes)
+}
+
+// --- bitsets ---
+
+/// Parse the `0`/`1` string of a bitset, highest bit first like `boost::to_string`
+fn parse_bitset(s: &str) -> Result<Vec<bool>, String> {
+    s.bytes()
+        .rev()
+        .map(|c| match c {
+            b'0' => Ok(false),
+            b'1' => Ok(true),
+            _ => Err(format!("expected bitset: \"{}\"", s)),
+        })
+        .collect()
+}
+
+fn format_bitset(bits: &[bool]) -> String {
+    bits.iter().rev().map(|&bit| if bit { '1' } else { '0' }).collect()
+}
+
+/// Write the number of bits, then the bits packed in bytes from the lowest bit, like
+/// `fc::dynamic_bitset`
+pub(crate) fn write_bitset(out: &mut Vec<u8>, bits: &[bool]) -> Result<(), String> {
+    stream::write_length(out, bits.len())?;
+    for chunk in bits.chunks(8) {
+        out.push(chunk.iter().enumerate().fold(0, |byte, (i, &bit)| byte | (bit as u8) << i));
+    }
+    Ok(())
+}
+
+/// Read a bitset, rejecting set bits past its size
+pub(crate) fn read_bitset(s: &mut InputStream) -> Result<Vec<bool>, String> {
+    let len = s.read_varuint32()? as usize;
+    let start = s.pos();
+    let bytes = s.read_bytes(len.div_ceil(8))?;
+    let bits: Vec<bool> = (0..bytes.len() * 8).map(|i| bytes[i / 8] & (1 << (i % 8)) != 0).collect();
+    if bits[len..].contains(&true) {
+        return Err(format!("invalid bitset padding at offset {}", start));
+    }
+    Ok(bits[..len].to_vec())
+}
+
+// --- time ---
+
+const BLOCK_INTERVAL_MS: i64 = 500;
+const BLOCK_TIMESTAMP_EPOCH_MS: i64 = 946_684_800_000;
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Proleptic Gregorian date for a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + (month <= 2) as i64, month, day)
+}
+
+/// Format milliseconds since the epoch as `YYYY-MM-DDTHH:MM:SS.mmm`
+pub(crate) fn format_time_ms(ms: i64) -> String {
+    let (year, month, day) = civil_from_days(ms.div_euclid(86_400_000));
+    let ms_of_day = ms.rem_euclid(86_400_000);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        ms_of_day / 3_600_000,
+        ms_of_day / 60_000 % 60,
+        ms_of_day / 1000 % 60,
+        ms_of_day % 1000
+    )
+}
+
+/// Parse `YYYY-MM-DDTHH:MM:SS[.ffffff][Z]` into microseconds since the epoch
+pub(crate) fn parse_time_point(s: &str) -> Result<i64, String> {
+    let invalid = || format!("expected time point: \"{}\"", s);
+    let text = s.strip_suffix('Z').unwrap_or(s);
+    let (datetime, fraction) = match text.split_once('.') {
+        Some((dt, f)) => (dt, f),
+        None => (text, ""),
+    };
+    let b = datetime.as_bytes();
+    if b.len() != 19 || b[4] != b'-' || b[7] != b'-' || b[10] != b'T' || b[13] != b':' || b[16] != b':' {
+        return Err(invalid());
+    }
+    let field = |range: std::ops::Range<usize>| -> Result<i64, String> {
+        let part = &datetime[range];
+        if !part.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        part.parse().map_err(|_| invalid())
+    };
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+    if fraction.len() > 6 || !fraction.bytes().all(|c| c.is_ascii_digit()) || (text.contains('.') && fraction.is_empty()) {
+        return Err(invalid());
+    }
+    let micros = if fraction.is_empty() { 0 } else { fraction.parse::<i64>().map_err(|_| invalid())? * 10i64.pow(6 - fraction.len() as u32) };
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second;
+    Ok(seconds * 1_000_000 + micros)
+}
+
+// --- symbols and assets ---
+
+const MAX_PRECISION: u8 = 18;
+const MAX_AMOUNT: i64 = (1 << 62) - 1;
+
+pub(crate) fn parse_symbol_code(s: &str) -> Result<u64, String> {
+    if s.is_empty() || s.len() > 7 || !s.bytes().all(|c| c.is_ascii_uppercase()) {
+        return Err(format!("invalid symbol code: \"{}\"", s));
+    }
+    Ok(s.bytes().rev().fold(0u64, |acc, c| (acc << 8) | c as u64))
+}
+
+pub(crate) fn format_symbol_code(raw: u64) -> String {
+    raw.to_le_bytes().iter().take_while(|&&c| c != 0).map(|&c| c as char).collect()
+}
+
+/// Parse `precision,CODE` into the raw symbol value
+pub(crate) fn parse_symbol(s: &str) -> Result<u64, String> {
+    let (precision, code) = s.split_once(',').ok_or_else(|| format!("invalid symbol: \"{}\"", s))?;
+    let precision: u8 = precision.parse().map_err(|_| format!("invalid symbol precision: \"{}\"", s))?;
+    if precision > MAX_PRECISION {
+        return Err(format!("symbol precision must be at most {}: \"{}\"", MAX_PRECISION, s));
+    }
+    Ok((parse_symbol_code(code)? << 8) | precision as u64)
+}
+
+pub(crate) fn format_symbol(raw: u64) -> String {
+    format!("{},{}", raw & 0xff, format_symbol_code(raw >> 8))
+}
+
+/// Parse `1.0000 EOS` into its amount and raw symbol
+pub(crate) fn parse_asset(s: &str) -> Result<(i64, u64), String> {
+    let invalid = || format!("invalid asset: \"{}\"", s);
+    let (amount, code) = s.trim().split_once(' ').ok_or_else(invalid)?;
+    let (negative, amount) = match amount.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, amount),
+    };
+    let (int_part, frac_part) = amount.split_once('.').unwrap_or((amount, ""));
+    if int_part.is_empty() || !int_part.bytes().all(|c| c.is_ascii_digit()) || !frac_part.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if amount.contains('.') && frac_part.is_empty() {
+        return Err(invalid());
+    }
+    let precision = u8::try_from(frac_part.len()).ok().filter(|&p| p <= MAX_PRECISION).ok_or_else(invalid)?;
+    let digits = format!("{}{}", int_part, frac_part);
+    let magnitude: i64 = digits.parse().ok().filter(|&a| a <= MAX_AMOUNT).ok_or_else(|| format!("asset amount is out of range: \"{}\"", s))?;
+    let symbol = (parse_symbol_code(code.trim_start())? << 8) | precision as u64;
+    Ok((if negative { -magnitude } else { magnitude }, symbol))
+}
+
+pub(crate) fn format_asset(amount: i64, symbol: u64) -> String {
+    let precision = (symbol & 0xff) as u32;
+    let magnitude = amount.unsigned_abs() as u128;
+    let sign = if amount < 0 { "-" } else { "" };
+    let code = format_symbol_code(symbol >> 8);
+    if precision == 0 {
+        return format!("{}{} {}", sign, magnitude, code);
+    }
+    let scale = 10u128.pow(precision);
+    format!("{}{}.{:0width$} {}", sign, magnitude / scale, magnitude % scale, code, width = precision as usize)
+}
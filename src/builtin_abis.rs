@@ -0,0 +1,118 @@
+//! # Builtin ABIs
+//!
+//!  System contract, transaction and state history ABIs compiled into the crate
+//!
+//! ```rust
+//! use rs_abieos::{builtin_abis, Abieos};
+//!
+//! let abieos = Abieos::with_system_abis().unwrap();
+//! assert!(abieos.has_contract(builtin_abis::EOSIO_TOKEN.account().unwrap()));
+//! ```
+
+use crate::{AbiDef, AbiLike, Abieos, AbieosError, Name};
+
+/// An ABI compiled into the crate, in JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinAbi {
+    name: &'static str,
+    account: Option<&'static str>,
+    json: &'static [u8],
+}
+
+impl BuiltinAbi {
+    /// Short name of the ABI, the account name for system contracts
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Account the contract is deployed to, `None` for ABIs not tied to a contract
+    pub fn account(&self) -> Option<Name> {
+        self.account.map(|account| account.parse().expect("builtin account names are valid"))
+    }
+
+    /// ABI in JSON
+    pub fn json(&self) -> &'static str {
+        std::str::from_utf8(self.json).expect("builtin ABIs are UTF-8")
+    }
+
+    /// ABI in JSON, as bytes
+    pub fn bytes(&self) -> &'static [u8] {
+        self.json
+    }
+
+    /// Owned copy of the ABI, to load with [`ContractHandle::load_abi`](crate::ContractHandle::load_abi)
+    pub fn to_abi_like(&self) -> AbiLike {
+        AbiLike::Json(self.json().to_string())
+    }
+
+    /// Parse the ABI
+    pub fn to_def(&self) -> Result<AbiDef, AbieosError> {
+        AbiDef::from_json(self.json())
+    }
+}
+
+/// Transaction types (`transaction`, `action`, `permission_level`...), not tied to a contract
+pub const TRANSACTION: BuiltinAbi = BuiltinAbi {
+    name: "transaction",
+    account: None,
+    json: include_bytes!("../abis/transaction.abi.json"),
+};
+
+/// System contract
+pub const EOSIO: BuiltinAbi = BuiltinAbi {
+    name: "eosio",
+    account: Some("eosio"),
+    json: include_bytes!("../abis/eosio.abi"),
+};
+
+/// Token contract
+pub const EOSIO_TOKEN: BuiltinAbi = BuiltinAbi {
+    name: "eosio.token",
+    account: Some("eosio.token"),
+    json: include_bytes!("../abis/eosio.token.abi"),
+};
+
+/// Multisig contract
+pub const EOSIO_MSIG: BuiltinAbi = BuiltinAbi {
+    name: "eosio.msig",
+    account: Some("eosio.msig"),
+    json: include_bytes!("../abis/eosio.msig.abi"),
+};
+
+/// Wrap contract, executing transactions with the privileges of `eosio.wrap`
+pub const EOSIO_WRAP: BuiltinAbi = BuiltinAbi {
+    name: "eosio.wrap",
+    account: Some("eosio.wrap"),
+    json: include_bytes!("../abis/eosio.wrap.abi"),
+};
+
+/// State history plugin protocol (`request`, `result`, traces and table deltas), not tied to a contract
+pub const STATE_HISTORY: BuiltinAbi = BuiltinAbi {
+    name: "state_history",
+    account: None,
+    json: include_bytes!("../abis/ship.abi"),
+};
+
+/// Every builtin ABI
+pub const ALL: [BuiltinAbi; 6] = [TRANSACTION, EOSIO, EOSIO_TOKEN, EOSIO_MSIG, EOSIO_WRAP, STATE_HISTORY];
+
+/// Find a builtin ABI by name
+pub fn get(name: &str) -> Option<BuiltinAbi> {
+    ALL.into_iter().find(|abi| abi.name == name)
+}
+
+impl Abieos {
+    /// Create a context with the builtin system contract ABIs loaded under their accounts
+    ///
+    /// `eosio`, `eosio.token`, `eosio.msig` and `eosio.wrap` are loaded. The transaction and
+    /// state history ABIs are not tied to an account and can be loaded under any name.
+    pub fn with_system_abis() -> Result<Abieos, AbieosError> {
+        let abieos = Abieos::new();
+        for abi in ALL {
+            if let Some(account) = abi.account() {
+                abieos.set_abi_json_native(account, abi.json())?;
+            }
+        }
+        Ok(abieos)
+    }
+}
@@ -0,0 +1,238 @@
+//! # Differential conformance checks
+//!
+//!  Runs payloads through the [`Abieos`] backend and through the pure-Rust reference codec,
+//!  reporting where the two disagree
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::json::JsonValue;
+use crate::serializer::AbiSerializer;
+use crate::{stream, AbiDef, Abieos, AbieosError, Name};
+
+/// Contract name the ABI is loaded under in the backend context
+const CONTRACT: Name = Name::from_literal("conformance");
+
+/// Conversion in which a divergence was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// JSON to binary serialization
+    JsonToBin,
+    /// Binary to JSON deserialization
+    BinToJson,
+    /// Re-serialization of the backend's own JSON output
+    Roundtrip,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Stage::JsonToBin => write!(f, "json_to_bin"),
+            Stage::BinToJson => write!(f, "bin_to_json"),
+            Stage::Roundtrip => write!(f, "roundtrip"),
+        }
+    }
+}
+
+/// A disagreement between the backend and the reference codec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub stage: Stage,
+    /// Offset of the first differing byte in the binary payload, when known
+    pub offset: Option<usize>,
+    /// Field path of the differing value (`transfer.quantity`, `names[2]`, `choice<string>`...)
+    pub path: String,
+    /// Backend result (hex, JSON or error message)
+    pub abieos: String,
+    /// Reference result (hex, JSON or error message)
+    pub reference: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{} divergence at {}", self.stage, self.path)?;
+        if let Some(offset) = self.offset {
+            write!(f, " (offset {})", offset)?;
+        }
+        write!(f, ": abieos {}, reference {}", self.abieos, self.reference)
+    }
+}
+
+/// Differential checker for a single ABI
+///
+/// Every payload is converted by an [`Abieos`] context and by an independent Rust encoder/decoder
+/// of the Antelope wire format. Both sides rejecting a payload is not a divergence.
+///
+/// With the `pure-rust` feature the backend is the reference codec itself: no divergence can be
+/// found, which [`Conformance::is_differential`] reports.
+///
+/// ```rust,no_run
+/// use rs_abieos::{AbiDef, Conformance};
+/// let abi = AbiDef::from_json(&std::fs::read_to_string("abis/eosio.abi").unwrap()).unwrap();
+/// let conformance = Conformance::new(&abi).unwrap();
+/// for divergence in conformance.check_hex("delegatebw", "0000000000EA3055...").unwrap() {
+///     println!("{}", divergence);
+/// }
+/// ```
+pub struct Conformance {
+    abieos: Abieos,
+    reference: AbiSerializer,
+}
+
+impl Conformance {
+    /// Load an ABI into a new backend context and into the reference codec
+    pub fn new(abi: &AbiDef) -> Result<Conformance, AbieosError> {
+        let reference = AbiSerializer::new(abi.clone()).map_err(AbieosError::InvalidAbi)?;
+        let abieos = Abieos::new();
+        abieos.set_abi_def(CONTRACT, abi)?;
+        Ok(Conformance { abieos, reference })
+    }
+
+    /// Whether the backend and the reference codec are different implementations, `false` with
+    /// the `pure-rust` feature where an empty list of divergences proves nothing
+    pub fn is_differential(&self) -> bool {
+        cfg!(not(feature = "pure-rust"))
+    }
+
+    /// Compare the serialization of a JSON payload, then the deserialization of the result
+    pub fn check_json(&self, datatype: &str, json: &str) -> Vec<Divergence> {
+        let abieos = self.abieos.json_to_bin_native(CONTRACT, datatype, json);
+        let reference = self.reference.json_to_bin(datatype, json);
+        match (abieos, reference) {
+            (Err(_), Err(_)) => vec![],
+            (Ok(abieos), Ok(reference)) if abieos == reference => self.check_bin(datatype, &reference),
+            (Ok(abieos), Ok(reference)) => {
+                let offset = first_difference(&abieos, &reference);
+                vec![Divergence {
+                    stage: Stage::JsonToBin,
+                    offset: Some(offset),
+                    path: self.path_at(datatype, &reference, offset),
+                    abieos: stream::to_hex(&abieos),
+                    reference: stream::to_hex(&reference),
+                }]
+            }
+            (abieos, reference) => vec![Divergence {
+                stage: Stage::JsonToBin,
+                offset: None,
+                path: datatype.to_string(),
+                abieos: describe(abieos.map(|bin| stream::to_hex(&bin))),
+                reference: describe(reference.map(|bin| stream::to_hex(&bin)).map_err(AbieosError::JsonToBin)),
+            }],
+        }
+    }
+
+    /// Compare the deserialization of a binary payload, then the backend's re-serialization of its JSON
+    pub fn check_bin(&self, datatype: &str, bin: &[u8]) -> Vec<Divergence> {
+        let abieos = self.abieos.bin_to_json_native(CONTRACT, datatype, bin);
+        let reference = self.reference.bin_to_json(datatype, bin);
+        let (abieos_json, reference_json) = match (abieos, reference) {
+            (Err(_), Err(_)) => return vec![],
+            (Ok(abieos), Ok(reference)) => (abieos, reference),
+            (abieos, reference) => {
+                return vec![Divergence {
+                    stage: Stage::BinToJson,
+                    offset: None,
+                    path: datatype.to_string(),
+                    abieos: describe(abieos),
+                    reference: describe(reference.map_err(AbieosError::BinToJson)),
+                }];
+            }
+        };
+
+        let mut divergences = vec![];
+        let difference = match (JsonValue::parse(&abieos_json), JsonValue::parse(&reference_json)) {
+            (Ok(parsed), Ok(expected)) => json_difference(datatype.to_string(), &parsed, &expected),
+            _ => Some((datatype.to_string(), abieos_json.clone(), reference_json.clone())),
+        };
+        if let Some((path, abieos, reference)) = difference {
+            divergences.push(Divergence {
+                stage: Stage::BinToJson,
+                offset: self.offset_of(datatype, bin, &path),
+                path,
+                abieos,
+                reference,
+            });
+        }
+
+        match self.abieos.json_to_bin_native(CONTRACT, datatype, &abieos_json) {
+            Ok(roundtrip) if roundtrip == bin => {}
+            Ok(roundtrip) => {
+                let offset = first_difference(&roundtrip, bin);
+                divergences.push(Divergence {
+                    stage: Stage::Roundtrip,
+                    offset: Some(offset),
+                    path: self.path_at(datatype, bin, offset),
+                    abieos: stream::to_hex(&roundtrip),
+                    reference: stream::to_hex(bin),
+                });
+            }
+            Err(e) => divergences.push(Divergence {
+                stage: Stage::Roundtrip,
+                offset: None,
+                path: datatype.to_string(),
+                abieos: describe::<String>(Err(e)),
+                reference: stream::to_hex(bin),
+            }),
+        }
+        divergences
+    }
+
+    /// Same as [`Conformance::check_bin`], with the payload as hex
+    pub fn check_hex(&self, datatype: &str, hex: &str) -> Result<Vec<Divergence>, AbieosError> {
+        let bin = stream::from_hex(hex).map_err(AbieosError::HexToJson)?;
+        Ok(self.check_bin(datatype, &bin))
+    }
+
+    /// Field path of the innermost value encoded at `offset`
+    fn path_at(&self, datatype: &str, bin: &[u8], offset: usize) -> String {
+        let spans = self.reference.trace(datatype, bin);
+        spans
+            .iter()
+            .find(|(start, end, _)| (*start..*end).contains(&offset))
+            .or(spans.last())
+            .map_or_else(|| datatype.to_string(), |(_, _, path)| path.clone())
+    }
+
+    /// Offset of the first value encoded under `path`
+    fn offset_of(&self, datatype: &str, bin: &[u8], path: &str) -> Option<usize> {
+        self.reference
+            .trace(datatype, bin)
+            .into_iter()
+            .find(|(_, _, span)| {
+                span.strip_prefix(path)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[', '<']))
+            })
+            .map(|(start, _, _)| start)
+    }
+}
+
+fn describe<T: Display>(result: Result<T, AbieosError>) -> String {
+    match result {
+        Ok(value) => value.to_string(),
+        Err(e) => format!("error ({})", e),
+    }
+}
+
+fn first_difference(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).position(|(x, y)| x != y).unwrap_or(a.len().min(b.len()))
+}
+
+/// Find the first differing value, returning its path and both JSON texts
+fn json_difference(path: String, a: &JsonValue, b: &JsonValue) -> Option<(String, String, String)> {
+    match (a, b) {
+        (JsonValue::Array(a_items), JsonValue::Array(b_items)) if a_items.len() == b_items.len() => a_items
+            .iter()
+            .zip(b_items)
+            .enumerate()
+            .find_map(|(i, (a, b))| json_difference(format!("{}[{}]", path, i), a, b)),
+        (JsonValue::Object(a_members), JsonValue::Object(b_members))
+            if a_members.iter().map(|(k, _)| k).eq(b_members.iter().map(|(k, _)| k)) =>
+        {
+            a_members
+                .iter()
+                .zip(b_members)
+                .find_map(|((key, a), (_, b))| json_difference(format!("{}.{}", path, key), a, b))
+        }
+        _ if a == b => None,
+        _ => Some((path, a.to_json(), b.to_json())),
+    }
+}
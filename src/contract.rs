@@ -0,0 +1,219 @@
+use crate::{AbiLike, AbiValue, Abieos, AbieosError, Name};
+
+/// Contract handle, borrowing the [`Abieos`] context it was created from
+///
+/// Created with [`Abieos::contract`]. The handle cannot outlive its context, and every
+/// serialization call checks that an ABI is loaded for the contract, returning
+/// [`AbieosError::AbiNotLoaded`] otherwise.
+///
+/// ```rust,no_run
+/// use rs_abieos::{AbiLike, Abieos, Name};
+/// let abieos = Abieos::new();
+/// let mut token = abieos.contract(Name::try_from("eosio.token").unwrap());
+/// token.load_abi(AbiLike::Json(std::fs::read_to_string("eosio.token.abi").unwrap())).unwrap();
+/// let hex = token.json_to_hex("transfer", r#"{"from":"alice","to":"bob","quantity":"1.0000 EOS","memo":""}"#);
+/// ```
+///
+/// The borrow checker prevents a handle from outliving its context:
+/// ```rust,compile_fail
+/// use rs_abieos::{Abieos, Name};
+/// let contract = {
+///     let abieos = Abieos::new();
+///     abieos.contract(Name::new(1))
+/// };
+/// ```
+pub struct ContractHandle<'a> {
+    abieos: &'a Abieos,
+    name: Name,
+}
+
+/// Former name of [`ContractHandle`], kept for compatibility
+pub type AbieosContract<'a> = ContractHandle<'a>;
+
+impl<'a> ContractHandle<'a> {
+    pub(crate) fn new(abieos: &'a Abieos, name: Name) -> ContractHandle<'a> {
+        ContractHandle { abieos, name }
+    }
+
+    /// Contract account name
+    pub fn name(&self) -> Name {
+        self.name
+    }
+
+    /// Returns `true` if an ABI is currently loaded for this contract
+    pub fn is_abi_loaded(&self) -> bool {
+        self.abieos.has_contract(self.name)
+    }
+
+    fn ensure_loaded(&self) -> Result<(), AbieosError> {
+        if self.is_abi_loaded() {
+            Ok(())
+        } else {
+            Err(AbieosError::AbiNotLoaded)
+        }
+    }
+
+    /// Load an ABI from a JSON file
+    pub fn load_json_file(&mut self, path: &str) -> Result<&mut Self, AbieosError> {
+        match std::fs::read_to_string(path) {
+            Ok(file) => {
+                self.abieos.set_abi_json_native(self.name, &file)?;
+                Ok(self)
+            }
+            Err(_) => Err(AbieosError::FileRead),
+        }
+    }
+
+    /// Load an ABI
+    pub fn load_abi(&mut self, abi: AbiLike) -> Result<&mut Self, AbieosError> {
+        self.abieos.set_abi_like(self.name, &abi)?;
+        Ok(self)
+    }
+
+    /// Remove the contract ABI from the context
+    ///
+    /// Returns `false` if no ABI was loaded.
+    pub fn delete(&mut self) -> Result<bool, AbieosError> {
+        self.abieos.delete_contract_native(self.name)
+    }
+
+    /// Get data type for an action
+    pub fn get_type_for_action(&self, action: &str) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        let action: Name = action.parse()?;
+        self.abieos.get_type_for_action_native(self.name, action)
+    }
+
+    /// Get data type for a table
+    pub fn get_type_for_table(&self, table: &str) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        let table: Name = table.parse()?;
+        self.abieos.get_type_for_table_native(self.name, table)
+    }
+
+    /// Get data type for an action result
+    pub fn get_type_for_action_result(&self, action: &str) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        let action: Name = action.parse()?;
+        self.abieos.get_type_for_action_result_native(self.name, action)
+    }
+
+    /// Serialize JSON into binary (output as HEX)
+    pub fn json_to_hex(&self, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.json_to_hex_native(self.name, datatype, json)
+    }
+
+    /// Serialize JSON into binary (output as binary)
+    pub fn json_to_bin(&self, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.json_to_bin_native(self.name, datatype, json)
+    }
+
+    /// Deserialize HEX string into JSON
+    pub fn hex_to_json(&self, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.hex_to_json_native(self.name, datatype, hex)
+    }
+
+    /// Deserialize Binary into JSON
+    pub fn bin_to_json(&self, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.bin_to_json_native(self.name, datatype, bin)
+    }
+
+    /// Serialize a `serde_json::Value` into binary (output as HEX)
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_hex_value(&self, datatype: &str, json: &serde_json::Value) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.json_to_hex_value(self.name, datatype, json)
+    }
+
+    /// Serialize a `serde_json::Value` into binary
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_bin_value(&self, datatype: &str, json: &serde_json::Value) -> Result<Vec<u8>, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.json_to_bin_value(self.name, datatype, json)
+    }
+
+    /// Deserialize HEX string into a `serde_json::Value`
+    #[cfg(feature = "serde_json")]
+    pub fn hex_to_json_value(&self, datatype: &str, hex: &str) -> Result<serde_json::Value, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.hex_to_json_value(self.name, datatype, hex)
+    }
+
+    /// Deserialize Binary into a `serde_json::Value`
+    #[cfg(feature = "serde_json")]
+    pub fn bin_to_json_value(&self, datatype: &str, bin: &[u8]) -> Result<serde_json::Value, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.bin_to_json_value(self.name, datatype, bin)
+    }
+
+    /// Serialize the data of an action, resolving its type through the ABI
+    pub fn encode_action(&self, action: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.ensure_loaded()?;
+        let action: Name = action.parse()?;
+        self.abieos.encode_action(self.name, action, json)
+    }
+
+    /// Deserialize the data of an action into JSON, resolving its type through the ABI
+    pub fn decode_action(&self, action: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        let action: Name = action.parse()?;
+        self.abieos.decode_action(self.name, action, bin)
+    }
+
+    /// Deserialize the return value of an action into JSON
+    pub fn decode_action_result(&self, action: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        let action: Name = action.parse()?;
+        self.abieos.decode_action_result(self.name, action, bin)
+    }
+
+    /// Serialize a table row, resolving its type through the ABI
+    pub fn encode_table_row(&self, table: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.ensure_loaded()?;
+        let table: Name = table.parse()?;
+        self.abieos.encode_table_row(self.name, table, json)
+    }
+
+    /// Deserialize a table row into JSON, resolving its type through the ABI
+    pub fn decode_table_row(&self, table: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.ensure_loaded()?;
+        let table: Name = table.parse()?;
+        self.abieos.decode_table_row(self.name, table, bin)
+    }
+
+    /// Read the primary key of a table row, see [`Abieos::table_row_primary_key`]
+    pub fn table_row_primary_key(&self, table: &str, bin: &[u8]) -> Result<Option<u64>, AbieosError> {
+        self.ensure_loaded()?;
+        let table: Name = table.parse()?;
+        self.abieos.table_row_primary_key(self.name, table, bin)
+    }
+
+    /// Deserialize binary into a typed [`AbiValue`]
+    pub fn bin_to_value(&self, datatype: &str, bin: &[u8]) -> Result<AbiValue, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.bin_to_value(self.name, datatype, bin)
+    }
+
+    /// Serialize a typed [`AbiValue`] into binary
+    pub fn value_to_bin(&self, datatype: &str, value: &AbiValue) -> Result<Vec<u8>, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.value_to_bin(self.name, datatype, value)
+    }
+
+    /// Parse JSON into a typed [`AbiValue`]
+    pub fn json_to_value(&self, datatype: &str, json: &str) -> Result<AbiValue, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.json_to_value(self.name, datatype, json)
+    }
+
+    /// Convert a `serde_json::Value` into a typed [`AbiValue`]
+    #[cfg(feature = "serde_json")]
+    pub fn json_value_to_value(&self, datatype: &str, json: &serde_json::Value) -> Result<AbiValue, AbieosError> {
+        self.ensure_loaded()?;
+        self.abieos.json_value_to_value(self.name, datatype, json)
+    }
+}
@@ -0,0 +1,333 @@
+//! # Binary deserializer
+//!
+//!  A serde [`Deserializer`](serde::Deserializer) reading the Antelope wire format directly into Rust types,
+//!  the mirror of [`ser`](crate::ser)
+//!
+//! The binary format is not self-describing, so the Rust type drives decoding with the mapping described
+//! in [`ser`](crate::ser). `deserialize_any` and `deserialize_ignored_any` are not supported.
+//!
+//! Errors are reported as [`AbieosError::Deserialize`] with the byte offset of the value that failed:
+//!
+//! ```rust
+//! use rs_abieos::{de, Name};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Asset {
+//!     amount: i64,
+//!     symbol: u64,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Transfer {
+//!     from: Name,
+//!     to: Name,
+//!     quantity: Asset,
+//!     memo: String,
+//! }
+//!
+//! let hex = "0000000000855C340000000000000E3D102700000000000004454F53000000000648656C6C6F21";
+//! let transfer: Transfer = de::from_hex(hex).unwrap();
+//! assert_eq!(transfer.from.to_string(), "alice");
+//! assert_eq!(transfer.memo, "Hello!");
+//!
+//! let error = de::from_hex::<Transfer>(&hex[..40]).err().unwrap();
+//! assert_eq!(error.to_string(), "Failed to deserialize value: unexpected end of data at offset 16");
+//! ```
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::Deserialize;
+
+use crate::stream::{self, InputStream};
+use crate::AbieosError;
+
+/// Deserialize a value from Antelope binary, requiring all data to be consumed
+pub fn from_bytes<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, AbieosError> {
+    let mut deserializer = Deserializer::new(bytes);
+    let value = T::deserialize(&mut deserializer).map_err(|e| deserializer.locate(e))?;
+    if deserializer.input.remaining() > 0 {
+        return Err(AbieosError::Deserialize(deserializer.input.error("extra data")));
+    }
+    Ok(value)
+}
+
+/// Deserialize a value from Antelope binary given as hex
+pub fn from_hex<T: DeserializeOwned>(hex: &str) -> Result<T, AbieosError> {
+    let bytes = stream::from_hex(hex).map_err(AbieosError::Deserialize)?;
+    from_bytes(&bytes)
+}
+
+/// Antelope binary deserializer, see the [module documentation](self)
+pub struct Deserializer<'de> {
+    input: InputStream<'de>,
+    /// Offset of the last value read, reported for errors raised by visitors
+    start: usize,
+    /// Set when the error already carries its offset
+    located: bool,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(bytes: &'de [u8]) -> Deserializer<'de> {
+        Deserializer { input: InputStream::new(bytes), start: 0, located: false }
+    }
+
+    /// Current read offset
+    pub fn offset(&self) -> usize {
+        self.input.pos()
+    }
+
+    /// Add the offset to errors raised outside of the stream (visitors, `custom` messages)
+    fn locate(&self, e: AbieosError) -> AbieosError {
+        match e {
+            AbieosError::Deserialize(message) if !self.located => {
+                AbieosError::Deserialize(format!("{} at offset {}", message, self.start))
+            }
+            e => e,
+        }
+    }
+
+    fn read<T>(&mut self, f: impl FnOnce(&mut InputStream<'de>) -> Result<T, String>) -> Result<T, AbieosError> {
+        self.start = self.input.pos();
+        f(&mut self.input).map_err(|e| {
+            self.located = true;
+            AbieosError::Deserialize(e)
+        })
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], AbieosError> {
+        self.read(|s| s.read_array())
+    }
+
+    fn read_length(&mut self) -> Result<usize, AbieosError> {
+        self.read(|s| s.read_length(1))
+    }
+
+    fn read_flag(&mut self, what: &str) -> Result<bool, AbieosError> {
+        match self.read(|s| s.read_u8())? {
+            0 => Ok(false),
+            1 => Ok(true),
+            v => Err(AbieosError::Deserialize(format!("invalid {} {}", what, v))),
+        }
+    }
+
+    fn read_str(&mut self) -> Result<&'de str, AbieosError> {
+        self.read(|s| {
+            let len = s.read_length(1)?;
+            let start = s.pos();
+            std::str::from_utf8(s.read_bytes(len)?).map_err(|_| format!("invalid utf-8 string at offset {}", start))
+        })
+    }
+}
+
+impl de::Error for AbieosError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        AbieosError::Deserialize(msg.to_string())
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident => $visit:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+                visitor.$visit(<$ty>::from_le_bytes(self.read_array()?))
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = AbieosError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, AbieosError> {
+        Err(AbieosError::Deserialize("the binary format is not self-describing, a concrete type is required".to_string()))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_bool(self.read_flag("bool")?)
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        let s = self.read_str()?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(AbieosError::Deserialize(format!("expected a single character, got \"{}\"", s))),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_borrowed_str(self.read_str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        let bytes = self.read(|s| {
+            let len = s.read_length(1)?;
+            s.read_bytes(len)
+        })?;
+        visitor.visit_borrowed_bytes(bytes)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        if self.read_flag("optional flag")? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        let len = self.read_length()?;
+        visitor.visit_seq(Items { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_seq(Items { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value, AbieosError> {
+        visitor.visit_seq(Items { de: self, remaining: len })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, AbieosError> {
+        let len = self.read_length()?;
+        visitor.visit_map(Items { de: self, remaining: len })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, AbieosError> {
+        visitor.visit_seq(Items { de: self, remaining: fields.len() })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, AbieosError> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, AbieosError> {
+        Err(AbieosError::Deserialize("identifiers are not encoded in the binary format".to_string()))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, AbieosError> {
+        Err(AbieosError::Deserialize("values cannot be skipped in the binary format".to_string()))
+    }
+}
+
+/// Access to a known number of sequence items, map entries or struct fields
+struct Items<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Items<'_, 'de> {
+    type Error = AbieosError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, AbieosError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Items<'_, 'de> {
+    type Error = AbieosError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, AbieosError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, AbieosError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = AbieosError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), AbieosError> {
+        let index = self.read(|s| s.read_varuint32())?;
+        let value = seed.deserialize(index.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = AbieosError;
+
+    fn unit_variant(self) -> Result<(), AbieosError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, AbieosError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, AbieosError> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, AbieosError> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
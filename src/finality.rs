@@ -0,0 +1,184 @@
+//! # Savanna finality
+//!
+//!  [`FinalizerPolicy`] and the [`FinalityExtension`] block header extension of Spring, decoded from
+//!  binary with [`de`](crate::de)
+//!
+//! Policy changes are carried in block headers as diffs against the previous policy, which
+//! [`FinalizerPolicy::apply_diff`] resolves.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{de, ser, AbieosError, BlsPublicKey, Name, PublicKey};
+
+fn invalid(message: String) -> AbieosError {
+    AbieosError::InvalidPolicyDiff(message)
+}
+
+/// A finalizer and its voting weight
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizerAuthority {
+    pub description: String,
+    pub weight: u64,
+    pub public_key: BlsPublicKey,
+}
+
+/// Finalizers of a chain, and the weight of votes needed for a quorum certificate
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FinalizerPolicy {
+    pub generation: u32,
+    pub threshold: u64,
+    pub finalizers: Vec<FinalizerAuthority>,
+}
+
+impl FinalizerPolicy {
+    /// Decode a policy from binary, which must hold exactly one policy
+    pub fn from_bin(bin: &[u8]) -> Result<FinalizerPolicy, AbieosError> {
+        de::from_bytes(bin)
+    }
+
+    /// Encode the policy in binary
+    pub fn to_bin(&self) -> Result<Vec<u8>, AbieosError> {
+        ser::to_bytes(self)
+    }
+
+    /// Policy resulting from a diff against this one
+    pub fn apply_diff(&self, diff: &FinalizerPolicyDiff) -> Result<FinalizerPolicy, AbieosError> {
+        Ok(FinalizerPolicy {
+            generation: diff.generation,
+            threshold: diff.threshold,
+            finalizers: diff.finalizers_diff.apply(&self.finalizers)?,
+        })
+    }
+}
+
+/// Changes between two ordered lists, as computed by `fc::ordered_diff` with `uint16` indexes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderedDiff<T> {
+    /// Strictly ascending indexes of the items removed from the original list
+    pub remove_indexes: Vec<u16>,
+    /// Items inserted once removals are done, with their strictly ascending index in the new list
+    pub insert_indexes: Vec<(u16, T)>,
+}
+
+impl<T> Default for OrderedDiff<T> {
+    fn default() -> Self {
+        OrderedDiff { remove_indexes: Vec::new(), insert_indexes: Vec::new() }
+    }
+}
+
+impl<T: Clone> OrderedDiff<T> {
+    /// List resulting from this diff against `items`
+    pub fn apply(&self, items: &[T]) -> Result<Vec<T>, AbieosError> {
+        let mut out = items.to_vec();
+        let mut previous = None;
+        for (removed, &index) in self.remove_indexes.iter().enumerate() {
+            if previous.is_some_and(|previous| index <= previous) {
+                return Err(invalid(format!("remove index {} is not in ascending order", index)));
+            }
+            previous = Some(index);
+            // each removal shifts the following items of the original list down by one
+            let at = (index as usize).checked_sub(removed).filter(|&at| at < out.len());
+            let at = at.ok_or_else(|| invalid(format!("remove index {} is out of range", index)))?;
+            out.remove(at);
+        }
+        let mut previous = None;
+        for (index, item) in &self.insert_indexes {
+            if previous.is_some_and(|previous| *index <= previous) {
+                return Err(invalid(format!("insert index {} is not in ascending order", index)));
+            }
+            previous = Some(*index);
+            if *index as usize > out.len() {
+                return Err(invalid(format!("insert index {} is out of range", index)));
+            }
+            out.insert(*index as usize, item.clone());
+        }
+        Ok(out)
+    }
+}
+
+/// Finalizer policy change proposed by a block
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FinalizerPolicyDiff {
+    pub generation: u32,
+    pub threshold: u64,
+    pub finalizers_diff: OrderedDiff<FinalizerAuthority>,
+}
+
+/// A block signing key and its weight
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyWeight {
+    pub key: PublicKey,
+    pub weight: u16,
+}
+
+/// Keys allowed to sign the blocks of a producer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockSigningAuthority {
+    #[serde(rename = "block_signing_authority_v0")]
+    V0 { threshold: u32, keys: Vec<KeyWeight> },
+}
+
+/// A block producer and its signing authority
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProducerAuthority {
+    pub producer_name: Name,
+    pub authority: BlockSigningAuthority,
+}
+
+/// Proposer schedule change proposed by a block
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ProposerPolicyDiff {
+    pub version: u32,
+    /// Block timestamp, in half-second slots since 2000-01-01
+    pub proposal_time: u32,
+    pub producer_auth_diff: OrderedDiff<ProducerAuthority>,
+}
+
+/// Latest quorum certificate claimed by a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct QcClaim {
+    pub block_num: u32,
+    pub is_strong_qc: bool,
+}
+
+/// The `finality_extension` block header extension of Savanna blocks
+///
+/// ```rust
+/// use rs_abieos::FinalityExtension;
+///
+/// let extension = FinalityExtension::from_bin(&[0x40, 0xe2, 0x01, 0x00, 0x01, 0x00, 0x00]).unwrap();
+/// assert_eq!(extension.qc_claim.block_num, 123456);
+/// assert!(extension.qc_claim.is_strong_qc);
+/// assert!(extension.new_finalizer_policy_diff.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FinalityExtension {
+    pub qc_claim: QcClaim,
+    pub new_finalizer_policy_diff: Option<FinalizerPolicyDiff>,
+    pub new_proposer_policy_diff: Option<ProposerPolicyDiff>,
+}
+
+impl FinalityExtension {
+    /// ID of the extension in `header_extensions`
+    pub const EXTENSION_ID: u16 = 2;
+
+    /// Decode the extension data, which must hold exactly one extension
+    pub fn from_bin(bin: &[u8]) -> Result<FinalityExtension, AbieosError> {
+        de::from_bytes(bin)
+    }
+
+    /// Encode the extension data
+    pub fn to_bin(&self) -> Result<Vec<u8>, AbieosError> {
+        ser::to_bytes(self)
+    }
+
+    /// Find and decode the extension in the `header_extensions` of a block, `None` for blocks
+    /// produced before Savanna
+    pub fn from_header_extensions(extensions: &[(u16, Vec<u8>)]) -> Result<Option<FinalityExtension>, AbieosError> {
+        extensions
+            .iter()
+            .find(|(id, _)| *id == FinalityExtension::EXTENSION_ID)
+            .map(|(_, data)| FinalityExtension::from_bin(data))
+            .transpose()
+    }
+}
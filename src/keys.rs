@@ -0,0 +1,323 @@
+//! Key and signature string formats (`PUB_K1_`, `PVT_R1_`, `SIG_WA_`, legacy `EOS`...)
+//!
+//! Conversions work on the wire encoding: a varuint32 key type followed by the key data.
+//! BLS keys and signatures (`PUB_BLS_`...) have no type prefix and use base64url instead of base58.
+
+use ripemd::{Digest, Ripemd160};
+
+use crate::stream::{self, InputStream};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64URL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Key curve, as stored in the varuint32 type prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    /// secp256k1
+    K1 = 0,
+    /// secp256r1 (P-256)
+    R1 = 1,
+    /// WebAuthn, a secp256r1 key with its relying party
+    WA = 2,
+}
+
+impl KeyType {
+    fn from_index(index: u32) -> Result<KeyType, String> {
+        match index {
+            0 => Ok(KeyType::K1),
+            1 => Ok(KeyType::R1),
+            2 => Ok(KeyType::WA),
+            _ => Err(format!("unknown key type {}", index)),
+        }
+    }
+
+    /// Name of the type in key strings (`K1` in `PUB_K1_`)
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            KeyType::K1 => "K1",
+            KeyType::R1 => "R1",
+            KeyType::WA => "WA",
+        }
+    }
+}
+
+pub(crate) fn base58_encode(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let mut out = String::with_capacity(zeros + digits.len());
+    out.extend(std::iter::repeat_n('1', zeros));
+    out.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    out
+}
+
+pub(crate) fn base58_decode(s: &str) -> Result<Vec<u8>, String> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len());
+    for c in s.bytes().skip(zeros) {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("invalid base58 character '{}'", c as char))? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut out = vec![0u8; zeros];
+    out.extend(bytes.iter().rev());
+    Ok(out)
+}
+
+/// Encode in base64url, without padding
+fn base64url_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL_ALPHABET[(bits >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+    out
+}
+
+/// Decode base64url, with or without padding
+fn base64url_decode(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim_end_matches('=');
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let (mut bits, mut count) = (0u32, 0u32);
+    for c in s.bytes() {
+        let value = BASE64URL_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("invalid base64url character '{}'", c as char))?;
+        bits = (bits << 6) | value as u32;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            out.push((bits >> count) as u8);
+        }
+    }
+    if count >= 6 || bits & ((1 << count) - 1) != 0 {
+        return Err("invalid base64url length".to_string());
+    }
+    Ok(out)
+}
+
+fn checksum(data: &[u8], suffix: &str) -> [u8; 4] {
+    let mut hasher = Ripemd160::new();
+    hasher.update(data);
+    hasher.update(suffix.as_bytes());
+    let digest = hasher.finalize();
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+fn encode(data: &[u8], suffix: &str) -> String {
+    let mut with_checksum = data.to_vec();
+    with_checksum.extend_from_slice(&checksum(data, suffix));
+    base58_encode(&with_checksum)
+}
+
+fn decode(s: &str, suffix: &str) -> Result<Vec<u8>, String> {
+    let mut data = base58_decode(s)?;
+    if data.len() < 4 {
+        return Err("key data is too short".to_string());
+    }
+    let check = data.split_off(data.len() - 4);
+    if check != checksum(&data, suffix) {
+        return Err("key checksum does not match".to_string());
+    }
+    Ok(data)
+}
+
+/// Format key data as `<prefix>_<type>_<base58 data and checksum>`
+pub(crate) fn to_string(prefix: &str, key_type: KeyType, data: &[u8]) -> String {
+    format!("{}_{}_{}", prefix, key_type.suffix(), encode(data, key_type.suffix()))
+}
+
+/// Format K1 public key data as a legacy `EOS...` string
+pub(crate) fn to_legacy_string(data: &[u8]) -> String {
+    format!("EOS{}", encode(data, ""))
+}
+
+/// Format BLS key or signature data as `<prefix>_BLS_<base64url data and checksum>`
+pub(crate) fn to_bls_string(prefix: &str, data: &[u8]) -> String {
+    let mut with_checksum = data.to_vec();
+    with_checksum.extend_from_slice(&checksum(data, ""));
+    format!("{}_BLS_{}", prefix, base64url_encode(&with_checksum))
+}
+
+/// Parse a `<prefix>_BLS_...` string into its `SIZE` bytes of data, verifying the checksum
+pub(crate) fn bls_from_string<const SIZE: usize>(s: &str, prefix: &str) -> Result<[u8; SIZE], String> {
+    let rest = s
+        .strip_prefix(prefix)
+        .and_then(|r| r.strip_prefix("_BLS_"))
+        .ok_or_else(|| format!("unrecognized {}_BLS_ format: \"{}\"", prefix, s))?;
+    let mut data = base64url_decode(rest)?;
+    if data.len() != SIZE + 4 {
+        return Err(format!("BLS data must be {} bytes, got {}", SIZE, data.len().saturating_sub(4)));
+    }
+    let check = data.split_off(SIZE);
+    if check != checksum(&data, "") {
+        return Err("key checksum does not match".to_string());
+    }
+    Ok(data.try_into().expect("SIZE bytes"))
+}
+
+/// Decode a legacy WIF private key (`5...`), which is always K1, into its key data
+#[cfg(feature = "signing")]
+pub(crate) fn wif_decode(s: &str) -> Result<[u8; 32], String> {
+    let data = base58_decode(s)?;
+    if data.len() != 37 || data[0] != 0x80 {
+        return Err("invalid WIF private key".to_string());
+    }
+    let digest = sha2::Sha256::digest(sha2::Sha256::digest(&data[..33]));
+    if data[33..] != digest[..4] {
+        return Err("WIF checksum does not match".to_string());
+    }
+    Ok(data[1..33].try_into().expect("37 bytes"))
+}
+
+/// Encode K1 private key data in the legacy WIF format
+#[cfg(feature = "signing")]
+pub(crate) fn wif_encode(key: &[u8; 32]) -> String {
+    let mut data = vec![0x80];
+    data.extend_from_slice(key);
+    let digest = sha2::Sha256::digest(sha2::Sha256::digest(&data));
+    data.extend_from_slice(&digest[..4]);
+    base58_encode(&data)
+}
+
+/// Split `PUB_K1_...` style strings into their key type and base58 data
+fn split_prefix<'s>(s: &'s str, prefix: &str) -> Option<(KeyType, &'s str)> {
+    let rest = s.strip_prefix(prefix)?;
+    [KeyType::K1, KeyType::R1, KeyType::WA]
+        .into_iter()
+        .find_map(|t| rest.strip_prefix(t.suffix()).and_then(|r| r.strip_prefix('_')).map(|r| (t, r)))
+}
+
+/// Check the key data of a given type is a well-formed wire encoding
+fn check_data(data: &[u8], key_type: KeyType, fixed_size: usize, read_extra: impl Fn(&mut InputStream) -> Result<(), String>) -> Result<(), String> {
+    if data.len() < fixed_size {
+        return Err(format!("{} data must be at least {} bytes", key_type.suffix(), fixed_size));
+    }
+    let mut s = InputStream::new(&data[fixed_size..]);
+    if key_type == KeyType::WA {
+        read_extra(&mut s)?;
+    }
+    if s.remaining() > 0 {
+        return Err(format!("{} data has {} unexpected trailing bytes", key_type.suffix(), s.remaining()));
+    }
+    Ok(())
+}
+
+fn read_wa_public_key_extra(s: &mut InputStream) -> Result<(), String> {
+    s.read_u8()?;
+    s.read_string()?;
+    Ok(())
+}
+
+fn read_wa_signature_extra(s: &mut InputStream) -> Result<(), String> {
+    s.read_byte_vec()?;
+    s.read_string()?;
+    Ok(())
+}
+
+fn with_type(key_type: KeyType, data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + 1);
+    stream::write_varuint32(&mut out, key_type as u32);
+    out.extend_from_slice(data);
+    out
+}
+
+/// Parse a public key string (`PUB_K1_`, `PUB_R1_`, `PUB_WA_` or legacy `EOS`) into its wire encoding
+pub(crate) fn public_key_from_string(s: &str) -> Result<Vec<u8>, String> {
+    if let Some(legacy) = s.strip_prefix("EOS") {
+        let data = decode(legacy, "")?;
+        check_data(&data, KeyType::K1, 33, |_| Ok(()))?;
+        return Ok(with_type(KeyType::K1, &data));
+    }
+    let (key_type, rest) = split_prefix(s, "PUB_").ok_or_else(|| format!("unrecognized public key format: \"{}\"", s))?;
+    let data = decode(rest, key_type.suffix())?;
+    check_data(&data, key_type, 33, read_wa_public_key_extra)?;
+    Ok(with_type(key_type, &data))
+}
+
+/// Parse a private key string (`PVT_K1_` or `PVT_R1_`) into its wire encoding
+pub(crate) fn private_key_from_string(s: &str) -> Result<Vec<u8>, String> {
+    let (key_type, rest) = split_prefix(s, "PVT_")
+        .filter(|(t, _)| *t != KeyType::WA)
+        .ok_or_else(|| "unrecognized private key format".to_string())?;
+    let data = decode(rest, key_type.suffix())?;
+    check_data(&data, key_type, 32, |_| Ok(()))?;
+    Ok(with_type(key_type, &data))
+}
+
+/// Parse a signature string (`SIG_K1_`, `SIG_R1_` or `SIG_WA_`) into its wire encoding
+pub(crate) fn signature_from_string(s: &str) -> Result<Vec<u8>, String> {
+    let (key_type, rest) = split_prefix(s, "SIG_").ok_or_else(|| format!("unrecognized signature format: \"{}\"", s))?;
+    let data = decode(rest, key_type.suffix())?;
+    check_data(&data, key_type, 65, read_wa_signature_extra)?;
+    Ok(with_type(key_type, &data))
+}
+
+/// Read the varuint32 key type of a wire-encoded key or signature
+pub(crate) fn read_key_type(s: &mut InputStream) -> Result<KeyType, String> {
+    KeyType::from_index(s.read_varuint32()?)
+}
+
+/// Read the key type and capture the remaining encoded data of a key or signature
+fn read_typed<'a>(
+    s: &mut InputStream<'a>,
+    fixed_size: usize,
+    read_extra: impl Fn(&mut InputStream<'a>) -> Result<(), String>,
+) -> Result<(KeyType, Vec<u8>), String> {
+    let key_type = read_key_type(s)?;
+    let mut data = s.read_bytes(fixed_size)?.to_vec();
+    if key_type == KeyType::WA {
+        let rest = s.rest();
+        let before = s.remaining();
+        read_extra(s)?;
+        data.extend_from_slice(&rest[..before - s.remaining()]);
+    }
+    Ok((key_type, data))
+}
+
+/// Read a wire-encoded public key and format it as `PUB_<type>_...`
+pub(crate) fn read_public_key(s: &mut InputStream) -> Result<String, String> {
+    let (key_type, data) = read_typed(s, 33, read_wa_public_key_extra)?;
+    Ok(to_string("PUB", key_type, &data))
+}
+
+/// Read a wire-encoded private key and format it as `PVT_<type>_...`
+pub(crate) fn read_private_key(s: &mut InputStream) -> Result<String, String> {
+    let start = s.clone();
+    let (key_type, data) = read_typed(s, 32, |_| Ok(()))?;
+    if key_type == KeyType::WA {
+        *s = start;
+        return Err(s.error("WA private keys are not supported"));
+    }
+    Ok(to_string("PVT", key_type, &data))
+}
+
+/// Read a wire-encoded signature and format it as `SIG_<type>_...`
+pub(crate) fn read_signature(s: &mut InputStream) -> Result<String, String> {
+    let (key_type, data) = read_typed(s, 65, read_wa_signature_extra)?;
+    Ok(to_string("SIG", key_type, &data))
+}
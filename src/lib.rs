@@ -5,26 +5,111 @@
 //! # Rust Abieos
 //!
 //! Abieos is a Rust wrapper for the abieos C library
+//!
+//! With the `pure-rust` feature, the same API is implemented natively and the C++ library
+//! is not built. Raw context access (`bindings`, `from_context`, `as_ptr`) is then unavailable.
 
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
+#[cfg(not(feature = "pure-rust"))]
 use std::os::raw::c_char;
 
+mod abi_cache;
+mod abi_def;
 mod abieos_error;
-
+mod asset;
+#[cfg(feature = "tokio")]
+mod async_abieos;
+mod bls;
+#[cfg(feature = "bls")]
+mod bls_signing;
+mod builtin;
+pub mod builtin_abis;
+mod conformance;
+mod contract;
+pub mod de;
+mod finality;
+mod json;
+mod keys;
+mod name;
+#[cfg(feature = "pure-rust")]
+mod native;
+mod packed_transaction;
+mod pool;
+mod public_key;
+mod registry;
+mod secondary;
+pub mod ser;
+mod serializer;
+mod signature;
+#[cfg(feature = "signing")]
+mod signing;
+mod snapshot;
+mod stream;
+mod transaction;
+mod value;
+
+pub use abi_def::{
+    AbiDef, AbiExtension, ActionDef, ActionResultDef, ClausePair, ErrorMessage, FieldDef, KvTableDef,
+    PrimaryKeyIndexDef, StructDef, TableDef, TypeDef, VariantDef,
+};
 pub use abieos_error::AbieosError;
-
+pub use asset::{Asset, ExtendedAsset, Symbol, SymbolCode};
+#[cfg(feature = "tokio")]
+pub use async_abieos::AsyncAbieos;
+pub use bls::{BlsPublicKey, BlsSignature};
+#[cfg(feature = "bls")]
+pub use bls_signing::BlsPrivateKey;
+pub use conformance::{Conformance, Divergence, Stage};
+pub use contract::{AbieosContract, ContractHandle};
+pub use finality::{
+    BlockSigningAuthority, FinalityExtension, FinalizerAuthority, FinalizerPolicy, FinalizerPolicyDiff, KeyWeight,
+    OrderedDiff, ProducerAuthority, ProposerPolicyDiff, QcClaim,
+};
+pub use keys::KeyType;
+pub use name::Name;
+pub use packed_transaction::{Compression, PackedTransaction};
+pub use pool::{AbieosPool, PooledAbieos};
+pub use public_key::PublicKey;
+pub use registry::AbiRegistry;
+pub use secondary::{SecondaryIndex, SecondaryKey};
+pub use signature::Signature;
+#[cfg(feature = "signing")]
+pub use signing::{recover_transaction_signers, PrivateKey, SignedTransaction};
+pub use snapshot::AbieosSnapshot;
+pub use transaction::{signing_digest, transaction_id};
+pub use value::AbiValue;
+
+#[cfg(not(feature = "pure-rust"))]
 pub mod bindings {
     include!("bindings.rs");
 }
 
+#[cfg(not(feature = "pure-rust"))]
 pub use bindings::*;
 
+#[cfg(not(feature = "pure-rust"))]
 fn string_from_ptr(ptr: *const c_char) -> String {
     unsafe {
         CStr::from_ptr(ptr).to_str().expect("Failed to convert CStr to str").to_string()
     }
 }
 
+/// Copy a string argument for the C API, strings with an interior nul byte are reported as `error`
+#[cfg(not(feature = "pure-rust"))]
+fn c_string(s: &str, error: fn(String) -> AbieosError) -> Result<CString, AbieosError> {
+    CString::new(s).map_err(|e| error(e.to_string()))
+}
+
+/// Write a JSON value for the C API
+///
+/// serde_json escapes control characters, so the output never has an interior nul byte.
+#[cfg(all(feature = "serde_json", not(feature = "pure-rust")))]
+fn c_json(json: &serde_json::Value, error: fn(String) -> AbieosError) -> Result<CString, AbieosError> {
+    let bytes = serde_json::to_vec(json).map_err(|e| error(e.to_string()))?;
+    CString::new(bytes).map_err(|e| error(e.to_string()))
+}
+
 /// Abieos is a Rust wrapper for the abieos C library.
 ///
 /// # Thread Safety
@@ -39,139 +124,51 @@ fn string_from_ptr(ptr: *const c_char) -> String {
 /// // Each thread gets its own context — no contention
 /// let abieos = Abieos::new();
 /// ```
+///
+/// [`AbieosPool`] manages a set of contexts sharing the same ABIs and leases them to threads,
+/// and [`AbiRegistry`] shares ABIs with a context created on demand in each thread.
 pub struct Abieos {
+    #[cfg(not(feature = "pure-rust"))]
     context: *mut abieos_context,
+    #[cfg(not(feature = "pure-rust"))]
     owns_context: bool,
+    #[cfg(feature = "pure-rust")]
+    native: RefCell<native::NativeContext>,
+    abis: RefCell<abi_cache::AbiCache>,
 }
 
+#[cfg(not(feature = "pure-rust"))]
 unsafe impl Send for Abieos {}
 
-/// Accepted name formats
-pub enum NameLike {
-    String(String),
-    StringRef(&'static str),
-    U64(u64),
-    I32(i32),
-}
-
 /// Accepted ABI formats
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AbiLike {
     Json(String),
     Hex(String),
     Bin(Vec<u8>),
 }
 
-/// Abieos Contract reference
-pub struct AbieosContract {
-    pub context: *mut abieos_context,
-    pub name: u64,
-    pub abiLoaded: bool,
-}
-
-impl AbieosContract {
-    //! # Abieos Contract
-    //!
-    //!  AbieosContract is a contract reference that can be used to load ABIs and serialize/deserialize data
-    //!
-
-    /// Load an ABI from a JSON file
-    pub fn load_json_file(&mut self, path: &str) -> Result<&mut Self, AbieosError> {
-        let ref_abieos = Abieos::from_context(self.context);
-        match std::fs::read_to_string(path) {
-            Ok(file) => {
-                match ref_abieos.set_abi_json_native(self.name, &file) {
-                    Ok(_) => {
-                        self.abiLoaded = true;
-                        Ok(self)
-                    }
-                    Err(e) => Err(e)
-                }
-            }
-            Err(_) => Err(AbieosError::FileRead),
-        }
-    }
-
-    /// Load an ABI
-    pub fn load_abi(&mut self, abi: AbiLike) -> Result<&mut Self, AbieosError> {
-        let ref_abieos = Abieos::from_context(self.context);
-        let result = match abi {
-            AbiLike::Json(abi_json) => {
-                ref_abieos.set_abi_json_native(self.name, &abi_json)
-            }
-            AbiLike::Hex(abi_hex) => {
-                ref_abieos.set_abi_hex_native(self.name, &abi_hex)
-            }
-            AbiLike::Bin(abi_bin) => {
-                ref_abieos.set_abi_bin_native(self.name, &abi_bin)
-            }
-        };
-        match result {
-            Ok(_) => {
-                self.abiLoaded = true;
-                Ok(self)
-            }
-            Err(e) => Err(e)
-        }
-    }
-
-    /// Get data type for an action
-    pub fn get_type_for_action(&self, action: &str) -> Result<String, AbieosError> {
-        let ref_abieos = Abieos::from_context(self.context);
-        match ref_abieos.string_to_name(action) {
-            Ok(x) => ref_abieos.get_type_for_action_native(self.name, x),
-            Err(_) => Err(AbieosError::StringToName)
-        }
-    }
-
-    /// Get data type for a table
-    pub fn get_type_for_table(&self, table: &str) -> Result<String, AbieosError> {
-        let ref_abieos = Abieos::from_context(self.context);
-        match ref_abieos.string_to_name(table) {
-            Ok(x) => ref_abieos.get_type_for_table_native(self.name, x),
-            Err(_) => Err(AbieosError::StringToName)
-        }
-    }
-
-    /// Serialize JSON into binary (output as HEX)
-    pub fn json_to_hex(&self, datatype: &str, json: &str) -> Result<String, AbieosError> {
-        let ref_abieos = Abieos::from_context(self.context);
-        ref_abieos.json_to_hex_native(self.name, datatype, json)
-    }
-
-    /// Deserialize HEX string into JSON
-    pub fn hex_to_json(&self, datatype: &str, hex: &str) -> Result<String, AbieosError> {
-        let ref_abieos = Abieos::from_context(self.context);
-        ref_abieos.hex_to_json_native(self.name, datatype, hex)
-    }
-}
-
-impl AbieosContract {
-    pub fn new(context: *mut abieos_context, name: u64) -> AbieosContract {
-        AbieosContract { context, name, abiLoaded: false }
-    }
-}
-
 impl Abieos {
     /// Reference a contract by name
-    pub fn contract(&self, account_name: NameLike) -> AbieosContract {
-        match account_name {
-            NameLike::StringRef(name) => {
-                let name_u64 = self.string_to_name(name)
-                    .expect("Failed to convert name to u64");
-                AbieosContract::new(self.ctx(), name_u64)
-            }
-            NameLike::String(name) => {
-                let name_u64 = self.string_to_name(name.as_str())
-                    .expect("Failed to convert name to u64");
-                AbieosContract::new(self.ctx(), name_u64)
-            }
-            NameLike::U64(name) => {
-                AbieosContract::new(self.ctx(), name)
-            }
-            NameLike::I32(name) => {
-                AbieosContract::new(self.ctx(), name as u64)
-            }
-        }
+    ///
+    /// String names must be parsed into a [`Name`] first, so invalid names are reported
+    /// as errors instead of panicking:
+    /// ```rust,no_run
+    /// use rs_abieos::{Abieos, Name};
+    /// let abieos = Abieos::new();
+    /// let name: Name = "eosio.token".parse().unwrap();
+    /// let contract = abieos.contract(name);
+    /// ```
+    pub fn contract(&self, account_name: impl Into<Name>) -> ContractHandle<'_> {
+        ContractHandle::new(self, account_name.into())
+    }
+
+    /// Returns `true` if an ABI was loaded for the contract through this wrapper
+    ///
+    /// Loaded contracts are tracked on the Rust side by the `set_abi_*` and `delete_contract*`
+    /// methods. Non-owning wrappers created with [`Abieos::from_context`] start with an empty record.
+    pub fn has_contract(&self, contract: impl Into<Name>) -> bool {
+        self.abis.borrow().contains(contract.into())
     }
 }
 
@@ -181,6 +178,7 @@ impl Default for Abieos {
     }
 }
 
+#[cfg(not(feature = "pure-rust"))]
 impl Drop for Abieos {
     fn drop(&mut self) {
         if self.owns_context && !self.context.is_null() {
@@ -189,6 +187,7 @@ impl Drop for Abieos {
     }
 }
 
+#[cfg(not(feature = "pure-rust"))]
 impl Abieos {
     /// Create a new Abieos instance.
     ///
@@ -197,6 +196,7 @@ impl Abieos {
         Abieos {
             context: abieos::create(),
             owns_context: true,
+            abis: Default::default(),
         }
     }
 
@@ -209,6 +209,7 @@ impl Abieos {
         Abieos {
             context,
             owns_context: false,
+            abis: Default::default(),
         }
     }
 
@@ -228,165 +229,100 @@ impl Abieos {
         }
     }
 
+    /// Run `f` with the pure-Rust serializer of a loaded contract
+    ///
+    /// Only ABIs loaded through this wrapper are available.
+    pub(crate) fn with_abi<T>(&self, contract: Name, f: impl FnOnce(&serializer::AbiSerializer) -> Result<T, String>) -> Result<T, String> {
+        let abis = self.abis.borrow();
+        f(abis.serializer(contract)?)
+    }
+
     /// Get the context pointer
     fn ctx(&self) -> *mut abieos_context {
         self.context
     }
 
-    /// Convert a string slice into an u64 native name
-    pub fn string_to_name(&self, name: &str) -> Result<u64, AbieosError> {
-        let ctx = self.ctx();
-        if name.len() > 13 {
-            return Err(AbieosError::NameTooLong);
-        }
-        unsafe {
-            let c_buf = CString::new(name.as_bytes()).unwrap();
-            Ok(abieos_string_to_name(ctx, c_buf.as_ptr()))
-        }
-    }
-
-    /// Convert a C-string into an u64 native name
-    pub fn c_string_to_name(&self, name: &CStr) -> u64 {
-        let ctx = self.ctx();
-        if name.to_bytes().len() > 13 {
-            return 0;
-        }
-        unsafe {
-            abieos_string_to_name(ctx, name.as_ptr())
-        }
-    }
-
-    /// Convert an u64 native name into a string slice
-    pub fn name_to_string(&self, name: u64) -> Result<&str, AbieosError> {
+    /// Copy the binary result of the last serialization out of the context
+    fn get_bin(&self) -> Vec<u8> {
         let ctx = self.ctx();
         unsafe {
-            let c_buf = abieos_name_to_string(ctx, name);
-            match CStr::from_ptr(c_buf).to_str() {
-                Ok(x) => Ok(x),
-                Err(_) => Err(AbieosError::NameToString),
-            }
-        }
-    }
-
-    /// Convert an u64 native name into a string slice
-    pub fn name_to_cstr(&self, name: u64) -> &CStr {
-        let ctx = self.ctx();
-        unsafe {
-            let c_buf = abieos_name_to_string(ctx, name);
-            CStr::from_ptr(c_buf)
-        }
-    }
-
-    /// Load a contract ABI to memory (JSON format)
-    pub fn set_abi_json(&self, contract: &str, abi_json: &str) -> Result<bool, AbieosError> {
-        match self.string_to_name(contract) {
-            Ok(contract_u64) => unsafe {
-                let abi_content_cs = CString::new(abi_json).unwrap();
-                match abieos_set_abi(self.ctx(), contract_u64, abi_content_cs.as_ptr()) {
-                    1 => Ok(true),
-                    _ => Err(AbieosError::SetAbi(self.get_error()))
-                }
+            let p = abieos_get_bin_data(ctx) as *const u8;
+            let len = abieos_get_bin_size(ctx) as usize;
+            if p.is_null() || len == 0 {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(p, len).to_vec()
             }
-            Err(_) => Err(AbieosError::StringToName)
         }
     }
 
     /// Load a contract ABI to memory (JSON format as C-String)
-    pub fn set_abi_json_c(&self, contract: &str, abi_json: &CStr) -> Result<bool, AbieosError> {
-        match self.string_to_name(contract) {
-            Ok(contract_u64) => unsafe {
-                match abieos_set_abi(self.ctx(), contract_u64, abi_json.as_ptr()) {
-                    1 => Ok(true),
-                    _ => Err(AbieosError::SetAbi(self.get_error()))
-                }
-            }
-            Err(_) => Err(AbieosError::StringToName)
-        }
-    }
-
-    /// Load a contract ABI to memory (JSON format)
-    pub fn set_abi_json_native(&self, contract_u64: u64, abi_json: &str) -> Result<bool, AbieosError> {
-        let abi_content_cs = CString::new(abi_json).unwrap();
+    pub fn set_abi_json_c(&self, contract: impl Into<Name>, abi_json: &CStr) -> Result<bool, AbieosError> {
+        let contract: Name = contract.into();
         unsafe {
-            match abieos_set_abi(self.ctx(), contract_u64, abi_content_cs.as_ptr()) {
-                1 => Ok(true),
-                _ => Err(AbieosError::SetAbi(self.get_error()))
-            }
-        }
-    }
-
-    /// Load a contract ABI to memory (HEX format)
-    pub fn set_abi_hex(&self, contract: &str, abi_hex: &str) -> Result<bool, AbieosError> {
-        let abi_hex_cs = CString::new(abi_hex).unwrap();
-        match self.string_to_name(contract) {
-            Ok(contract_u64) => unsafe {
-                match abieos_set_abi_hex(self.ctx(), contract_u64, abi_hex_cs.as_ptr()) {
-                    1 => Ok(true),
-                    _ => Err(AbieosError::SetAbi(self.get_error()))
+            match abieos_set_abi(self.ctx(), contract.as_u64(), abi_json.as_ptr()) {
+                1 => {
+                    let abi_json = abi_json.to_string_lossy().into_owned();
+                    self.record_abi(contract, Some(AbiLike::Json(abi_json)));
+                    Ok(true)
                 }
+                _ => Err(AbieosError::SetAbi(self.get_error()))
             }
-            Err(_) => Err(AbieosError::StringToName)
         }
     }
 
-    /// Load a contract ABI to memory (HEX format, u64 contract name)
-    pub fn set_abi_hex_native(&self, contract: u64, abi_hex: &str) -> Result<bool, AbieosError> {
-        let abi_hex_cs = CString::new(abi_hex).unwrap();
+    /// Load a contract ABI to memory (JSON format, native contract name)
+    pub fn set_abi_json_native(&self, contract: impl Into<Name>, abi_json: &str) -> Result<bool, AbieosError> {
+        let abi_content_cs = c_string(abi_json, AbieosError::SetAbi)?;
+        let contract: Name = contract.into();
         unsafe {
-            match abieos_set_abi_hex(self.ctx(), contract, abi_hex_cs.as_ptr()) {
-                1 => Ok(true),
+            match abieos_set_abi(self.ctx(), contract.as_u64(), abi_content_cs.as_ptr()) {
+                1 => {
+                    self.record_abi(contract, Some(AbiLike::Json(abi_json.to_string())));
+                    Ok(true)
+                }
                 _ => Err(AbieosError::SetAbi(self.get_error()))
             }
         }
     }
 
-    /// Load a contract ABI to memory (binary format)
-    pub fn set_abi_bin(&self, contract: &str, abi_bin: &[u8]) -> Result<bool, AbieosError> {
-        match self.string_to_name(contract) {
-            Ok(contract_u64) => unsafe {
-                let abi_bin_data: *const c_char = abi_bin.as_ptr() as *const c_char;
-                let abi_bin_size: usize = abi_bin.len();
-                match abieos_set_abi_bin(self.ctx(), contract_u64, abi_bin_data, abi_bin_size) {
-                    1 => Ok(true),
-                    _ => Err(AbieosError::SetAbi(self.get_error()))
+    /// Load a contract ABI to memory (HEX format, native contract name)
+    pub fn set_abi_hex_native(&self, contract: impl Into<Name>, abi_hex: &str) -> Result<bool, AbieosError> {
+        let abi_hex_cs = c_string(abi_hex, AbieosError::SetAbi)?;
+        let contract: Name = contract.into();
+        unsafe {
+            match abieos_set_abi_hex(self.ctx(), contract.as_u64(), abi_hex_cs.as_ptr()) {
+                1 => {
+                    self.record_abi(contract, Some(AbiLike::Hex(abi_hex.to_string())));
+                    Ok(true)
                 }
+                _ => Err(AbieosError::SetAbi(self.get_error()))
             }
-            Err(_) => Err(AbieosError::StringToName)
         }
     }
 
-    /// Load a contract ABI to memory (binary format, u64 contract name)
-    pub fn set_abi_bin_native(&self, contract: u64, abi_bin: &[u8]) -> Result<bool, AbieosError> {
+    /// Load a contract ABI to memory (binary format, native contract name)
+    pub fn set_abi_bin_native(&self, contract: impl Into<Name>, abi_bin: &[u8]) -> Result<bool, AbieosError> {
         let abi_bin_data: *const c_char = abi_bin.as_ptr() as *const c_char;
         let abi_bin_size: usize = abi_bin.len();
+        let contract: Name = contract.into();
         unsafe {
-            match abieos_set_abi_bin(self.ctx(), contract, abi_bin_data, abi_bin_size) {
-                1 => Ok(true),
-                _ => Err(AbieosError::SetAbi(self.get_error()))
-            }
-        }
-    }
-
-    /// Serialize JSON into binary (output as HEX)
-    pub fn json_to_hex(&self, account: &str, datatype: &str, json: &str) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let account = self.string_to_name(account)?;
-
-        let datatype = CString::new(datatype).unwrap();
-        let json = CString::new(json).unwrap();
-        unsafe {
-            match abieos_json_to_bin_reorderable(ctx, account, datatype.as_ptr(), json.as_ptr()) {
+            match abieos_set_abi_bin(self.ctx(), contract.as_u64(), abi_bin_data, abi_bin_size) {
                 1 => {
-                    let p = abieos_get_bin_hex(ctx);
-                    Ok(string_from_ptr(p))
+                    self.record_abi(contract, Some(AbiLike::Bin(abi_bin.to_vec())));
+                    Ok(true)
                 }
-                _ => Err(AbieosError::JsonToHex(self.get_error()))
+                _ => Err(AbieosError::SetAbi(self.get_error()))
             }
         }
     }
 
     /// Serialize JSON into binary (output as HEX)
-    pub fn json_to_hex_c(&self, account: &CStr, datatype: &CStr, json: &CStr) -> &CStr {
+    ///
+    /// The returned C-string points into the context result buffer, which is overwritten
+    /// by the next call. It borrows `self` mutably, so the borrow checker rejects any other
+    /// call on this context while the result is alive. Use `.to_owned()` to keep it longer.
+    pub fn json_to_hex_c(&mut self, account: &CStr, datatype: &CStr, json: &CStr) -> &CStr {
         let ctx = self.ctx();
         let account = self.c_string_to_name(account);
         unsafe {
@@ -400,123 +336,121 @@ impl Abieos {
         }
     }
 
-    /// Serialize JSON into binary (output as HEX, u64 account name)
-    pub fn json_to_hex_native(&self, account: u64, datatype: &str, json: &str) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let datatype = CString::new(datatype).unwrap();
-        let json = CString::new(json).unwrap();
+    /// Serialize a JSON C-string, leaving the result in the context
+    fn json_to_bin_raw(&self, account: Name, datatype: &str, json: &CStr, error: fn(String) -> AbieosError) -> Result<(), AbieosError> {
+        let datatype = c_string(datatype, error)?;
         unsafe {
-            match abieos_json_to_bin_reorderable(ctx, account, datatype.as_ptr(), json.as_ptr()) {
-                1 => {
-                    let p = abieos_get_bin_hex(ctx);
-                    Ok(string_from_ptr(p))
-                }
-                _ => Err(AbieosError::JsonToHex(self.get_error()))
+            match abieos_json_to_bin_reorderable(self.ctx(), account.as_u64(), datatype.as_ptr(), json.as_ptr()) {
+                1 => Ok(()),
+                _ => Err(error(self.get_error()))
             }
         }
     }
 
-    /// Serialize JSON into binary (output as binary)
-    pub fn json_to_bin(&self, account: &str, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
-        let ctx = self.ctx();
-        let account = self.string_to_name(account)?;
-        let datatype = CString::new(datatype).unwrap();
-        let json = CString::new(json).unwrap();
-        unsafe {
-            match abieos_json_to_bin_reorderable(ctx, account, datatype.as_ptr(), json.as_ptr()) {
-                1 => {
-                    let p = abieos_get_bin_data(ctx);
-                    let len = abieos_get_bin_size(ctx);
-                    let mut result: Vec<c_char> = Vec::with_capacity(len as usize);
-                    std::ptr::copy_nonoverlapping(p, result.as_mut_ptr(), len as usize);
-                    result.set_len(len as usize);
-                    Ok(result.iter().map(|&c| c as u8).collect())
-                }
-                _ => Err(AbieosError::JsonToBin(self.get_error()))
-            }
-        }
+    /// Serialize JSON into binary (output as HEX, native account name)
+    pub fn json_to_hex_native(&self, account: impl Into<Name>, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        let json = c_string(json, AbieosError::JsonToHex)?;
+        self.json_to_bin_raw(account.into(), datatype, &json, AbieosError::JsonToHex)?;
+        Ok(string_from_ptr(unsafe { abieos_get_bin_hex(self.ctx()) }))
     }
 
-    /// Deserialize HEX string into JSON
-    pub fn hex_to_json(&self, account: &str, datatype: &str, hex: &str) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let account = self.string_to_name(account)?;
+    /// Serialize JSON into binary (output as binary, native account name)
+    pub fn json_to_bin_native(&self, account: impl Into<Name>, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let json = c_string(json, AbieosError::JsonToBin)?;
+        self.json_to_bin_raw(account.into(), datatype, &json, AbieosError::JsonToBin)?;
+        Ok(self.get_bin())
+    }
 
-        let datatype = CString::new(datatype).unwrap();
-        let hex = CString::new(hex).unwrap();
-        unsafe {
-            let p = abieos_hex_to_json(ctx, account, datatype.as_ptr(), hex.as_ptr());
-            if p.is_null() {
-                Err(AbieosError::HexToJson(self.get_error()))
-            } else {
-                Ok(string_from_ptr(p))
-            }
-        }
+    /// Serialize a `serde_json::Value` into binary (output as HEX)
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_hex_value(&self, account: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<String, AbieosError> {
+        let json = c_json(json, AbieosError::JsonToHex)?;
+        self.json_to_bin_raw(account.into(), datatype, &json, AbieosError::JsonToHex)?;
+        Ok(string_from_ptr(unsafe { abieos_get_bin_hex(self.ctx()) }))
     }
 
+    /// Serialize a `serde_json::Value` into binary
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_bin_value(&self, account: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<Vec<u8>, AbieosError> {
+        let json = c_json(json, AbieosError::JsonToBin)?;
+        self.json_to_bin_raw(account.into(), datatype, &json, AbieosError::JsonToBin)?;
+        Ok(self.get_bin())
+    }
 
     /// Deserialize HEX string into JSON
-    pub fn hex_to_json_c(&self, account: &CStr, datatype: &CStr, hex: &CStr) -> &CStr {
+    ///
+    /// Like [`Abieos::json_to_hex_c`], the result is tied to a mutable borrow of `self`.
+    /// An empty C-string is returned on error.
+    pub fn hex_to_json_c(&mut self, account: &CStr, datatype: &CStr, hex: &CStr) -> &CStr {
         let ctx = self.ctx();
         let account = self.c_string_to_name(account);
         unsafe {
             let p = abieos_hex_to_json(ctx, account, datatype.as_ptr(), hex.as_ptr());
-            CStr::from_ptr(p)
-        }
-    }
-
-    /// Deserialize Binary into JSON
-    pub fn bin_to_json(&self, account: &str, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let account = self.string_to_name(account)?;
-        let datatype = CString::new(datatype).unwrap();
-        let bin_data: *const c_char = bin.as_ptr() as *const c_char;
-        let bin_size: usize = bin.len();
-        unsafe {
-            let p = abieos_bin_to_json(ctx, account, datatype.as_ptr(), bin_data, bin_size);
             if p.is_null() {
-                Err(AbieosError::BinToJson(self.get_error()))
+                c""
             } else {
-                Ok(string_from_ptr(p))
+                CStr::from_ptr(p)
             }
         }
     }
 
-    /// Deserialize HEX string into JSON (u64 account name)
-    pub fn hex_to_json_native(&self, account: u64, datatype: &str, hex: &str) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let datatype = CString::new(datatype).unwrap();
-        let hex = CString::new(hex).unwrap();
+    /// Deserialize HEX string into JSON, returning the context result buffer
+    fn hex_to_json_raw(&self, account: Name, datatype: &str, hex: &str) -> Result<&CStr, AbieosError> {
+        let datatype = c_string(datatype, AbieosError::HexToJson)?;
+        let hex = c_string(hex, AbieosError::HexToJson)?;
         unsafe {
-            let p = abieos_hex_to_json(ctx, account, datatype.as_ptr(), hex.as_ptr());
+            let p = abieos_hex_to_json(self.ctx(), account.as_u64(), datatype.as_ptr(), hex.as_ptr());
             if p.is_null() {
                 Err(AbieosError::HexToJson(self.get_error()))
             } else {
-                Ok(string_from_ptr(p))
+                Ok(CStr::from_ptr(p))
             }
         }
     }
 
-
-    /// Get the type for an action (string names as input)
-    pub fn get_type_for_action(&self, contract: &str, action: &str) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let contract = self.string_to_name(contract)?;
-        let action = self.string_to_name(action)?;
+    /// Deserialize Binary into JSON, returning the context result buffer
+    fn bin_to_json_raw(&self, account: Name, datatype: &str, bin: &[u8]) -> Result<&CStr, AbieosError> {
+        let datatype = c_string(datatype, AbieosError::BinToJson)?;
+        let bin_data: *const c_char = bin.as_ptr() as *const c_char;
+        let bin_size: usize = bin.len();
         unsafe {
-            let p = abieos_get_type_for_action(ctx, contract, action);
+            let p = abieos_bin_to_json(self.ctx(), account.as_u64(), datatype.as_ptr(), bin_data, bin_size);
             if p.is_null() {
-                Err(AbieosError::GetTypeForAction(self.get_error()))
+                Err(AbieosError::BinToJson(self.get_error()))
             } else {
-                Ok(string_from_ptr(p))
+                Ok(CStr::from_ptr(p))
             }
         }
     }
 
-    /// Get the type for an action (u64 names as input)
-    pub fn get_type_for_action_native(&self, contract: u64, action: u64) -> Result<String, AbieosError> {
+    /// Deserialize HEX string into JSON (native account name)
+    pub fn hex_to_json_native(&self, account: impl Into<Name>, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        self.hex_to_json_raw(account.into(), datatype, hex).map(|json| json.to_string_lossy().into_owned())
+    }
+
+    /// Deserialize Binary into JSON (native account name)
+    pub fn bin_to_json_native(&self, account: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.bin_to_json_raw(account.into(), datatype, bin).map(|json| json.to_string_lossy().into_owned())
+    }
+
+    /// Deserialize HEX string into a `serde_json::Value`
+    #[cfg(feature = "serde_json")]
+    pub fn hex_to_json_value(&self, account: impl Into<Name>, datatype: &str, hex: &str) -> Result<serde_json::Value, AbieosError> {
+        let json = self.hex_to_json_raw(account.into(), datatype, hex)?;
+        serde_json::from_slice(json.to_bytes()).map_err(|e| AbieosError::HexToJson(e.to_string()))
+    }
+
+    /// Deserialize Binary into a `serde_json::Value`
+    #[cfg(feature = "serde_json")]
+    pub fn bin_to_json_value(&self, account: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<serde_json::Value, AbieosError> {
+        let json = self.bin_to_json_raw(account.into(), datatype, bin)?;
+        serde_json::from_slice(json.to_bytes()).map_err(|e| AbieosError::BinToJson(e.to_string()))
+    }
+
+    /// Get the type for an action (native names as input)
+    pub fn get_type_for_action_native(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
         let ctx = self.ctx();
-        let p = unsafe { abieos_get_type_for_action(ctx, contract, action) };
+        let p = unsafe { abieos_get_type_for_action(ctx, contract.into().as_u64(), action.into().as_u64()) };
         if p.is_null() {
             Err(AbieosError::GetTypeForAction(self.get_error()))
         } else {
@@ -524,25 +458,10 @@ impl Abieos {
         }
     }
 
-    /// Get the type for a table
-    pub fn get_type_for_table(&self, contract: &str, table: &str) -> Result<String, AbieosError> {
+    /// Get the type for a table (native names as input)
+    pub fn get_type_for_table_native(&self, contract: impl Into<Name>, table: impl Into<Name>) -> Result<String, AbieosError> {
         let ctx = self.ctx();
-        let contract = self.string_to_name(contract)?;
-        let table = self.string_to_name(table)?;
-        unsafe {
-            let p = abieos_get_type_for_table(ctx, contract, table);
-            if p.is_null() {
-                Err(AbieosError::GetTypeForTable(self.get_error()))
-            } else {
-                Ok(string_from_ptr(p))
-            }
-        }
-    }
-
-    /// Get the type for a table (u64 names as input)
-    pub fn get_type_for_table_native(&self, contract: u64, table: u64) -> Result<String, AbieosError> {
-        let ctx = self.ctx();
-        let p = unsafe { abieos_get_type_for_table(ctx, contract, table) };
+        let p = unsafe { abieos_get_type_for_table(ctx, contract.into().as_u64(), table.into().as_u64()) };
         if p.is_null() {
             Err(AbieosError::GetTypeForTable(self.get_error()))
         } else {
@@ -550,18 +469,14 @@ impl Abieos {
         }
     }
 
-    /// Get the type for an action result
-    pub fn get_type_for_action_result(&self, contract: &str, action: &str) -> Result<String, AbieosError> {
+    /// Get the type for an action result (native names as input)
+    pub fn get_type_for_action_result_native(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
         let ctx = self.ctx();
-        let contract = self.string_to_name(contract)?;
-        let action_res = self.string_to_name(action)?;
-        unsafe {
-            let p = abieos_get_type_for_action_result(ctx, contract, action_res);
-            if p.is_null() {
-                Err(AbieosError::GetTypeForActionResult(self.get_error()))
-            } else {
-                Ok(string_from_ptr(p))
-            }
+        let p = unsafe { abieos_get_type_for_action_result(ctx, contract.into().as_u64(), action.into().as_u64()) };
+        if p.is_null() {
+            Err(AbieosError::GetTypeForActionResult(self.get_error()))
+        } else {
+            Ok(string_from_ptr(p))
         }
     }
 
@@ -587,33 +502,22 @@ impl Abieos {
     /// Convert ABI JSON to binary
     pub fn abi_json_to_bin(&self, json: &str) -> Result<Vec<u8>, AbieosError> {
         let ctx = self.ctx();
-        let abi_json: CString = CString::new(json).unwrap();
+        let abi_json = c_string(json, AbieosError::AbiJsonToBin)?;
         unsafe {
             match abieos_abi_json_to_bin(ctx, abi_json.as_ptr()) {
-                1 => {
-                    let p = abieos_get_bin_data(ctx);
-                    let len = abieos_get_bin_size(ctx);
-                    let mut result: Vec<c_char> = Vec::with_capacity(len as usize);
-                    std::ptr::copy_nonoverlapping(p, result.as_mut_ptr(), len as usize);
-                    result.set_len(len as usize);
-                    Ok(result.iter().map(|&c| c as u8).collect())
-                }
+                1 => Ok(self.get_bin()),
                 _ => Err(AbieosError::AbiJsonToBin(self.get_error()))
             }
         }
     }
 
-    /// Delete a contract from the context (string name)
-    pub fn delete_contract(&self, contract: &str) -> Result<bool, AbieosError> {
-        let contract_u64 = self.string_to_name(contract)?;
-        self.delete_contract_native(contract_u64)
-    }
-
-    /// Delete a contract from the context (u64 name)
-    pub fn delete_contract_native(&self, contract: u64) -> Result<bool, AbieosError> {
+    /// Delete a contract from the context (native name)
+    pub fn delete_contract_native(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
         let ctx = self.ctx();
+        let contract: Name = contract.into();
+        self.record_abi(contract, None);
         unsafe {
-            match abieos_delete_contract(ctx, contract) {
+            match abieos_delete_contract(ctx, contract.as_u64()) {
                 1 => Ok(true),
                 _ => Ok(false),
             }
@@ -621,6 +525,254 @@ impl Abieos {
     }
 }
 
+impl Abieos {
+    /// Record the ABI loaded for a contract (`None` once deleted) on the Rust side
+    fn record_abi(&self, contract: Name, abi: Option<AbiLike>) {
+        let mut abis = self.abis.borrow_mut();
+        match abi {
+            Some(abi) => abis.insert(contract, abi),
+            None => abis.remove(contract),
+        }
+    }
+
+    /// Load a contract ABI in any accepted format
+    pub(crate) fn set_abi_like(&self, contract: Name, abi: &AbiLike) -> Result<bool, AbieosError> {
+        match abi {
+            AbiLike::Json(abi_json) => self.set_abi_json_native(contract, abi_json),
+            AbiLike::Hex(abi_hex) => self.set_abi_hex_native(contract, abi_hex),
+            AbiLike::Bin(abi_bin) => self.set_abi_bin_native(contract, abi_bin),
+        }
+    }
+
+    /// Convert a string slice into an u64 native name
+    ///
+    /// The conversion is done in pure Rust by [`Name`], invalid names are rejected.
+    pub fn string_to_name(&self, name: &str) -> Result<u64, AbieosError> {
+        name.parse::<Name>().map(u64::from)
+    }
+
+    /// Convert a C-string into an u64 native name (returns 0 for invalid names)
+    pub fn c_string_to_name(&self, name: &CStr) -> u64 {
+        match name.to_str() {
+            Ok(name) => name.parse::<Name>().map_or(0, u64::from),
+            Err(_) => 0,
+        }
+    }
+
+    /// Convert an u64 native name into an owned string
+    ///
+    /// The conversion is done in pure Rust by [`Name`], so the result does not
+    /// borrow from the C context.
+    pub fn name_to_string(&self, name: impl Into<Name>) -> Result<String, AbieosError> {
+        Ok(name.into().to_string())
+    }
+
+    /// Convert an u64 native name into an owned C-string
+    pub fn name_to_cstr(&self, name: impl Into<Name>) -> CString {
+        // names only contain [.1-5a-z], so there is never an interior nul byte
+        CString::new(name.into().to_string()).unwrap_or_default()
+    }
+
+    /// Load a contract ABI to memory (JSON format)
+    pub fn set_abi_json(&self, contract: impl Into<Name>, abi_json: &str) -> Result<bool, AbieosError> {
+        self.set_abi_json_native(contract, abi_json)
+    }
+
+    /// Load a contract ABI to memory (HEX format)
+    pub fn set_abi_hex(&self, contract: impl Into<Name>, abi_hex: &str) -> Result<bool, AbieosError> {
+        self.set_abi_hex_native(contract, abi_hex)
+    }
+
+    /// Load a contract ABI to memory from a parsed [`AbiDef`]
+    pub fn set_abi_def(&self, contract: impl Into<Name>, abi: &AbiDef) -> Result<bool, AbieosError> {
+        self.set_abi_bin_native(contract, &abi.to_bin()?)
+    }
+
+    /// Load a contract ABI to memory (binary format)
+    pub fn set_abi_bin(&self, contract: impl Into<Name>, abi_bin: &[u8]) -> Result<bool, AbieosError> {
+        self.set_abi_bin_native(contract, abi_bin)
+    }
+
+    /// Serialize JSON into binary (output as HEX)
+    pub fn json_to_hex(&self, account: &str, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        let account: Name = account.parse()?;
+        self.json_to_hex_native(account, datatype, json)
+    }
+
+    /// Serialize JSON into binary (output as binary)
+    pub fn json_to_bin(&self, account: &str, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let account: Name = account.parse()?;
+        self.json_to_bin_native(account, datatype, json)
+    }
+
+    /// Deserialize HEX string into JSON
+    pub fn hex_to_json(&self, account: &str, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        let account: Name = account.parse()?;
+        self.hex_to_json_native(account, datatype, hex)
+    }
+
+    /// Deserialize Binary into JSON
+    pub fn bin_to_json(&self, account: &str, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        let account: Name = account.parse()?;
+        self.bin_to_json_native(account, datatype, bin)
+    }
+
+    /// Get the type for an action
+    pub fn get_type_for_action(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        self.get_type_for_action_native(contract, action)
+    }
+
+    /// Get the type for a table
+    pub fn get_type_for_table(&self, contract: impl Into<Name>, table: impl Into<Name>) -> Result<String, AbieosError> {
+        self.get_type_for_table_native(contract, table)
+    }
+
+    /// Get the type for an action result
+    pub fn get_type_for_action_result(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        self.get_type_for_action_result_native(contract, action)
+    }
+
+    /// Delete a contract from the context
+    pub fn delete_contract(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
+        self.delete_contract_native(contract)
+    }
+
+    /// Error for a type the contract ABI does not declare, [`AbieosError::AbiNotLoaded`] if no ABI
+    /// was loaded for the contract
+    fn unresolved_type(&self, contract: Name, unknown: AbieosError) -> AbieosError {
+        if self.has_contract(contract) {
+            unknown
+        } else {
+            AbieosError::AbiNotLoaded
+        }
+    }
+
+    /// Type of an action, [`AbieosError::UnknownAction`] if the ABI does not declare it
+    fn action_type(&self, contract: Name, action: Name) -> Result<String, AbieosError> {
+        self.get_type_for_action_native(contract, action)
+            .map_err(|_| self.unresolved_type(contract, AbieosError::UnknownAction(format!("{}::{}", contract, action))))
+    }
+
+    /// Serialize the data of an action, resolving its type through the contract ABI
+    ///
+    /// Fails with [`AbieosError::UnknownAction`] if the ABI has no such action.
+    pub fn encode_action(&self, contract: impl Into<Name>, action: impl Into<Name>, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let contract = contract.into();
+        let datatype = self.action_type(contract, action.into())?;
+        self.json_to_bin_native(contract, &datatype, json)
+    }
+
+    /// Deserialize the data of an action into JSON, resolving its type through the contract ABI
+    ///
+    /// Fails with [`AbieosError::UnknownAction`] if the ABI has no such action.
+    pub fn decode_action(&self, contract: impl Into<Name>, action: impl Into<Name>, bin: &[u8]) -> Result<String, AbieosError> {
+        let contract = contract.into();
+        let datatype = self.action_type(contract, action.into())?;
+        self.bin_to_json_native(contract, &datatype, bin)
+    }
+
+    /// Deserialize the return value of an action into JSON, using the type from the ABI `action_results`
+    ///
+    /// Fails with [`AbieosError::UnknownActionResult`] if the ABI declares no result type for the action.
+    pub fn decode_action_result(&self, contract: impl Into<Name>, action: impl Into<Name>, bin: &[u8]) -> Result<String, AbieosError> {
+        let (contract, action) = (contract.into(), action.into());
+        let datatype = self.get_type_for_action_result_native(contract, action).map_err(|_| {
+            self.unresolved_type(contract, AbieosError::UnknownActionResult(format!("{}::{}", contract, action)))
+        })?;
+        self.bin_to_json_native(contract, &datatype, bin)
+    }
+
+    /// Type of a table row, [`AbieosError::UnknownTable`] if the ABI does not declare the table
+    fn table_type(&self, contract: Name, table: Name) -> Result<String, AbieosError> {
+        self.get_type_for_table_native(contract, table)
+            .map_err(|_| self.unresolved_type(contract, AbieosError::UnknownTable(format!("{}::{}", contract, table))))
+    }
+
+    /// Serialize a table row, resolving its type through the contract ABI
+    ///
+    /// Fails with [`AbieosError::UnknownTable`] if the ABI has no such table.
+    pub fn encode_table_row(&self, contract: impl Into<Name>, table: impl Into<Name>, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let contract = contract.into();
+        let datatype = self.table_type(contract, table.into())?;
+        self.json_to_bin_native(contract, &datatype, json)
+    }
+
+    /// Deserialize a table row into JSON, resolving its type through the contract ABI
+    ///
+    /// Fails with [`AbieosError::UnknownTable`] if the ABI has no such table.
+    pub fn decode_table_row(&self, contract: impl Into<Name>, table: impl Into<Name>, bin: &[u8]) -> Result<String, AbieosError> {
+        let contract = contract.into();
+        let datatype = self.table_type(contract, table.into())?;
+        self.bin_to_json_native(contract, &datatype, bin)
+    }
+
+    /// Read the primary key of a table row
+    ///
+    /// The key is the row field named by the first of the table `key_names`. Returns `None` if
+    /// the ABI declares no key, or if the key is computed by the contract rather than stored in
+    /// a field (like `accounts` in eosio.token, keyed by the balance symbol code).
+    pub fn table_row_primary_key(&self, contract: impl Into<Name>, table: impl Into<Name>, bin: &[u8]) -> Result<Option<u64>, AbieosError> {
+        let (contract, table) = (contract.into(), table.into());
+        let datatype = self.table_type(contract, table)?;
+        let key = self
+            .with_abi(contract, |abi| Ok(abi.table_key(table).map(str::to_string)))
+            .map_err(AbieosError::InvalidAbi)?;
+        let Some(key) = key else { return Ok(None) };
+        let row = self.bin_to_value(contract, &datatype, bin)?;
+        let Some(value) = row.get(&key) else { return Ok(None) };
+        match value {
+            AbiValue::Uint8(v) => Ok(Some(u64::from(*v))),
+            AbiValue::Uint16(v) => Ok(Some(u64::from(*v))),
+            AbiValue::Uint32(v) => Ok(Some(u64::from(*v))),
+            AbiValue::Uint64(v) => Ok(Some(*v)),
+            AbiValue::Name(v) => Ok(Some(v.as_u64())),
+            AbiValue::Symbol(v) => Ok(Some(v.as_u64())),
+            AbiValue::SymbolCode(v) => Ok(Some(v.as_u64())),
+            _ => Err(AbieosError::Deserialize(format!("primary key \"{}\" of {}::{} is not a 64-bit key", key, contract, table))),
+        }
+    }
+
+    /// Deserialize binary into a typed [`AbiValue`]
+    ///
+    /// Values are decoded in pure Rust with the contract ABI, all data must be consumed.
+    pub fn bin_to_value(&self, contract: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<AbiValue, AbieosError> {
+        self.with_abi(contract.into(), |abi| abi.bin_to_value_exact(datatype, bin))
+            .map_err(AbieosError::Deserialize)
+    }
+
+    /// Serialize a typed [`AbiValue`] into binary
+    pub fn value_to_bin(&self, contract: impl Into<Name>, datatype: &str, value: &AbiValue) -> Result<Vec<u8>, AbieosError> {
+        self.with_abi(contract.into(), |abi| {
+            let mut out = Vec::new();
+            abi.value_to_bin(datatype, value, &mut out)?;
+            Ok(out)
+        })
+        .map_err(AbieosError::Serialize)
+    }
+
+    /// Parse JSON into a typed [`AbiValue`]
+    pub fn json_to_value(&self, contract: impl Into<Name>, datatype: &str, json: &str) -> Result<AbiValue, AbieosError> {
+        let contract = contract.into();
+        let bin = self
+            .with_abi(contract, |abi| abi.json_to_bin(datatype, json))
+            .map_err(AbieosError::Serialize)?;
+        self.bin_to_value(contract, datatype, &bin)
+    }
+
+    /// Convert a `serde_json::Value` into a typed [`AbiValue`]
+    ///
+    /// This is the reverse of `serde_json::Value::from(AbiValue)`.
+    #[cfg(feature = "serde_json")]
+    pub fn json_value_to_value(&self, contract: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<AbiValue, AbieosError> {
+        let contract = contract.into();
+        let bin = self
+            .with_abi(contract, |abi| abi.json_value_to_bin_vec(datatype, &json::JsonValue::from(json)))
+            .map_err(AbieosError::Serialize)?;
+        self.bin_to_value(contract, datatype, &bin)
+    }
+}
+
+#[cfg(not(feature = "pure-rust"))]
 pub mod abieos {
     //! # Abieos
     //!
@@ -0,0 +1,185 @@
+//! # Name
+//!
+//!  Antelope account, action and table names, converted in pure Rust
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::AbieosError;
+
+const CHARMAP: &[u8; 32] = b".12345abcdefghijklmnopqrstuvwxyz";
+
+/// An Antelope name (account, action, table, permission...)
+///
+/// Names are base32-encoded into a `u64`, using the charset `.12345abcdefghijklmnopqrstuvwxyz`.
+/// Up to 12 characters can use the full charset, while the optional 13th character is
+/// restricted to `.12345abcdefghij`. Conversion is done in pure Rust and does not require
+/// an abieos context.
+///
+/// Ordering and hashing follow the `u64` value, like on chain.
+///
+/// ```rust
+/// use rs_abieos::Name;
+///
+/// let name: Name = "eosio.token".parse().unwrap();
+/// assert_eq!(name.as_u64(), 6138663591592764928);
+/// assert_eq!(name.to_string(), "eosio.token");
+/// assert!(Name::try_from("Invalid").is_err());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Name(u64);
+
+impl Name {
+    /// Create a name from its raw `u64` value
+    pub const fn new(value: u64) -> Name {
+        Name(value)
+    }
+
+    /// Get the raw `u64` value
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` for the empty name (zero value)
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Name of a literal of up to 12 characters, checked at compile time in `const` items
+    pub(crate) const fn from_literal(s: &str) -> Name {
+        let bytes = s.as_bytes();
+        assert!(bytes.len() <= 12 && !matches!(bytes.last(), Some(b'.')), "invalid name literal");
+        let mut value = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let Some(symbol) = char_to_symbol(bytes[i]) else { panic!("invalid name literal") };
+            value |= symbol << (64 - 5 * (i + 1));
+            i += 1;
+        }
+        Name(value)
+    }
+}
+
+const fn char_to_symbol(c: u8) -> Option<u64> {
+    match c {
+        b'a'..=b'z' => Some((c - b'a') as u64 + 6),
+        b'1'..=b'5' => Some((c - b'1') as u64 + 1),
+        b'.' => Some(0),
+        _ => None,
+    }
+}
+
+impl FromStr for Name {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        if bytes.len() > 13 {
+            return Err(AbieosError::NameTooLong);
+        }
+        let mut value: u64 = 0;
+        for (i, &c) in bytes.iter().enumerate() {
+            let symbol = char_to_symbol(c)
+                .ok_or_else(|| AbieosError::InvalidName(format!("invalid character '{}' in \"{}\"", c as char, s)))?;
+            if i < 12 {
+                value |= symbol << (64 - 5 * (i + 1));
+            } else {
+                if symbol > 0x0f {
+                    return Err(AbieosError::InvalidName(format!("13th character of \"{}\" must be in [.1-5a-j]", s)));
+                }
+                value |= symbol;
+            }
+        }
+        if bytes.last() == Some(&b'.') {
+            return Err(AbieosError::InvalidName(format!("\"{}\" is not normalized (trailing dots)", s)));
+        }
+        Ok(Name(value))
+    }
+}
+
+impl TryFrom<&str> for Name {
+    type Error = AbieosError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<&String> for Name {
+    type Error = AbieosError;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for Name {
+    type Error = AbieosError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<u64> for Name {
+    fn from(value: u64) -> Self {
+        Name(value)
+    }
+}
+
+impl From<Name> for u64 {
+    fn from(value: Name) -> Self {
+        value.0
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut buf = [b'.'; 13];
+        let mut tmp = self.0;
+        for i in 0..13 {
+            let (mask, shift) = if i == 0 { (0x0f, 4) } else { (0x1f, 5) };
+            buf[12 - i] = CHARMAP[(tmp & mask) as usize];
+            tmp >>= shift;
+        }
+        let len = buf.iter().rposition(|&c| c != b'.').map_or(0, |p| p + 1);
+        // the charmap is plain ASCII, so this can never fail
+        f.write_str(std::str::from_utf8(&buf[..len]).unwrap_or_default())
+    }
+}
+
+impl Debug for Name {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "Name({})", self)
+    }
+}
+
+impl PartialEq<u64> for Name {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Names are written as strings in human-readable formats and as their `u64` value otherwise
+impl Serialize for Name {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(serde::de::Error::custom)
+        } else {
+            u64::deserialize(deserializer).map(Name)
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! # Pure-Rust backend
+//!
+//!  Implementation of the [`Abieos`] context without the abieos C++ library,
+//!  enabled by the `pure-rust` feature
+
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString};
+
+#[cfg(feature = "serde_json")]
+use crate::json::JsonValue;
+use crate::serializer::AbiSerializer;
+use crate::{stream, AbiDef, AbiLike, Abieos, AbieosError, Name};
+
+/// Contract ABIs loaded in a pure-Rust context
+#[derive(Default)]
+pub(crate) struct NativeContext {
+    abis: BTreeMap<Name, AbiSerializer>,
+    c_result: CString,
+}
+
+fn message(e: AbieosError) -> String {
+    match e {
+        AbieosError::InvalidAbi(e) => e,
+        e => e.to_string(),
+    }
+}
+
+impl Abieos {
+    /// Create a new Abieos instance.
+    pub fn new() -> Abieos {
+        Abieos {
+            native: Default::default(),
+            abis: Default::default(),
+        }
+    }
+
+    /// Run `f` with the serializer of a loaded contract
+    pub(crate) fn with_abi<T>(&self, contract: Name, f: impl FnOnce(&AbiSerializer) -> Result<T, String>) -> Result<T, String> {
+        let native = self.native.borrow();
+        let abi = native.abis.get(&contract).ok_or_else(|| format!("contract \"{}\" is not loaded", contract))?;
+        f(abi)
+    }
+
+    fn load_abi_def(&self, contract: Name, abi: Result<AbiDef, AbieosError>, source: AbiLike) -> Result<bool, AbieosError> {
+        let serializer = abi
+            .map_err(message)
+            .and_then(AbiSerializer::new)
+            .map_err(AbieosError::SetAbi)?;
+        self.native.borrow_mut().abis.insert(contract, serializer);
+        self.record_abi(contract, Some(source));
+        Ok(true)
+    }
+
+    /// Store a C-string result, returning an empty C-string on error
+    fn set_c_result(&mut self, result: Result<String, AbieosError>) -> &CStr {
+        let native = self.native.get_mut();
+        native.c_result = result.ok().and_then(|s| CString::new(s).ok()).unwrap_or_default();
+        &native.c_result
+    }
+
+    /// Load a contract ABI to memory (JSON format as C-String)
+    pub fn set_abi_json_c(&self, contract: impl Into<Name>, abi_json: &CStr) -> Result<bool, AbieosError> {
+        let abi_json = abi_json.to_str().map_err(|e| AbieosError::SetAbi(e.to_string()))?;
+        self.set_abi_json_native(contract, abi_json)
+    }
+
+    /// Load a contract ABI to memory (JSON format, native contract name)
+    pub fn set_abi_json_native(&self, contract: impl Into<Name>, abi_json: &str) -> Result<bool, AbieosError> {
+        self.load_abi_def(contract.into(), AbiDef::from_json(abi_json), AbiLike::Json(abi_json.to_string()))
+    }
+
+    /// Load a contract ABI to memory (HEX format, native contract name)
+    pub fn set_abi_hex_native(&self, contract: impl Into<Name>, abi_hex: &str) -> Result<bool, AbieosError> {
+        let abi_bin = stream::from_hex(abi_hex).map_err(AbieosError::SetAbi)?;
+        self.load_abi_def(contract.into(), AbiDef::from_bin(&abi_bin), AbiLike::Hex(abi_hex.to_string()))
+    }
+
+    /// Load a contract ABI to memory (binary format, native contract name)
+    pub fn set_abi_bin_native(&self, contract: impl Into<Name>, abi_bin: &[u8]) -> Result<bool, AbieosError> {
+        self.load_abi_def(contract.into(), AbiDef::from_bin(abi_bin), AbiLike::Bin(abi_bin.to_vec()))
+    }
+
+    /// Serialize JSON into binary (output as HEX)
+    ///
+    /// The returned C-string is stored in the context and overwritten by the next call.
+    /// It borrows `self` mutably, so the borrow checker rejects any other call on this
+    /// context while the result is alive. Use `.to_owned()` to keep it longer.
+    pub fn json_to_hex_c(&mut self, account: &CStr, datatype: &CStr, json: &CStr) -> &CStr {
+        let account = self.c_string_to_name(account);
+        let result = match (datatype.to_str(), json.to_str()) {
+            (Ok(datatype), Ok(json)) => self.json_to_hex_native(account, datatype, json),
+            _ => Err(AbieosError::JsonToHex("invalid utf-8 input".to_string())),
+        };
+        self.set_c_result(result)
+    }
+
+    /// Serialize JSON into binary (output as HEX, native account name)
+    pub fn json_to_hex_native(&self, account: impl Into<Name>, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        self.with_abi(account.into(), |abi| abi.json_to_bin(datatype, json))
+            .map(|bin| stream::to_hex(&bin))
+            .map_err(AbieosError::JsonToHex)
+    }
+
+    /// Serialize JSON into binary (output as binary, native account name)
+    pub fn json_to_bin_native(&self, account: impl Into<Name>, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.with_abi(account.into(), |abi| abi.json_to_bin(datatype, json))
+            .map_err(AbieosError::JsonToBin)
+    }
+
+    /// Serialize a `serde_json::Value` into binary (output as HEX)
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_hex_value(&self, account: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<String, AbieosError> {
+        self.with_abi(account.into(), |abi| abi.json_value_to_bin_vec(datatype, &JsonValue::from(json)))
+            .map(|bin| stream::to_hex(&bin))
+            .map_err(AbieosError::JsonToHex)
+    }
+
+    /// Serialize a `serde_json::Value` into binary
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_bin_value(&self, account: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<Vec<u8>, AbieosError> {
+        self.with_abi(account.into(), |abi| abi.json_value_to_bin_vec(datatype, &JsonValue::from(json)))
+            .map_err(AbieosError::JsonToBin)
+    }
+
+    /// Deserialize HEX string into JSON
+    ///
+    /// Like [`Abieos::json_to_hex_c`], the result is tied to a mutable borrow of `self`.
+    /// An empty C-string is returned on error.
+    pub fn hex_to_json_c(&mut self, account: &CStr, datatype: &CStr, hex: &CStr) -> &CStr {
+        let account = self.c_string_to_name(account);
+        let result = match (datatype.to_str(), hex.to_str()) {
+            (Ok(datatype), Ok(hex)) => self.hex_to_json_native(account, datatype, hex),
+            _ => Err(AbieosError::HexToJson("invalid utf-8 input".to_string())),
+        };
+        self.set_c_result(result)
+    }
+
+    /// Deserialize HEX string into JSON (native account name)
+    pub fn hex_to_json_native(&self, account: impl Into<Name>, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        let bin = stream::from_hex(hex).map_err(AbieosError::HexToJson)?;
+        self.with_abi(account.into(), |abi| abi.bin_to_json(datatype, &bin))
+            .map_err(AbieosError::HexToJson)
+    }
+
+    /// Deserialize Binary into JSON (native account name)
+    pub fn bin_to_json_native(&self, account: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.with_abi(account.into(), |abi| abi.bin_to_json(datatype, bin))
+            .map_err(AbieosError::BinToJson)
+    }
+
+    /// Deserialize HEX string into a `serde_json::Value`
+    #[cfg(feature = "serde_json")]
+    pub fn hex_to_json_value(&self, account: impl Into<Name>, datatype: &str, hex: &str) -> Result<serde_json::Value, AbieosError> {
+        let bin = stream::from_hex(hex).map_err(AbieosError::HexToJson)?;
+        self.with_abi(account.into(), |abi| abi.bin_to_value_exact::<JsonValue>(datatype, &bin))
+            .map(|json| serde_json::Value::from(&json))
+            .map_err(AbieosError::HexToJson)
+    }
+
+    /// Deserialize Binary into a `serde_json::Value`
+    #[cfg(feature = "serde_json")]
+    pub fn bin_to_json_value(&self, account: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<serde_json::Value, AbieosError> {
+        self.with_abi(account.into(), |abi| abi.bin_to_value_exact::<JsonValue>(datatype, bin))
+            .map(|json| serde_json::Value::from(&json))
+            .map_err(AbieosError::BinToJson)
+    }
+
+    /// Get the type for an action (native names as input)
+    pub fn get_type_for_action_native(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        let (contract, action) = (contract.into(), action.into());
+        self.with_abi(contract, |abi| {
+            abi.action_type(action)
+                .map(str::to_string)
+                .ok_or_else(|| format!("contract \"{}\" does not have action \"{}\"", contract, action))
+        })
+        .map_err(AbieosError::GetTypeForAction)
+    }
+
+    /// Get the type for a table (native names as input)
+    pub fn get_type_for_table_native(&self, contract: impl Into<Name>, table: impl Into<Name>) -> Result<String, AbieosError> {
+        let (contract, table) = (contract.into(), table.into());
+        self.with_abi(contract, |abi| {
+            abi.table_type(table)
+                .map(str::to_string)
+                .ok_or_else(|| format!("contract \"{}\" does not have table \"{}\"", contract, table))
+        })
+        .map_err(AbieosError::GetTypeForTable)
+    }
+
+    /// Get the type for an action result (native names as input)
+    pub fn get_type_for_action_result_native(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        let (contract, action) = (contract.into(), action.into());
+        self.with_abi(contract, |abi| {
+            abi.action_result_type(action)
+                .map(str::to_string)
+                .ok_or_else(|| format!("contract \"{}\" does not have action_result \"{}\"", contract, action))
+        })
+        .map_err(AbieosError::GetTypeForActionResult)
+    }
+
+    /// Convert ABI binary to JSON
+    pub fn abi_bin_to_json(&self, abi: &[u8]) -> Result<String, AbieosError> {
+        let abi = AbiDef::from_bin(abi).map_err(|e| AbieosError::AbiBinToJson(message(e)))?;
+        crate::serializer::check_version(&abi.version).map_err(AbieosError::AbiBinToJson)?;
+        Ok(abi.to_json())
+    }
+
+    /// Convert ABI JSON to binary
+    pub fn abi_json_to_bin(&self, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let abi = AbiDef::from_json(json).map_err(|e| AbieosError::AbiJsonToBin(message(e)))?;
+        crate::serializer::check_version(&abi.version).map_err(AbieosError::AbiJsonToBin)?;
+        abi.to_bin().map_err(|e| AbieosError::AbiJsonToBin(message(e)))
+    }
+
+    /// Delete a contract from the context (native name)
+    pub fn delete_contract_native(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
+        let contract: Name = contract.into();
+        self.record_abi(contract, None);
+        Ok(self.native.borrow_mut().abis.remove(&contract).is_some())
+    }
+}
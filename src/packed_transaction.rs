@@ -0,0 +1,290 @@
+//! # Packed transactions
+//!
+//!  [`PackedTransaction`], the form transactions are pushed to nodeos and relayed between nodes
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+
+use crate::json::JsonValue;
+use crate::stream::{self, InputStream};
+use crate::transaction::{self, pack_context_free_data, transaction_abi};
+use crate::{AbieosError, Signature};
+
+/// Largest unpacked transaction or context free data, guarding against zlib bombs
+const MAX_UNPACKED_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Compression of the transaction and context free data of a [`PackedTransaction`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Zlib,
+}
+
+impl Compression {
+    /// Name used in the JSON form of packed transactions
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Zlib => "zlib",
+        }
+    }
+
+    fn from_u8(value: u8) -> Result<Compression, AbieosError> {
+        match value {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Zlib),
+            _ => Err(invalid(format!("unknown compression {}", value))),
+        }
+    }
+
+    fn compress(self, bytes: Vec<u8>) -> Result<Vec<u8>, AbieosError> {
+        match self {
+            Compression::None => Ok(bytes),
+            Compression::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&bytes).map_err(|e| invalid(e.to_string()))?;
+                encoder.finish().map_err(|e| invalid(e.to_string()))
+            }
+        }
+    }
+
+    fn decompress(self, bytes: &[u8]) -> Result<Vec<u8>, AbieosError> {
+        match self {
+            Compression::None => Ok(bytes.to_vec()),
+            Compression::Zlib => {
+                let mut out = Vec::new();
+                ZlibDecoder::new(bytes)
+                    .take(MAX_UNPACKED_SIZE + 1)
+                    .read_to_end(&mut out)
+                    .map_err(|e| invalid(format!("zlib: {}", e)))?;
+                if out.len() as u64 > MAX_UNPACKED_SIZE {
+                    return Err(invalid(format!("unpacked data exceeds {} bytes", MAX_UNPACKED_SIZE)));
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// Parses `none` and `zlib`
+impl FromStr for Compression {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Compression::None),
+            "zlib" => Ok(Compression::Zlib),
+            _ => Err(invalid(format!("unknown compression \"{}\"", s))),
+        }
+    }
+}
+
+impl Display for Compression {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+fn invalid(message: String) -> AbieosError {
+    AbieosError::InvalidPackedTransaction(message)
+}
+
+/// Check that `bin` is exactly one serialized transaction
+fn check_transaction(bin: &[u8]) -> Result<(), AbieosError> {
+    transaction_abi()
+        .bin_to_value_exact::<JsonValue>("transaction", bin)
+        .map(drop)
+        .map_err(|e| invalid(format!("transaction: {}", e)))
+}
+
+/// A serialized transaction with its signatures, optionally compressed
+///
+/// The JSON form is the body of nodeos `push_transaction`:
+///
+/// ```json
+/// {"signatures":[],"compression":"none","packed_context_free_data":"","packed_trx":"..."}
+/// ```
+///
+/// ```rust
+/// use rs_abieos::{Abieos, Compression, PackedTransaction};
+///
+/// let abieos = Abieos::new();
+/// let trx = abieos.encode_transaction(r#"{
+///     "expiration": "2024-01-01T00:00:00.000", "ref_block_num": 1, "ref_block_prefix": 2,
+///     "max_net_usage_words": 0, "max_cpu_usage_ms": 0, "delay_sec": 0,
+///     "context_free_actions": [], "actions": [], "transaction_extensions": []
+/// }"#).unwrap();
+/// let packed = PackedTransaction::pack(&trx, Compression::Zlib).unwrap();
+/// assert_eq!(packed.unpack().unwrap(), trx);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PackedTransaction {
+    pub signatures: Vec<Signature>,
+    pub compression: Compression,
+    /// Serialized `vector<bytes>` of context free data, compressed with `compression`
+    pub packed_context_free_data: Vec<u8>,
+    /// Serialized transaction, compressed with `compression`
+    pub packed_trx: Vec<u8>,
+}
+
+impl PackedTransaction {
+    /// Pack a serialized transaction without signatures nor context free data
+    pub fn pack(transaction: &[u8], compression: Compression) -> Result<PackedTransaction, AbieosError> {
+        PackedTransaction::pack_with_context_free_data(transaction, &[], compression)
+    }
+
+    /// Pack a serialized transaction and its context free data, without signatures
+    pub fn pack_with_context_free_data(
+        transaction: &[u8],
+        context_free_data: &[Vec<u8>],
+        compression: Compression,
+    ) -> Result<PackedTransaction, AbieosError> {
+        check_transaction(transaction)?;
+        // Like nodeos, no context free data packs to nothing rather than an empty vector
+        let packed_context_free_data = if context_free_data.is_empty() {
+            Vec::new()
+        } else {
+            compression.compress(pack_context_free_data(context_free_data).map_err(invalid)?)?
+        };
+        Ok(PackedTransaction {
+            signatures: Vec::new(),
+            compression,
+            packed_context_free_data,
+            packed_trx: compression.compress(transaction.to_vec())?,
+        })
+    }
+
+    /// Uncompressed serialized transaction
+    pub fn unpack(&self) -> Result<Vec<u8>, AbieosError> {
+        let trx = self.compression.decompress(&self.packed_trx)?;
+        check_transaction(&trx)?;
+        Ok(trx)
+    }
+
+    /// Uncompressed context free data
+    pub fn unpack_context_free_data(&self) -> Result<Vec<Vec<u8>>, AbieosError> {
+        if self.packed_context_free_data.is_empty() {
+            return Ok(Vec::new());
+        }
+        let cfd = self.compression.decompress(&self.packed_context_free_data)?;
+        let mut s = InputStream::new(&cfd);
+        let read = |s: &mut InputStream| -> Result<Vec<Vec<u8>>, String> {
+            let len = s.read_length(1)?;
+            let data = (0..len).map(|_| s.read_byte_vec()).collect::<Result<Vec<_>, _>>()?;
+            if s.remaining() > 0 {
+                return Err(s.error("extra data after context free data"));
+            }
+            Ok(data)
+        };
+        read(&mut s).map_err(|e| invalid(format!("context free data: {}", e)))
+    }
+
+    /// ID of the transaction
+    pub fn id(&self) -> Result<[u8; 32], AbieosError> {
+        Ok(transaction::transaction_id(&self.unpack()?))
+    }
+
+    /// Digest to sign for the transaction and its context free data on the chain `chain_id`
+    pub fn signing_digest(&self, chain_id: &[u8; 32]) -> Result<[u8; 32], AbieosError> {
+        Ok(transaction::signing_digest(chain_id, &self.unpack()?, &self.unpack_context_free_data()?))
+    }
+
+    /// Serialize in binary, as found in state history and the p2p protocol
+    pub fn to_bin(&self) -> Result<Vec<u8>, AbieosError> {
+        let mut out = Vec::new();
+        stream::write_length(&mut out, self.signatures.len()).map_err(invalid)?;
+        for signature in &self.signatures {
+            out.extend(signature.to_bin());
+        }
+        out.push(self.compression as u8);
+        stream::write_bytes(&mut out, &self.packed_context_free_data).map_err(invalid)?;
+        stream::write_bytes(&mut out, &self.packed_trx).map_err(invalid)?;
+        Ok(out)
+    }
+
+    /// Deserialize from binary, which must hold exactly one packed transaction
+    pub fn from_bin(bin: &[u8]) -> Result<PackedTransaction, AbieosError> {
+        let mut s = InputStream::new(bin);
+        let len = s.read_length(1).map_err(invalid)?;
+        let signatures = (0..len).map(|_| Signature::read(&mut s)).collect::<Result<_, _>>().map_err(invalid)?;
+        let compression = Compression::from_u8(s.read_u8().map_err(invalid)?)?;
+        let packed_context_free_data = s.read_byte_vec().map_err(invalid)?;
+        let packed_trx = s.read_byte_vec().map_err(invalid)?;
+        if s.remaining() > 0 {
+            return Err(invalid(s.error("extra data after packed transaction")));
+        }
+        Ok(PackedTransaction {
+            signatures,
+            compression,
+            packed_context_free_data,
+            packed_trx,
+        })
+    }
+
+    /// Format as the JSON body of nodeos `push_transaction`, with lowercase HEX
+    pub fn to_json(&self) -> String {
+        let hex = |bytes: &[u8]| JsonValue::String(stream::to_hex(bytes).to_ascii_lowercase());
+        JsonValue::Object(vec![
+            (
+                "signatures".to_string(),
+                JsonValue::Array(self.signatures.iter().map(|signature| JsonValue::String(signature.to_string())).collect()),
+            ),
+            ("compression".to_string(), JsonValue::String(self.compression.as_str().to_string())),
+            ("packed_context_free_data".to_string(), hex(&self.packed_context_free_data)),
+            ("packed_trx".to_string(), hex(&self.packed_trx)),
+        ])
+        .to_json()
+    }
+
+    /// Parse the JSON body of nodeos `push_transaction`
+    ///
+    /// `compression` is either a name (`none`, `zlib`) or its number. Missing `signatures`,
+    /// `compression` and `packed_context_free_data` take their defaults.
+    pub fn from_json(json: &str) -> Result<PackedTransaction, AbieosError> {
+        let value = JsonValue::parse(json).map_err(invalid)?;
+        let fields = value.as_object().ok_or_else(|| invalid(format!("expected object, got {}", value.kind())))?;
+        let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+        let hex = |key: &str| -> Result<Vec<u8>, AbieosError> {
+            match field(key) {
+                None => Ok(Vec::new()),
+                Some(value) => {
+                    let hex = value.as_str().ok_or_else(|| invalid(format!("{} must be a HEX string", key)))?;
+                    stream::from_hex(hex).map_err(|e| invalid(format!("{}: {}", key, e)))
+                }
+            }
+        };
+        let signatures = match field("signatures") {
+            None => Vec::new(),
+            Some(JsonValue::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    let signature = item.as_str().ok_or_else(|| invalid("signatures must be strings".to_string()))?;
+                    signature.parse().map_err(|e: AbieosError| invalid(e.to_string()))
+                })
+                .collect::<Result<_, AbieosError>>()?,
+            Some(other) => return Err(invalid(format!("signatures must be an array, got {}", other.kind()))),
+        };
+        let compression = match field("compression") {
+            None | Some(JsonValue::Null) => Compression::None,
+            Some(JsonValue::String(name)) => name.parse()?,
+            Some(JsonValue::Number(n)) => {
+                Compression::from_u8(n.parse().map_err(|_| invalid(format!("unknown compression {}", n)))?)?
+            }
+            Some(other) => return Err(invalid(format!("compression must be a string or number, got {}", other.kind()))),
+        };
+        if field("packed_trx").is_none() {
+            return Err(invalid("missing packed_trx".to_string()));
+        }
+        Ok(PackedTransaction {
+            signatures,
+            compression,
+            packed_context_free_data: hex("packed_context_free_data")?,
+            packed_trx: hex("packed_trx")?,
+        })
+    }
+}
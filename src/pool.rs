@@ -0,0 +1,354 @@
+//! # Context pool
+//!
+//!  [`AbieosPool`], a fixed set of [`Abieos`] contexts sharing the same ABIs across threads
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::{AbiDef, AbiLike, AbiValue, Abieos, AbieosError, Name};
+
+/// A context along with the ABI generation it is synchronized to
+struct Slot {
+    abieos: Abieos,
+    synced: u64,
+}
+
+/// Record of the ABI changes made to a set of contexts
+///
+/// Every change gets a new generation, so a context synchronized to a generation only has
+/// to apply the later changes.
+#[derive(Default)]
+pub(crate) struct AbiLog {
+    generation: u64,
+    /// Latest ABI of every contract with the generation it was set at, `None` once deleted
+    abis: BTreeMap<Name, (u64, Option<Arc<AbiLike>>)>,
+    /// Contract changed at each generation, only the latest change of a contract is kept
+    changes: BTreeMap<u64, Name>,
+}
+
+impl AbiLog {
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub(crate) fn contains(&self, contract: Name) -> bool {
+        self.abis.get(&contract).is_some_and(|(_, abi)| abi.is_some())
+    }
+
+    /// Changes a context synchronized to `synced` has not applied yet
+    pub(crate) fn pending(&self, synced: u64) -> Vec<(Name, Option<Arc<AbiLike>>)> {
+        self.changes
+            .range(synced + 1..)
+            .map(|(_, contract)| (*contract, self.abis[contract].1.clone()))
+            .collect()
+    }
+
+    /// Record a new ABI (or a deletion with `None`), returning its generation
+    pub(crate) fn record(&mut self, contract: Name, abi: Option<AbiLike>) -> u64 {
+        self.generation += 1;
+        if let Some((previous, _)) = self.abis.insert(contract, (self.generation, abi.map(Arc::new))) {
+            self.changes.remove(&previous);
+        }
+        self.changes.insert(self.generation, contract);
+        self.generation
+    }
+}
+
+/// Apply pending changes to a context
+pub(crate) fn apply(abieos: &Abieos, pending: Vec<(Name, Option<Arc<AbiLike>>)>) {
+    for (contract, abi) in pending {
+        // every ABI was loaded successfully into a scratch context before being recorded
+        let _ = match abi {
+            Some(abi) => abieos.set_abi_like(contract, &abi),
+            None => abieos.delete_contract_native(contract),
+        };
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    idle: Vec<Slot>,
+    log: AbiLog,
+}
+
+/// A pool of [`Abieos`] contexts for multi-threaded use
+///
+/// `Abieos` is not `Sync`, so each thread needs its own context. The pool owns a fixed number
+/// of contexts, replicates every `set_abi_*` and `delete_contract` call to all of them, and
+/// leases them to worker threads with [`AbieosPool::lease`]. The pool itself is `Sync` and is
+/// usually shared through an `Arc`.
+///
+/// ABI changes are applied to a context when it is leased, a context already leased keeps
+/// the ABIs it had until it is returned.
+///
+/// ```rust,no_run
+/// use rs_abieos::{AbieosPool, Name};
+///
+/// let pool = AbieosPool::new(4);
+/// let token = Name::try_from("eosio.token").unwrap();
+/// pool.set_abi_json(token, &std::fs::read_to_string("eosio.token.abi").unwrap()).unwrap();
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             let abieos = pool.lease();
+///             let json = abieos.hex_to_json_native(token, "transfer", "0000000000855C34...").unwrap();
+///         });
+///     }
+/// });
+/// ```
+pub struct AbieosPool {
+    size: usize,
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+impl AbieosPool {
+    /// Create a pool of `size` contexts (at least one)
+    pub fn new(size: usize) -> AbieosPool {
+        let size = size.max(1);
+        let idle = (0..size).map(|_| Slot { abieos: Abieos::new(), synced: 0 }).collect();
+        AbieosPool {
+            size,
+            state: Mutex::new(PoolState { idle, ..Default::default() }),
+            returned: Condvar::new(),
+        }
+    }
+
+    /// Number of contexts owned by the pool
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of contexts not currently leased
+    pub fn available(&self) -> usize {
+        self.lock().idle.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        // a panic while the lock is held cannot leave the state half-updated
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Lease a context, waiting until one is available
+    ///
+    /// The context is returned to the pool when the lease is dropped.
+    pub fn lease(&self) -> PooledAbieos<'_> {
+        let mut state = self.lock();
+        loop {
+            if let Some(slot) = state.idle.pop() {
+                return self.sync(state, slot);
+            }
+            state = self.returned.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Lease a context if one is available right now
+    pub fn try_lease(&self) -> Option<PooledAbieos<'_>> {
+        let mut state = self.lock();
+        let slot = state.idle.pop()?;
+        Some(self.sync(state, slot))
+    }
+
+    /// Apply the pending ABI changes to a context taken out of the pool
+    fn sync(&self, state: MutexGuard<'_, PoolState>, mut slot: Slot) -> PooledAbieos<'_> {
+        let pending = state.log.pending(slot.synced);
+        let generation = state.log.generation();
+        drop(state);
+        apply(&slot.abieos, pending);
+        slot.synced = generation;
+        PooledAbieos { pool: self, slot: Some(slot) }
+    }
+
+    fn give_back(&self, slot: Slot) {
+        self.lock().idle.push(slot);
+        self.returned.notify_one();
+    }
+
+    /// Check an ABI loads into a scratch context, then record it for all contexts
+    ///
+    /// No context is leased, so threads holding a lease can load ABIs too.
+    fn set_abi(&self, contract: Name, abi: AbiLike) -> Result<bool, AbieosError> {
+        Abieos::new().set_abi_like(contract, &abi)?;
+        self.lock().log.record(contract, Some(abi));
+        Ok(true)
+    }
+
+    /// Load a contract ABI into all contexts (JSON format)
+    pub fn set_abi_json(&self, contract: impl Into<Name>, abi_json: &str) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Json(abi_json.to_string()))
+    }
+
+    /// Load a contract ABI into all contexts (HEX format)
+    pub fn set_abi_hex(&self, contract: impl Into<Name>, abi_hex: &str) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Hex(abi_hex.to_string()))
+    }
+
+    /// Load a contract ABI into all contexts (binary format)
+    pub fn set_abi_bin(&self, contract: impl Into<Name>, abi_bin: &[u8]) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Bin(abi_bin.to_vec()))
+    }
+
+    /// Load a contract ABI into all contexts from a parsed [`AbiDef`]
+    pub fn set_abi_def(&self, contract: impl Into<Name>, abi: &AbiDef) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Bin(abi.to_bin()?))
+    }
+
+    /// Delete a contract from all contexts
+    ///
+    /// Returns `false` if no ABI was loaded.
+    pub fn delete_contract(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
+        let contract = contract.into();
+        let mut state = self.lock();
+        if !state.log.contains(contract) {
+            return Ok(false);
+        }
+        state.log.record(contract, None);
+        Ok(true)
+    }
+
+    /// Returns `true` if an ABI is loaded for the contract
+    pub fn has_contract(&self, contract: impl Into<Name>) -> bool {
+        self.lock().log.contains(contract.into())
+    }
+}
+
+/// A context leased from an [`AbieosPool`], returned to the pool on drop
+pub struct PooledAbieos<'a> {
+    pool: &'a AbieosPool,
+    slot: Option<Slot>,
+}
+
+/// Codec methods of the leased context
+///
+/// ABIs are loaded through the pool only: a lease exposes the conversions and type lookups
+/// of [`Abieos`], not its `set_abi_*` and `delete_contract*` methods, which would bypass
+/// the ABI log shared by the other contexts.
+impl PooledAbieos<'_> {
+    fn abieos(&self) -> &Abieos {
+        &self.slot.as_ref().expect("slot is only taken on drop").abieos
+    }
+
+    /// See [`Abieos::has_contract`]
+    pub fn has_contract(&self, contract: impl Into<Name>) -> bool {
+        self.abieos().has_contract(contract)
+    }
+
+    /// See [`Abieos::json_to_hex_native`]
+    pub fn json_to_hex_native(&self, account: impl Into<Name>, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        self.abieos().json_to_hex_native(account, datatype, json)
+    }
+
+    /// See [`Abieos::json_to_bin_native`]
+    pub fn json_to_bin_native(&self, account: impl Into<Name>, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.abieos().json_to_bin_native(account, datatype, json)
+    }
+
+    /// See [`Abieos::hex_to_json_native`]
+    pub fn hex_to_json_native(&self, account: impl Into<Name>, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        self.abieos().hex_to_json_native(account, datatype, hex)
+    }
+
+    /// See [`Abieos::bin_to_json_native`]
+    pub fn bin_to_json_native(&self, account: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.abieos().bin_to_json_native(account, datatype, bin)
+    }
+
+    /// See [`Abieos::json_to_hex_value`]
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_hex_value(&self, account: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<String, AbieosError> {
+        self.abieos().json_to_hex_value(account, datatype, json)
+    }
+
+    /// See [`Abieos::json_to_bin_value`]
+    #[cfg(feature = "serde_json")]
+    pub fn json_to_bin_value(&self, account: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<Vec<u8>, AbieosError> {
+        self.abieos().json_to_bin_value(account, datatype, json)
+    }
+
+    /// See [`Abieos::hex_to_json_value`]
+    #[cfg(feature = "serde_json")]
+    pub fn hex_to_json_value(&self, account: impl Into<Name>, datatype: &str, hex: &str) -> Result<serde_json::Value, AbieosError> {
+        self.abieos().hex_to_json_value(account, datatype, hex)
+    }
+
+    /// See [`Abieos::bin_to_json_value`]
+    #[cfg(feature = "serde_json")]
+    pub fn bin_to_json_value(&self, account: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<serde_json::Value, AbieosError> {
+        self.abieos().bin_to_json_value(account, datatype, bin)
+    }
+
+    /// See [`Abieos::get_type_for_action_native`]
+    pub fn get_type_for_action_native(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        self.abieos().get_type_for_action_native(contract, action)
+    }
+
+    /// See [`Abieos::get_type_for_table_native`]
+    pub fn get_type_for_table_native(&self, contract: impl Into<Name>, table: impl Into<Name>) -> Result<String, AbieosError> {
+        self.abieos().get_type_for_table_native(contract, table)
+    }
+
+    /// See [`Abieos::get_type_for_action_result_native`]
+    pub fn get_type_for_action_result_native(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        self.abieos().get_type_for_action_result_native(contract, action)
+    }
+
+    /// See [`Abieos::encode_action`]
+    pub fn encode_action(&self, contract: impl Into<Name>, action: impl Into<Name>, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.abieos().encode_action(contract, action, json)
+    }
+
+    /// See [`Abieos::decode_action`]
+    pub fn decode_action(&self, contract: impl Into<Name>, action: impl Into<Name>, bin: &[u8]) -> Result<String, AbieosError> {
+        self.abieos().decode_action(contract, action, bin)
+    }
+
+    /// See [`Abieos::decode_action_result`]
+    pub fn decode_action_result(&self, contract: impl Into<Name>, action: impl Into<Name>, bin: &[u8]) -> Result<String, AbieosError> {
+        self.abieos().decode_action_result(contract, action, bin)
+    }
+
+    /// See [`Abieos::encode_table_row`]
+    pub fn encode_table_row(&self, contract: impl Into<Name>, table: impl Into<Name>, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.abieos().encode_table_row(contract, table, json)
+    }
+
+    /// See [`Abieos::decode_table_row`]
+    pub fn decode_table_row(&self, contract: impl Into<Name>, table: impl Into<Name>, bin: &[u8]) -> Result<String, AbieosError> {
+        self.abieos().decode_table_row(contract, table, bin)
+    }
+
+    /// See [`Abieos::table_row_primary_key`]
+    pub fn table_row_primary_key(&self, contract: impl Into<Name>, table: impl Into<Name>, bin: &[u8]) -> Result<Option<u64>, AbieosError> {
+        self.abieos().table_row_primary_key(contract, table, bin)
+    }
+
+    /// See [`Abieos::bin_to_value`]
+    pub fn bin_to_value(&self, contract: impl Into<Name>, datatype: &str, bin: &[u8]) -> Result<AbiValue, AbieosError> {
+        self.abieos().bin_to_value(contract, datatype, bin)
+    }
+
+    /// See [`Abieos::value_to_bin`]
+    pub fn value_to_bin(&self, contract: impl Into<Name>, datatype: &str, value: &AbiValue) -> Result<Vec<u8>, AbieosError> {
+        self.abieos().value_to_bin(contract, datatype, value)
+    }
+
+    /// See [`Abieos::json_to_value`]
+    pub fn json_to_value(&self, contract: impl Into<Name>, datatype: &str, json: &str) -> Result<AbiValue, AbieosError> {
+        self.abieos().json_to_value(contract, datatype, json)
+    }
+
+    /// See [`Abieos::json_value_to_value`]
+    #[cfg(feature = "serde_json")]
+    pub fn json_value_to_value(&self, contract: impl Into<Name>, datatype: &str, json: &serde_json::Value) -> Result<AbiValue, AbieosError> {
+        self.abieos().json_value_to_value(contract, datatype, json)
+    }
+}
+
+impl Drop for PooledAbieos<'_> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            self.pool.give_back(slot);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! # Public keys
+//!
+//!  [`PublicKey`], parsed and formatted in pure Rust with the layout abieos uses in binary
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::stream::{self, InputStream};
+use crate::{keys, AbieosError, KeyType};
+
+/// Size of a compressed curve point
+const KEY_SIZE: usize = 33;
+
+/// A K1, R1 or WebAuthn public key
+///
+/// Parsed from `PUB_K1_`, `PUB_R1_`, `PUB_WA_` and legacy `EOS` strings, whose checksums are
+/// verified, and displayed as `PUB_<type>_`. [`PublicKey::to_legacy_string`] gives back the
+/// `EOS` form of K1 keys.
+///
+/// ```rust
+/// use rs_abieos::PublicKey;
+///
+/// let key: PublicKey = "EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV".parse().unwrap();
+/// assert_eq!(key.to_string(), "PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63");
+/// assert_eq!(key.to_legacy_string().unwrap(), "EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PublicKey {
+    /// Compressed secp256k1 point
+    K1([u8; KEY_SIZE]),
+    /// Compressed secp256r1 point
+    R1([u8; KEY_SIZE]),
+    /// Compressed secp256r1 point of a WebAuthn authenticator
+    WA {
+        key: [u8; KEY_SIZE],
+        /// User presence flags required from the authenticator
+        user_presence: u8,
+        /// Relying party ID
+        rpid: String,
+    },
+}
+
+fn invalid(message: impl Into<String>) -> AbieosError {
+    AbieosError::InvalidKey(message.into())
+}
+
+impl PublicKey {
+    /// Curve of the key
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            PublicKey::K1(_) => KeyType::K1,
+            PublicKey::R1(_) => KeyType::R1,
+            PublicKey::WA { .. } => KeyType::WA,
+        }
+    }
+
+    /// Compressed curve point
+    pub fn point(&self) -> &[u8; KEY_SIZE] {
+        match self {
+            PublicKey::K1(key) | PublicKey::R1(key) | PublicKey::WA { key, .. } => key,
+        }
+    }
+
+    pub(crate) fn read(s: &mut InputStream) -> Result<PublicKey, String> {
+        let key_type = keys::read_key_type(s)?;
+        let key = s.read_array()?;
+        Ok(match key_type {
+            KeyType::K1 => PublicKey::K1(key),
+            KeyType::R1 => PublicKey::R1(key),
+            KeyType::WA => PublicKey::WA {
+                key,
+                user_presence: s.read_u8()?,
+                rpid: s.read_string()?,
+            },
+        })
+    }
+
+    /// Decode a key from binary, which must hold exactly one key
+    pub fn from_bin(bin: &[u8]) -> Result<PublicKey, AbieosError> {
+        let mut s = InputStream::new(bin);
+        let key = PublicKey::read(&mut s).map_err(invalid)?;
+        if s.remaining() > 0 {
+            return Err(invalid(s.error("extra data after public key")));
+        }
+        Ok(key)
+    }
+
+    /// Encode the key in binary: its type as a varuint32, the point, then the WebAuthn fields
+    pub fn to_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + KEY_SIZE);
+        stream::write_varuint32(&mut out, self.key_type() as u32);
+        out.extend_from_slice(self.point());
+        if let PublicKey::WA { user_presence, rpid, .. } = self {
+            out.push(*user_presence);
+            stream::write_string(&mut out, rpid).expect("relying party ID length fits in a varuint32");
+        }
+        out
+    }
+
+    /// Legacy `EOS...` string of a K1 key, `None` for other key types
+    pub fn to_legacy_string(&self) -> Option<String> {
+        match self {
+            PublicKey::K1(key) => Some(keys::to_legacy_string(key)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `PUB_K1_`, `PUB_R1_`, `PUB_WA_` and legacy `EOS` strings
+impl FromStr for PublicKey {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PublicKey::from_bin(&keys::public_key_from_string(s).map_err(invalid)?)
+    }
+}
+
+impl Display for PublicKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&keys::to_string("PUB", self.key_type(), &self.to_bin()[1..]))
+    }
+}
+
+/// Serialized as a string in human-readable formats, and in the binary layout otherwise
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        // WebAuthn keys have two more fields
+        let len = 1 + KEY_SIZE + if matches!(self, PublicKey::WA { .. }) { 2 } else { 0 };
+        let mut tuple = serializer.serialize_tuple(len)?;
+        tuple.serialize_element(&(self.key_type() as u8))?;
+        for byte in self.point() {
+            tuple.serialize_element(byte)?;
+        }
+        if let PublicKey::WA { user_presence, rpid, .. } = self {
+            tuple.serialize_element(user_presence)?;
+            tuple.serialize_element(rpid)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return String::deserialize(deserializer)?.parse().map_err(D::Error::custom);
+        }
+
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = PublicKey;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str("a public key")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PublicKey, A::Error> {
+                let mut next = |what: &str| seq.next_element::<u8>()?.ok_or_else(|| A::Error::custom(format!("missing {}", what)));
+                let key_type = next("key type")?;
+                let mut key = [0u8; KEY_SIZE];
+                for byte in key.iter_mut() {
+                    *byte = next("key data")?;
+                }
+                match key_type {
+                    0 => Ok(PublicKey::K1(key)),
+                    1 => Ok(PublicKey::R1(key)),
+                    2 => {
+                        let user_presence = next("user presence")?;
+                        let rpid = seq.next_element::<String>()?.ok_or_else(|| A::Error::custom("missing relying party ID"))?;
+                        Ok(PublicKey::WA { key, user_presence, rpid })
+                    }
+                    _ => Err(A::Error::custom(format!("unknown key type {}", key_type))),
+                }
+            }
+        }
+
+        // the length of WebAuthn keys, the longest ones
+        deserializer.deserialize_tuple(1 + KEY_SIZE + 2, KeyVisitor)
+    }
+}
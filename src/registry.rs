@@ -0,0 +1,194 @@
+//! # ABI registry
+//!
+//!  [`AbiRegistry`], process-wide ABIs with a lazily synchronized context per thread
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+
+use crate::pool::{apply, AbiLog};
+use crate::{AbiDef, AbiLike, Abieos, AbieosError, Name};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+struct Shared {
+    id: u64,
+    /// Copy of `log.generation()`, read without locking
+    generation: AtomicU64,
+    log: RwLock<AbiLog>,
+}
+
+/// Context of the current thread for one registry
+struct LocalContext {
+    registry: Weak<Shared>,
+    abieos: Abieos,
+    synced: Cell<u64>,
+}
+
+thread_local! {
+    static CONTEXTS: RefCell<HashMap<u64, Rc<LocalContext>>> = RefCell::new(HashMap::new());
+}
+
+/// Shared ABI registry with one lazily synchronized [`Abieos`] context per thread
+///
+/// The registry is `Sync` and cheap to clone, clones share the same ABIs. Each thread using it
+/// gets its own context, created on first use. When the ABIs change, a context applies the
+/// changes the next time its thread uses it; otherwise calls only check an atomic counter,
+/// without locking.
+///
+/// ```rust,no_run
+/// use rs_abieos::{AbiRegistry, Name};
+///
+/// let registry = AbiRegistry::new();
+/// let token = Name::try_from("eosio.token").unwrap();
+/// registry.set_abi_json(token, &std::fs::read_to_string("eosio.token.abi").unwrap()).unwrap();
+///
+/// let worker = registry.clone();
+/// std::thread::spawn(move || {
+///     let bytes = [0u8; 0];
+///     let json = worker.bin_to_json("eosio.token", "transfer", &bytes);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct AbiRegistry {
+    shared: Arc<Shared>,
+}
+
+impl AbiRegistry {
+    /// Create an empty registry
+    pub fn new() -> AbiRegistry {
+        AbiRegistry {
+            shared: Arc::new(Shared {
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                generation: AtomicU64::new(0),
+                log: RwLock::new(AbiLog::default()),
+            }),
+        }
+    }
+
+    /// Number of ABI changes made to the registry
+    pub fn generation(&self) -> u64 {
+        self.shared.generation.load(Ordering::Acquire)
+    }
+
+    /// Context of the current thread, synchronized with the registry
+    fn local(&self) -> Rc<LocalContext> {
+        let local = CONTEXTS.with(|contexts| {
+            let mut contexts = contexts.borrow_mut();
+            if !contexts.contains_key(&self.shared.id) {
+                // contexts of dropped registries are released along the way
+                contexts.retain(|_, local| local.registry.strong_count() > 0);
+            }
+            contexts
+                .entry(self.shared.id)
+                .or_insert_with(|| {
+                    Rc::new(LocalContext { registry: Arc::downgrade(&self.shared), abieos: Abieos::new(), synced: Cell::new(0) })
+                })
+                .clone()
+        });
+        if local.synced.get() != self.generation() {
+            let log = self.shared.log.read().unwrap_or_else(|e| e.into_inner());
+            apply(&local.abieos, log.pending(local.synced.get()));
+            local.synced.set(log.generation());
+        }
+        local
+    }
+
+    /// Run `f` with the context of the current thread
+    ///
+    /// The context is created on first use and has all the ABIs of the registry loaded.
+    /// ABIs loaded directly into it are not shared with other threads.
+    pub fn with_context<T>(&self, f: impl FnOnce(&Abieos) -> T) -> T {
+        f(&self.local().abieos)
+    }
+
+    /// Load an ABI into the context of this thread, then record it for all the others
+    fn set_abi(&self, contract: Name, abi: AbiLike) -> Result<bool, AbieosError> {
+        let local = self.local();
+        local.abieos.set_abi_like(contract, &abi)?;
+        let mut log = self.shared.log.write().unwrap_or_else(|e| e.into_inner());
+        let generation = log.record(contract, Some(abi));
+        self.shared.generation.store(generation, Ordering::Release);
+        if local.synced.get() == generation - 1 {
+            local.synced.set(generation);
+        }
+        Ok(true)
+    }
+
+    /// Register a contract ABI (JSON format)
+    pub fn set_abi_json(&self, contract: impl Into<Name>, abi_json: &str) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Json(abi_json.to_string()))
+    }
+
+    /// Register a contract ABI (HEX format)
+    pub fn set_abi_hex(&self, contract: impl Into<Name>, abi_hex: &str) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Hex(abi_hex.to_string()))
+    }
+
+    /// Register a contract ABI (binary format)
+    pub fn set_abi_bin(&self, contract: impl Into<Name>, abi_bin: &[u8]) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Bin(abi_bin.to_vec()))
+    }
+
+    /// Register a contract ABI from a parsed [`AbiDef`]
+    pub fn set_abi_def(&self, contract: impl Into<Name>, abi: &AbiDef) -> Result<bool, AbieosError> {
+        self.set_abi(contract.into(), AbiLike::Bin(abi.to_bin()?))
+    }
+
+    /// Remove a contract from the registry
+    ///
+    /// Returns `false` if no ABI was registered.
+    pub fn delete_contract(&self, contract: impl Into<Name>) -> Result<bool, AbieosError> {
+        let contract = contract.into();
+        let mut log = self.shared.log.write().unwrap_or_else(|e| e.into_inner());
+        if !log.contains(contract) {
+            return Ok(false);
+        }
+        let generation = log.record(contract, None);
+        self.shared.generation.store(generation, Ordering::Release);
+        Ok(true)
+    }
+
+    /// Returns `true` if an ABI is registered for the contract
+    pub fn has_contract(&self, contract: impl Into<Name>) -> bool {
+        self.shared.log.read().unwrap_or_else(|e| e.into_inner()).contains(contract.into())
+    }
+
+    /// Serialize JSON into binary (output as HEX)
+    pub fn json_to_hex(&self, account: &str, datatype: &str, json: &str) -> Result<String, AbieosError> {
+        self.with_context(|abieos| abieos.json_to_hex(account, datatype, json))
+    }
+
+    /// Serialize JSON into binary (output as binary)
+    pub fn json_to_bin(&self, account: &str, datatype: &str, json: &str) -> Result<Vec<u8>, AbieosError> {
+        self.with_context(|abieos| abieos.json_to_bin(account, datatype, json))
+    }
+
+    /// Deserialize HEX string into JSON
+    pub fn hex_to_json(&self, account: &str, datatype: &str, hex: &str) -> Result<String, AbieosError> {
+        self.with_context(|abieos| abieos.hex_to_json(account, datatype, hex))
+    }
+
+    /// Deserialize Binary into JSON
+    pub fn bin_to_json(&self, account: &str, datatype: &str, bin: &[u8]) -> Result<String, AbieosError> {
+        self.with_context(|abieos| abieos.bin_to_json(account, datatype, bin))
+    }
+
+    /// Get the type for an action
+    pub fn get_type_for_action(&self, contract: impl Into<Name>, action: impl Into<Name>) -> Result<String, AbieosError> {
+        self.with_context(|abieos| abieos.get_type_for_action(contract, action))
+    }
+
+    /// Get the type for a table
+    pub fn get_type_for_table(&self, contract: impl Into<Name>, table: impl Into<Name>) -> Result<String, AbieosError> {
+        self.with_context(|abieos| abieos.get_type_for_table(contract, table))
+    }
+}
+
+impl Default for AbiRegistry {
+    fn default() -> Self {
+        AbiRegistry::new()
+    }
+}
@@ -0,0 +1,209 @@
+//! # Secondary index keys
+//!
+//!  [`SecondaryKey`], the keys of contract table secondary indexes, which are not ABI types
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use crate::{builtin, stream, AbieosError};
+
+/// Kinds of secondary index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecondaryIndex {
+    Idx64,
+    Idx128,
+    Idx256,
+    IdxDouble,
+    IdxLongDouble,
+}
+
+impl SecondaryIndex {
+    /// Size of a key in binary
+    pub const fn key_size(self) -> usize {
+        match self {
+            SecondaryIndex::Idx64 | SecondaryIndex::IdxDouble => 8,
+            SecondaryIndex::Idx128 | SecondaryIndex::IdxLongDouble => 16,
+            SecondaryIndex::Idx256 => 32,
+        }
+    }
+
+    /// Name of the index in the chain database
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            SecondaryIndex::Idx64 => "idx64",
+            SecondaryIndex::Idx128 => "idx128",
+            SecondaryIndex::Idx256 => "idx256",
+            SecondaryIndex::IdxDouble => "idx_double",
+            SecondaryIndex::IdxLongDouble => "idx_long_double",
+        }
+    }
+}
+
+/// Parses database index names (`idx64`...) and the `key_type` names of `get_table_rows`
+/// (`i64`, `i128`, `i256`, `sha256`, `float64`, `float128`)
+impl FromStr for SecondaryIndex {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "idx64" | "i64" => Ok(SecondaryIndex::Idx64),
+            "idx128" | "i128" => Ok(SecondaryIndex::Idx128),
+            "idx256" | "i256" | "sha256" => Ok(SecondaryIndex::Idx256),
+            "idx_double" | "float64" => Ok(SecondaryIndex::IdxDouble),
+            "idx_long_double" | "float128" => Ok(SecondaryIndex::IdxLongDouble),
+            _ => Err(AbieosError::InvalidSecondaryKey(format!("unknown index type \"{}\"", s))),
+        }
+    }
+}
+
+impl Display for SecondaryIndex {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Key of a secondary index row
+///
+/// Decoded from the binary form stored by the chain, as found in the `secondary_key` of
+/// state history `contract_index*` deltas. Keys are displayed like nodeos does:
+///
+/// - `idx64` and `idx128` as decimal numbers,
+/// - `idx256` as a checksum256, in lowercase hex,
+/// - `idx_double` as a number and `idx_long_double` as `0x` followed by its 16 bytes in hex.
+///
+/// An `idx256` key is stored as two little-endian 128-bit words, each holding one half of the
+/// checksum in big-endian order. The binary key is therefore not the checksum: every half is
+/// byte-reversed, and state history, which serializes the key as a `checksum256`, shows it
+/// that way. [`SecondaryKey::to_checksum256`] returns the checksum the contract indexed.
+///
+/// ```rust
+/// use rs_abieos::{SecondaryIndex, SecondaryKey};
+///
+/// let key = SecondaryKey::from_bin(SecondaryIndex::Idx64, &42u64.to_le_bytes()).unwrap();
+/// assert_eq!(key.to_string(), "42");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SecondaryKey {
+    Idx64(u64),
+    Idx128(u128),
+    /// Words as stored, the first word holds the first half of the checksum
+    Idx256([u128; 2]),
+    IdxDouble(f64),
+    /// Little-endian IEEE 754 quadruple precision bytes
+    IdxLongDouble([u8; 16]),
+}
+
+impl SecondaryKey {
+    /// Kind of index the key belongs to
+    pub fn index(&self) -> SecondaryIndex {
+        match self {
+            SecondaryKey::Idx64(_) => SecondaryIndex::Idx64,
+            SecondaryKey::Idx128(_) => SecondaryIndex::Idx128,
+            SecondaryKey::Idx256(_) => SecondaryIndex::Idx256,
+            SecondaryKey::IdxDouble(_) => SecondaryIndex::IdxDouble,
+            SecondaryKey::IdxLongDouble(_) => SecondaryIndex::IdxLongDouble,
+        }
+    }
+
+    /// Decode a key from its binary form, which must have the exact size of the index keys
+    pub fn from_bin(index: SecondaryIndex, bin: &[u8]) -> Result<SecondaryKey, AbieosError> {
+        if bin.len() != index.key_size() {
+            return Err(AbieosError::InvalidSecondaryKey(format!(
+                "{} key must be {} bytes, got {}",
+                index,
+                index.key_size(),
+                bin.len()
+            )));
+        }
+        let word = |at: usize| u128::from_le_bytes(bin[at..at + 16].try_into().expect("size checked"));
+        Ok(match index {
+            SecondaryIndex::Idx64 => SecondaryKey::Idx64(u64::from_le_bytes(bin.try_into().expect("size checked"))),
+            SecondaryIndex::Idx128 => SecondaryKey::Idx128(word(0)),
+            SecondaryIndex::Idx256 => SecondaryKey::Idx256([word(0), word(16)]),
+            SecondaryIndex::IdxDouble => SecondaryKey::IdxDouble(f64::from_le_bytes(bin.try_into().expect("size checked"))),
+            SecondaryIndex::IdxLongDouble => SecondaryKey::IdxLongDouble(bin.try_into().expect("size checked")),
+        })
+    }
+
+    /// Decode a key from its binary form as HEX
+    pub fn from_hex(index: SecondaryIndex, hex: &str) -> Result<SecondaryKey, AbieosError> {
+        let bin = stream::from_hex(hex).map_err(AbieosError::InvalidSecondaryKey)?;
+        SecondaryKey::from_bin(index, &bin)
+    }
+
+    /// Encode the key in binary
+    pub fn to_bin(&self) -> Vec<u8> {
+        match self {
+            SecondaryKey::Idx64(v) => v.to_le_bytes().to_vec(),
+            SecondaryKey::Idx128(v) => v.to_le_bytes().to_vec(),
+            SecondaryKey::Idx256([high, low]) => [high.to_le_bytes(), low.to_le_bytes()].concat(),
+            SecondaryKey::IdxDouble(v) => v.to_le_bytes().to_vec(),
+            SecondaryKey::IdxLongDouble(v) => v.to_vec(),
+        }
+    }
+
+    /// Key of an `idx256` index for a checksum
+    pub fn from_checksum256(checksum: [u8; 32]) -> SecondaryKey {
+        let half = |at: usize| u128::from_be_bytes(checksum[at..at + 16].try_into().expect("32 bytes"));
+        SecondaryKey::Idx256([half(0), half(16)])
+    }
+
+    /// Checksum indexed by an `idx256` key, `None` for other indexes
+    pub fn to_checksum256(&self) -> Option<[u8; 32]> {
+        match self {
+            SecondaryKey::Idx256([high, low]) => {
+                let mut checksum = [0u8; 32];
+                checksum[..16].copy_from_slice(&high.to_be_bytes());
+                checksum[16..].copy_from_slice(&low.to_be_bytes());
+                Some(checksum)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a key from the format it is displayed in
+    ///
+    /// `idx64` and `idx128` keys are also accepted in `0x` prefixed hex.
+    pub fn parse(index: SecondaryIndex, text: &str) -> Result<SecondaryKey, AbieosError> {
+        let invalid = || AbieosError::InvalidSecondaryKey(format!("invalid {} key \"{}\"", index, text));
+        let hex = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X"));
+        match index {
+            SecondaryIndex::Idx64 => match hex {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => text.parse(),
+            }
+            .map(SecondaryKey::Idx64)
+            .map_err(|_| invalid()),
+            SecondaryIndex::Idx128 => match hex {
+                Some(hex) => u128::from_str_radix(hex, 16),
+                None => text.parse(),
+            }
+            .map(SecondaryKey::Idx128)
+            .map_err(|_| invalid()),
+            SecondaryIndex::Idx256 => {
+                let checksum = stream::from_hex(text).ok().and_then(|bin| bin.try_into().ok()).ok_or_else(invalid)?;
+                Ok(SecondaryKey::from_checksum256(checksum))
+            }
+            SecondaryIndex::IdxDouble => text.parse().map(SecondaryKey::IdxDouble).map_err(|_| invalid()),
+            SecondaryIndex::IdxLongDouble => {
+                let bin = hex.and_then(|hex| stream::from_hex(hex).ok()).ok_or_else(invalid)?;
+                SecondaryKey::from_bin(index, &bin).map_err(|_| invalid())
+            }
+        }
+    }
+}
+
+impl Display for SecondaryKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            SecondaryKey::Idx64(v) => write!(f, "{}", v),
+            SecondaryKey::Idx128(v) => write!(f, "{}", v),
+            SecondaryKey::Idx256(_) => {
+                let checksum = self.to_checksum256().expect("idx256 key");
+                f.write_str(&stream::to_hex(&checksum).to_ascii_lowercase())
+            }
+            SecondaryKey::IdxDouble(v) => f.write_str(&builtin::format_float(*v)),
+            SecondaryKey::IdxLongDouble(v) => write!(f, "0x{}", stream::to_hex(v).to_ascii_lowercase()),
+        }
+    }
+}
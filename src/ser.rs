@@ -0,0 +1,403 @@
+//! # Binary serializer
+//!
+//!  A serde [`Serializer`](serde::Serializer) writing the Antelope wire format directly from Rust values,
+//!  without going through JSON
+//!
+//! | Rust / serde                     | Antelope binary                            |
+//! |----------------------------------|--------------------------------------------|
+//! | `bool`, integers, floats         | little-endian (`bool` as one byte)         |
+//! | `String`, `&str`, `char`         | varuint32 length + UTF-8 bytes             |
+//! | `Vec<T>`, `&[T]`, sets, bytes    | varuint32 length + items (`T[]`, `bytes`)  |
+//! | maps                             | varuint32 length + key/value pairs         |
+//! | `[T; N]`, tuples                 | items only (`checksum256`, ...)            |
+//! | `Option<T>`                      | presence byte + value (`T?`)               |
+//! | structs                          | fields in declaration order                |
+//! | enums                            | varuint32 index + payload (ABI variants)   |
+//! | [`Name`](crate::Name)            | `u64`                                      |
+//!
+//! Output is byte-identical to `json_to_bin` when the Rust type mirrors the ABI type:
+//!
+//! ```rust
+//! use rs_abieos::{ser, Name};
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Asset {
+//!     amount: i64,
+//!     symbol: u64,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Transfer {
+//!     from: Name,
+//!     to: Name,
+//!     quantity: Asset,
+//!     memo: String,
+//! }
+//!
+//! let transfer = Transfer {
+//!     from: "alice".parse().unwrap(),
+//!     to: "bob".parse().unwrap(),
+//!     quantity: Asset { amount: 10000, symbol: 0x534f4504 },
+//!     memo: "Hello!".to_string(),
+//! };
+//! let bin = ser::to_bytes(&transfer).unwrap();
+//! assert_eq!(bin.len(), 39);
+//! ```
+
+use serde::ser::{self, Serialize};
+
+use crate::stream;
+use crate::AbieosError;
+
+/// Serialize a value into Antelope binary
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, AbieosError> {
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_bytes())
+}
+
+/// Serialize a value into Antelope binary, as uppercase hex
+pub fn to_hex<T: Serialize + ?Sized>(value: &T) -> Result<String, AbieosError> {
+    to_bytes(value).map(|bin| stream::to_hex(&bin))
+}
+
+/// Antelope binary serializer, see the [module documentation](self)
+#[derive(Default)]
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    pub fn new() -> Serializer {
+        Serializer::default()
+    }
+
+    /// Consume the serializer, returning the bytes written so far
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.output
+    }
+
+    fn write_length(&mut self, len: usize) -> Result<(), AbieosError> {
+        stream::write_length(&mut self.output, len).map_err(AbieosError::Serialize)
+    }
+}
+
+impl ser::Error for AbieosError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        AbieosError::Serialize(msg.to_string())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = AbieosError;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), AbieosError> {
+        self.output.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), AbieosError> {
+        self.output.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), AbieosError> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), AbieosError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), AbieosError> {
+        stream::write_string(&mut self.output, v).map_err(AbieosError::Serialize)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), AbieosError> {
+        stream::write_bytes(&mut self.output, v).map_err(AbieosError::Serialize)
+    }
+
+    fn serialize_none(self) -> Result<(), AbieosError> {
+        self.output.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), AbieosError> {
+        self.output.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), AbieosError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), AbieosError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<(), AbieosError> {
+        stream::write_varuint32(&mut self.output, variant_index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), AbieosError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), AbieosError> {
+        stream::write_varuint32(&mut self.output, variant_index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'a>, AbieosError> {
+        Compound::prefixed(self, len)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, AbieosError> {
+        Ok(Compound::fixed(self))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>, AbieosError> {
+        Ok(Compound::fixed(self))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, AbieosError> {
+        stream::write_varuint32(&mut self.output, variant_index);
+        Ok(Compound::fixed(self))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'a>, AbieosError> {
+        Compound::prefixed(self, len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>, AbieosError> {
+        Ok(Compound::fixed(self))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, AbieosError> {
+        stream::write_varuint32(&mut self.output, variant_index);
+        Ok(Compound::fixed(self))
+    }
+}
+
+/// State of a sequence, map, tuple or struct being serialized
+///
+/// Sequences of unknown length are counted, and their length prefix is inserted at the end.
+pub struct Compound<'a> {
+    ser: &'a mut Serializer,
+    /// Start offset and item count of a sequence whose length was not known upfront
+    pending: Option<(usize, usize)>,
+}
+
+impl<'a> Compound<'a> {
+    fn fixed(ser: &'a mut Serializer) -> Compound<'a> {
+        Compound { ser, pending: None }
+    }
+
+    fn prefixed(ser: &'a mut Serializer, len: Option<usize>) -> Result<Compound<'a>, AbieosError> {
+        match len {
+            Some(len) => {
+                ser.write_length(len)?;
+                Ok(Compound::fixed(ser))
+            }
+            None => {
+                let start = ser.output.len();
+                Ok(Compound { ser, pending: Some((start, 0)) })
+            }
+        }
+    }
+
+    fn item<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), AbieosError> {
+        if let Some((_, count)) = &mut self.pending {
+            *count += 1;
+        }
+        value.serialize(&mut *self.ser)
+    }
+
+    fn finish(self) -> Result<(), AbieosError> {
+        if let Some((start, count)) = self.pending {
+            let mut prefix = Vec::new();
+            stream::write_length(&mut prefix, count).map_err(AbieosError::Serialize)?;
+            self.ser.output.splice(start..start, prefix);
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), AbieosError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), AbieosError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), AbieosError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), AbieosError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), AbieosError> {
+        self.item(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), AbieosError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), AbieosError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = AbieosError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), AbieosError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), AbieosError> {
+        self.finish()
+    }
+}
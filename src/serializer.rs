@@ -0,0 +1,528 @@
+//! ABI-driven serializer, converting JSON to the wire format and back in pure Rust
+
+use std::collections::HashMap;
+
+use crate::abi_def::{AbiDef, StructDef, VariantDef};
+use crate::builtin::Builtin;
+use crate::json::JsonValue;
+use crate::stream::{self, InputStream};
+use crate::Name;
+
+/// Maximum nesting of structs, variants, arrays and optionals
+const MAX_DEPTH: usize = 64;
+
+/// Maximum length of typedef and base chains
+const MAX_CHAIN: usize = 32;
+
+/// Value tree converted to and from the wire format by the serializer
+///
+/// Implemented by the JSON model and by [`AbiValue`](crate::AbiValue), which only differ in
+/// how builtin types and the containers are represented.
+pub(crate) trait WireValue: Sized {
+    /// Short description of the value kind, for error messages
+    fn kind(&self) -> &'static str;
+    fn builtin_to_bin(&self, builtin: Builtin, out: &mut Vec<u8>) -> Result<(), String>;
+    fn builtin_from_bin(builtin: Builtin, s: &mut InputStream) -> Result<Self, String>;
+    /// `None` for an empty optional, the contained value otherwise
+    fn as_optional(&self) -> Option<&Self>;
+    fn from_optional(value: Option<Self>) -> Self;
+    fn as_items(&self) -> Option<&[Self]>;
+    fn from_items(items: Vec<Self>) -> Self;
+    fn is_struct(&self) -> bool;
+    fn field(&self, name: &str) -> Option<&Self>;
+    fn from_fields(fields: Vec<(String, Self)>) -> Self;
+    fn as_variant(&self) -> Option<(&str, &Self)>;
+    fn from_variant(alternative: String, value: Self) -> Self;
+}
+
+impl WireValue for JsonValue {
+    fn kind(&self) -> &'static str {
+        JsonValue::kind(self)
+    }
+
+    fn builtin_to_bin(&self, builtin: Builtin, out: &mut Vec<u8>) -> Result<(), String> {
+        builtin.json_to_bin(self, out)
+    }
+
+    fn builtin_from_bin(builtin: Builtin, s: &mut InputStream) -> Result<Self, String> {
+        builtin.bin_to_json(s)
+    }
+
+    fn as_optional(&self) -> Option<&Self> {
+        match self {
+            JsonValue::Null => None,
+            value => Some(value),
+        }
+    }
+
+    fn from_optional(value: Option<Self>) -> Self {
+        value.unwrap_or(JsonValue::Null)
+    }
+
+    fn as_items(&self) -> Option<&[Self]> {
+        self.as_array()
+    }
+
+    fn from_items(items: Vec<Self>) -> Self {
+        JsonValue::Array(items)
+    }
+
+    fn is_struct(&self) -> bool {
+        self.as_object().is_some()
+    }
+
+    fn field(&self, name: &str) -> Option<&Self> {
+        self.get(name)
+    }
+
+    fn from_fields(fields: Vec<(String, Self)>) -> Self {
+        JsonValue::Object(fields)
+    }
+
+    fn as_variant(&self) -> Option<(&str, &Self)> {
+        match self.as_array() {
+            Some([JsonValue::String(alternative), value]) => Some((alternative, value)),
+            _ => None,
+        }
+    }
+
+    fn from_variant(alternative: String, value: Self) -> Self {
+        JsonValue::Array(vec![JsonValue::String(alternative), value])
+    }
+}
+
+/// A type name resolved against the ABI
+enum Resolved<'a> {
+    Builtin(Builtin),
+    Optional(&'a str),
+    Array(&'a str),
+    FixedArray(&'a str, usize),
+    Extension(&'a str),
+    Struct(&'a StructDef),
+    Variant(&'a VariantDef),
+}
+
+/// Serializer for one contract ABI
+///
+/// All typedefs, struct bases, fields and variant alternatives are checked to resolve
+/// when the serializer is created.
+pub(crate) struct AbiSerializer {
+    abi: AbiDef,
+    typedefs: HashMap<String, String>,
+    structs: HashMap<String, usize>,
+    variants: HashMap<String, usize>,
+}
+
+impl AbiSerializer {
+    pub(crate) fn new(abi: AbiDef) -> Result<AbiSerializer, String> {
+        check_version(&abi.version)?;
+        let mut serializer = AbiSerializer {
+            typedefs: HashMap::new(),
+            structs: HashMap::new(),
+            variants: HashMap::new(),
+            abi,
+        };
+        let mut names: Vec<&str> = Vec::new();
+        for t in &serializer.abi.types {
+            names.push(&t.new_type_name);
+            serializer.typedefs.insert(t.new_type_name.clone(), t.type_.clone());
+        }
+        for (i, s) in serializer.abi.structs.iter().enumerate() {
+            names.push(&s.name);
+            serializer.structs.insert(s.name.clone(), i);
+        }
+        for (i, v) in serializer.abi.variants.iter().enumerate() {
+            names.push(&v.name);
+            serializer.variants.insert(v.name.clone(), i);
+        }
+        let mut seen = std::collections::HashSet::new();
+        for name in names {
+            if Builtin::from_name(name).is_some() || !seen.insert(name) {
+                return Err(format!("abi redefines type \"{}\"", name));
+            }
+        }
+        serializer.validate()?;
+        Ok(serializer)
+    }
+
+    #[cfg(feature = "pure-rust")]
+    pub(crate) fn action_type(&self, action: Name) -> Option<&str> {
+        self.abi.get_action(action).map(|a| a.type_.as_str())
+    }
+
+    #[cfg(feature = "pure-rust")]
+    pub(crate) fn table_type(&self, table: Name) -> Option<&str> {
+        self.abi.get_table(table).map(|t| t.type_.as_str())
+    }
+
+    /// Name of the primary key field of a table, if the ABI declares one
+    pub(crate) fn table_key(&self, table: Name) -> Option<&str> {
+        self.abi.get_table(table)?.key_names.first().map(String::as_str)
+    }
+
+    #[cfg(feature = "pure-rust")]
+    pub(crate) fn action_result_type(&self, action: Name) -> Option<&str> {
+        self.abi.get_action_result(action).map(|r| r.result_type.as_str())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for t in &self.abi.types {
+            self.validate_type(&t.type_, false)?;
+        }
+        for s in &self.abi.structs {
+            let mut base = s;
+            for _ in 0..MAX_CHAIN {
+                if base.base.is_empty() {
+                    break;
+                }
+                base = match self.resolve(&base.base)? {
+                    Resolved::Struct(b) => b,
+                    _ => return Err(format!("base of struct \"{}\" is not a struct: \"{}\"", s.name, base.base)),
+                };
+            }
+            if !base.base.is_empty() {
+                return Err(format!("struct \"{}\" has a recursive base", s.name));
+            }
+            let mut extensions = false;
+            for f in &s.fields {
+                self.validate_type(&f.type_, true)?;
+                if f.type_.ends_with('$') {
+                    extensions = true;
+                } else if extensions {
+                    return Err(format!("field \"{}.{}\" follows a binary extension", s.name, f.name));
+                }
+            }
+        }
+        for v in &self.abi.variants {
+            for t in &v.types {
+                self.validate_type(t, false)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_type(&self, name: &str, allow_extension: bool) -> Result<(), String> {
+        match self.resolve(name)? {
+            Resolved::Extension(inner) if allow_extension => self.validate_type(inner, false),
+            Resolved::Extension(_) => Err(format!("binary extension \"{}\" is only allowed on struct fields", name)),
+            Resolved::Optional(inner) | Resolved::Array(inner) | Resolved::FixedArray(inner, _) => self.validate_type(inner, false),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolve a type name, following typedefs
+    fn resolve<'a>(&'a self, name: &'a str) -> Result<Resolved<'a>, String> {
+        let mut name = name;
+        for _ in 0..MAX_CHAIN {
+            if let Some(inner) = name.strip_suffix('$') {
+                return Ok(Resolved::Extension(inner));
+            }
+            if let Some(inner) = name.strip_suffix('?') {
+                return Ok(Resolved::Optional(inner));
+            }
+            if let Some(inner) = name.strip_suffix("[]") {
+                return Ok(Resolved::Array(inner));
+            }
+            if let Some(open) = name.strip_suffix(']').and_then(|n| n.rfind('[')) {
+                let size = name[open + 1..name.len() - 1]
+                    .parse()
+                    .map_err(|_| format!("invalid array size in \"{}\"", name))?;
+                return Ok(Resolved::FixedArray(&name[..open], size));
+            }
+            if let Some(builtin) = Builtin::from_name(name) {
+                return Ok(Resolved::Builtin(builtin));
+            }
+            if let Some(&i) = self.structs.get(name) {
+                return Ok(Resolved::Struct(&self.abi.structs[i]));
+            }
+            if let Some(&i) = self.variants.get(name) {
+                return Ok(Resolved::Variant(&self.abi.variants[i]));
+            }
+            match self.typedefs.get(name) {
+                Some(target) => name = target,
+                None => return Err(format!("unknown type \"{}\"", name)),
+            }
+        }
+        Err(format!("typedef chain is too long at \"{}\"", name))
+    }
+
+    /// Serialize JSON text into binary
+    pub(crate) fn json_to_bin(&self, type_name: &str, json: &str) -> Result<Vec<u8>, String> {
+        self.json_value_to_bin_vec(type_name, &JsonValue::parse(json)?)
+    }
+
+    /// Serialize a parsed JSON value into binary
+    pub(crate) fn json_value_to_bin_vec(&self, type_name: &str, value: &JsonValue) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        self.json_value_to_bin(type_name, value, &mut out)?;
+        Ok(out)
+    }
+
+    /// Serialize a parsed JSON value into binary, appending to `out`
+    pub(crate) fn json_value_to_bin(&self, type_name: &str, value: &JsonValue, out: &mut Vec<u8>) -> Result<(), String> {
+        self.value_to_bin(type_name, value, out)
+    }
+
+    /// Serialize a value tree into binary, appending to `out`
+    pub(crate) fn value_to_bin<V: WireValue>(&self, type_name: &str, value: &V, out: &mut Vec<u8>) -> Result<(), String> {
+        let mut walker = Walker::new(type_name);
+        walker.encode(self, type_name, value, out)
+    }
+
+    /// Deserialize binary into JSON text, requiring all data to be consumed
+    pub(crate) fn bin_to_json(&self, type_name: &str, bin: &[u8]) -> Result<String, String> {
+        self.bin_to_value_exact::<JsonValue>(type_name, bin).map(|value| value.to_json())
+    }
+
+    /// Deserialize binary into a value tree, requiring all data to be consumed
+    pub(crate) fn bin_to_value_exact<V: WireValue>(&self, type_name: &str, bin: &[u8]) -> Result<V, String> {
+        let mut s = InputStream::new(bin);
+        let value = self.bin_to_value(type_name, &mut s)?;
+        if s.remaining() > 0 {
+            return Err(s.error("extra data"));
+        }
+        Ok(value)
+    }
+
+    /// Deserialize one value tree from the stream
+    pub(crate) fn bin_to_value<V: WireValue>(&self, type_name: &str, s: &mut InputStream) -> Result<V, String> {
+        let mut walker = Walker::new(type_name);
+        walker.decode(self, type_name, s)
+    }
+
+    /// Byte range and field path of every decoded value, in stream order
+    ///
+    /// Decoding stops at the first error, the values read until then are returned.
+    pub(crate) fn trace(&self, type_name: &str, bin: &[u8]) -> Vec<(usize, usize, String)> {
+        let mut walker = Walker::new(type_name);
+        walker.spans = Some(Vec::new());
+        let _ = walker.decode::<JsonValue>(self, type_name, &mut InputStream::new(bin));
+        walker.spans.unwrap_or_default()
+    }
+}
+
+/// Only the `eosio::abi/1.x` ABI format is supported
+pub(crate) fn check_version(version: &str) -> Result<(), String> {
+    if version.starts_with("eosio::abi/1.") {
+        Ok(())
+    } else {
+        Err(format!("unsupported abi version \"{}\"", version))
+    }
+}
+
+/// Recursion state, tracking the field path for error messages
+struct Walker {
+    path: Vec<String>,
+    depth: usize,
+    /// Byte ranges of decoded values and their paths, when tracing
+    spans: Option<Vec<(usize, usize, String)>>,
+}
+
+impl Walker {
+    fn new(type_name: &str) -> Walker {
+        Walker { path: vec![type_name.to_string()], depth: 0, spans: None }
+    }
+
+    fn record(&mut self, start: usize, end: usize) {
+        if let Some(spans) = &mut self.spans {
+            spans.push((start, end, self.path.concat()));
+        }
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> String {
+        format!("{} (at {})", message, self.path.concat())
+    }
+
+    fn enter(&mut self, segment: String) -> Result<(), String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("recursion limit reached"));
+        }
+        self.depth += 1;
+        self.path.push(segment);
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+        self.path.pop();
+    }
+
+    fn nested<T>(&mut self, segment: String, f: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        self.enter(segment)?;
+        let result = f(self);
+        self.leave();
+        result
+    }
+
+    fn encode<V: WireValue>(&mut self, abi: &AbiSerializer, type_name: &str, value: &V, out: &mut Vec<u8>) -> Result<(), String> {
+        match abi.resolve(type_name).map_err(|e| self.error(e))? {
+            Resolved::Builtin(builtin) => value.builtin_to_bin(builtin, out).map_err(|e| self.error(e)),
+            Resolved::Extension(_) => Err(self.error(format!("unexpected binary extension \"{}\"", type_name))),
+            Resolved::Optional(inner) => match value.as_optional() {
+                None => {
+                    out.push(0);
+                    Ok(())
+                }
+                Some(value) => {
+                    out.push(1);
+                    self.encode(abi, inner, value, out)
+                }
+            },
+            Resolved::Array(inner) => {
+                let items = value.as_items().ok_or_else(|| self.error(format!("expected array, got {}", value.kind())))?;
+                stream::write_length(out, items.len()).map_err(|e| self.error(e))?;
+                self.items_to_bin(abi, inner, items, out)
+            }
+            Resolved::FixedArray(inner, size) => {
+                let items = value.as_items().ok_or_else(|| self.error(format!("expected array, got {}", value.kind())))?;
+                if items.len() != size {
+                    return Err(self.error(format!("expected {} items, got {}", size, items.len())));
+                }
+                stream::write_length(out, size).map_err(|e| self.error(e))?;
+                self.items_to_bin(abi, inner, items, out)
+            }
+            Resolved::Struct(def) => {
+                if !value.is_struct() {
+                    return Err(self.error(format!("expected object, got {}", value.kind())));
+                }
+                let mut missing_extension = None;
+                self.struct_to_bin(abi, def, value, out, &mut missing_extension)
+            }
+            Resolved::Variant(def) => {
+                let (alternative, inner) = value
+                    .as_variant()
+                    .ok_or_else(|| self.error(format!("expected variant, got {}", value.kind())))?;
+                let index = def
+                    .types
+                    .iter()
+                    .position(|t| t == alternative)
+                    .ok_or_else(|| self.error(format!("type \"{}\" is not valid for variant \"{}\"", alternative, def.name)))?;
+                stream::write_varuint32(out, index as u32);
+                self.nested(format!("<{}>", alternative), |w| w.encode(abi, &def.types[index], inner, out))
+            }
+        }
+    }
+
+    fn items_to_bin<V: WireValue>(&mut self, abi: &AbiSerializer, inner: &str, items: &[V], out: &mut Vec<u8>) -> Result<(), String> {
+        for (i, item) in items.iter().enumerate() {
+            self.nested(format!("[{}]", i), |w| w.encode(abi, inner, item, out))?;
+        }
+        Ok(())
+    }
+
+    fn struct_to_bin<V: WireValue>(
+        &mut self,
+        abi: &AbiSerializer,
+        def: &StructDef,
+        value: &V,
+        out: &mut Vec<u8>,
+        missing_extension: &mut Option<String>,
+    ) -> Result<(), String> {
+        if !def.base.is_empty() {
+            if let Resolved::Struct(base) = abi.resolve(&def.base).map_err(|e| self.error(e))? {
+                self.struct_to_bin(abi, base, value, out, missing_extension)?;
+            }
+        }
+        for field in &def.fields {
+            let field_value = value.field(&field.name);
+            if let Some(inner) = field.type_.strip_suffix('$') {
+                match (field_value, &missing_extension) {
+                    (None, _) => *missing_extension = Some(field.name.clone()),
+                    (Some(_), Some(missing)) => {
+                        return Err(self.error(format!("unexpected field \"{}\" after missing binary extension \"{}\"", field.name, missing)));
+                    }
+                    (Some(v), None) => self.nested(format!(".{}", field.name), |w| w.encode(abi, inner, v, out))?,
+                }
+                continue;
+            }
+            match field_value {
+                Some(v) => self.nested(format!(".{}", field.name), |w| w.encode(abi, &field.type_, v, out))?,
+                None if field.type_.ends_with('?') => out.push(0),
+                None => return Err(self.error(format!("missing field \"{}\"", field.name))),
+            }
+        }
+        Ok(())
+    }
+
+    fn decode<V: WireValue>(&mut self, abi: &AbiSerializer, type_name: &str, s: &mut InputStream) -> Result<V, String> {
+        let start = s.pos();
+        match abi.resolve(type_name).map_err(|e| self.error(e))? {
+            Resolved::Builtin(builtin) => {
+                let value = V::builtin_from_bin(builtin, s).map_err(|e| self.error(e))?;
+                self.record(start, s.pos());
+                Ok(value)
+            }
+            Resolved::Extension(_) => Err(self.error(format!("unexpected binary extension \"{}\"", type_name))),
+            Resolved::Optional(inner) => {
+                let flag = s.read_u8().map_err(|e| self.error(e))?;
+                self.record(start, s.pos());
+                match flag {
+                    0 => Ok(V::from_optional(None)),
+                    1 => Ok(V::from_optional(Some(self.decode(abi, inner, s)?))),
+                    _ => Err(self.error(format!("invalid optional flag at offset {}", start))),
+                }
+            }
+            Resolved::Array(inner) => {
+                let len = s.read_length(1).map_err(|e| self.error(e))?;
+                self.record(start, s.pos());
+                self.items_to_json(abi, inner, len, s)
+            }
+            Resolved::FixedArray(inner, size) => {
+                let len = s.read_length(1).map_err(|e| self.error(e))?;
+                self.record(start, s.pos());
+                if len != size {
+                    return Err(self.error(format!("expected {} items, got {}", size, len)));
+                }
+                self.items_to_json(abi, inner, len, s)
+            }
+            Resolved::Struct(def) => {
+                let mut members = Vec::new();
+                self.struct_to_json(abi, def, s, &mut members)?;
+                Ok(V::from_fields(members))
+            }
+            Resolved::Variant(def) => {
+                let index = s.read_varuint32().map_err(|e| self.error(e))? as usize;
+                self.record(start, s.pos());
+                let alternative = def
+                    .types
+                    .get(index)
+                    .ok_or_else(|| self.error(format!("invalid index {} for variant \"{}\" at offset {}", index, def.name, start)))?;
+                let inner = self.nested(format!("<{}>", alternative), |w| w.decode(abi, alternative, s))?;
+                Ok(V::from_variant(alternative.clone(), inner))
+            }
+        }
+    }
+
+    fn items_to_json<V: WireValue>(&mut self, abi: &AbiSerializer, inner: &str, len: usize, s: &mut InputStream) -> Result<V, String> {
+        let mut items = Vec::with_capacity(len);
+        for i in 0..len {
+            items.push(self.nested(format!("[{}]", i), |w| w.decode(abi, inner, s))?);
+        }
+        Ok(V::from_items(items))
+    }
+
+    fn struct_to_json<V: WireValue>(
+        &mut self,
+        abi: &AbiSerializer,
+        def: &StructDef,
+        s: &mut InputStream,
+        members: &mut Vec<(String, V)>,
+    ) -> Result<(), String> {
+        if !def.base.is_empty() {
+            if let Resolved::Struct(base) = abi.resolve(&def.base).map_err(|e| self.error(e))? {
+                self.struct_to_json(abi, base, s, members)?;
+            }
+        }
+        for field in &def.fields {
+            let type_name = match field.type_.strip_suffix('$') {
+                // missing binary extensions are omitted from the output
+                Some(_) if s.remaining() == 0 => return Ok(()),
+                Some(inner) => inner,
+                None => &field.type_,
+            };
+            let value = self.nested(format!(".{}", field.name), |w| w.decode(abi, type_name, s))?;
+            members.push((field.name.clone(), value));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! # Signatures
+//!
+//!  [`Signature`], parsed and formatted in pure Rust with the layout abieos uses in binary
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::stream::{self, InputStream};
+use crate::{keys, AbieosError, KeyType};
+
+/// Size of a compact signature: the recovery ID, `r` and `s`
+const COMPACT_SIZE: usize = 65;
+
+/// A K1, R1 or WebAuthn signature
+///
+/// Parsed from `SIG_K1_`, `SIG_R1_` and `SIG_WA_` strings, whose checksums are verified.
+///
+/// ```rust
+/// use rs_abieos::{KeyType, Signature};
+///
+/// let text = "SIG_K1_KX7XyBK1NG2nV3Kz8vFu9hKNhBbuWfrNHTwaynweSfLncxre5VTxDgGiLy1kxk1BHoPaijPsoyTKRKkTGZ5S9TkoM93vm5";
+/// let signature: Signature = text.parse().unwrap();
+/// assert_eq!(signature.key_type(), KeyType::K1);
+/// assert_eq!(Signature::from_bin(&signature.to_bin()).unwrap().to_string(), text);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Signature {
+    K1([u8; COMPACT_SIZE]),
+    R1([u8; COMPACT_SIZE]),
+    /// Signature of a WebAuthn authenticator, with the data it signed besides the digest
+    WA {
+        compact: [u8; COMPACT_SIZE],
+        auth_data: Vec<u8>,
+        client_json: String,
+    },
+}
+
+fn invalid(message: impl Into<String>) -> AbieosError {
+    AbieosError::InvalidSignature(message.into())
+}
+
+impl Signature {
+    /// Curve of the key that made the signature
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            Signature::K1(_) => KeyType::K1,
+            Signature::R1(_) => KeyType::R1,
+            Signature::WA { .. } => KeyType::WA,
+        }
+    }
+
+    /// Compact signature: the recovery ID plus 31, `r` and `s`
+    pub fn compact(&self) -> &[u8; COMPACT_SIZE] {
+        match self {
+            Signature::K1(compact) | Signature::R1(compact) | Signature::WA { compact, .. } => compact,
+        }
+    }
+
+    pub(crate) fn read(s: &mut InputStream) -> Result<Signature, String> {
+        let key_type = keys::read_key_type(s)?;
+        let compact = s.read_array()?;
+        Ok(match key_type {
+            KeyType::K1 => Signature::K1(compact),
+            KeyType::R1 => Signature::R1(compact),
+            KeyType::WA => Signature::WA {
+                compact,
+                auth_data: s.read_byte_vec()?,
+                client_json: s.read_string()?,
+            },
+        })
+    }
+
+    /// Decode a signature from binary, which must hold exactly one signature
+    pub fn from_bin(bin: &[u8]) -> Result<Signature, AbieosError> {
+        let mut s = InputStream::new(bin);
+        let signature = Signature::read(&mut s).map_err(invalid)?;
+        if s.remaining() > 0 {
+            return Err(invalid(s.error("extra data after signature")));
+        }
+        Ok(signature)
+    }
+
+    /// Encode the signature in binary: its type as a varuint32, the compact signature, then the
+    /// WebAuthn fields
+    pub fn to_bin(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + COMPACT_SIZE);
+        stream::write_varuint32(&mut out, self.key_type() as u32);
+        out.extend_from_slice(self.compact());
+        if let Signature::WA { auth_data, client_json, .. } = self {
+            stream::write_bytes(&mut out, auth_data).expect("auth data length fits in a varuint32");
+            stream::write_string(&mut out, client_json).expect("client JSON length fits in a varuint32");
+        }
+        out
+    }
+}
+
+/// Parses `SIG_K1_`, `SIG_R1_` and `SIG_WA_` strings
+impl FromStr for Signature {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Signature::from_bin(&keys::signature_from_string(s).map_err(invalid)?)
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&keys::to_string("SIG", self.key_type(), &self.to_bin()[1..]))
+    }
+}
+
+/// Serialized as a string in human-readable formats, and in the binary layout otherwise
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        // WebAuthn signatures have two more fields
+        let len = 1 + COMPACT_SIZE + if matches!(self, Signature::WA { .. }) { 2 } else { 0 };
+        let mut tuple = serializer.serialize_tuple(len)?;
+        tuple.serialize_element(&(self.key_type() as u8))?;
+        for byte in self.compact() {
+            tuple.serialize_element(byte)?;
+        }
+        if let Signature::WA { auth_data, client_json, .. } = self {
+            tuple.serialize_element(auth_data)?;
+            tuple.serialize_element(client_json)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return String::deserialize(deserializer)?.parse().map_err(D::Error::custom);
+        }
+
+        struct SignatureVisitor;
+
+        impl<'de> Visitor<'de> for SignatureVisitor {
+            type Value = Signature;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str("a signature")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Signature, A::Error> {
+                let mut next = |what: &str| seq.next_element::<u8>()?.ok_or_else(|| A::Error::custom(format!("missing {}", what)));
+                let key_type = next("key type")?;
+                let mut compact = [0u8; COMPACT_SIZE];
+                for byte in compact.iter_mut() {
+                    *byte = next("signature data")?;
+                }
+                match key_type {
+                    0 => Ok(Signature::K1(compact)),
+                    1 => Ok(Signature::R1(compact)),
+                    2 => {
+                        let auth_data = seq.next_element::<Vec<u8>>()?.ok_or_else(|| A::Error::custom("missing auth data"))?;
+                        let client_json = seq.next_element::<String>()?.ok_or_else(|| A::Error::custom("missing client JSON"))?;
+                        Ok(Signature::WA { compact, auth_data, client_json })
+                    }
+                    _ => Err(A::Error::custom(format!("unknown key type {}", key_type))),
+                }
+            }
+        }
+
+        // the length of WebAuthn signatures, the longest ones
+        deserializer.deserialize_tuple(1 + COMPACT_SIZE + 2, SignatureVisitor)
+    }
+}
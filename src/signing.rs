@@ -0,0 +1,303 @@
+//! # Signing
+//!
+//!  [`PrivateKey`] and [`SignedTransaction`], signing transactions offline with K1 and R1 keys,
+//!  and recovery of the keys that signed them
+
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use ecdsa::elliptic_curve::generic_array::ArrayLength;
+use ecdsa::elliptic_curve::ops::{Invert, Reduce};
+use ecdsa::elliptic_curve::point::DecompressPoint;
+use ecdsa::elliptic_curve::sec1::{self, FromEncodedPoint, ToEncodedPoint};
+use ecdsa::elliptic_curve::subtle::CtOption;
+use ecdsa::elliptic_curve::{AffinePoint, CurveArithmetic, FieldBytes, FieldBytesSize, PrimeCurve, PrimeField, Scalar};
+use ecdsa::hazmat::{SignPrimitive, VerifyPrimitive};
+use ecdsa::{RecoveryId, SignatureSize, SigningKey, VerifyingKey};
+use rfc6979::HmacDrbg;
+use sha2::Sha256;
+
+use crate::packed_transaction::{Compression, PackedTransaction};
+use crate::stream;
+use crate::transaction::{pack_context_free_data, signing_digest, transaction_id};
+use crate::{keys, AbieosError, KeyType, PublicKey, Signature};
+
+/// Private key used to sign transactions
+///
+/// Parsed from `PVT_K1_`, `PVT_R1_` and legacy WIF (`5...`, always K1) strings. It is
+/// displayed as `PVT_K1_`/`PVT_R1_`, and `Debug` only shows the public key.
+///
+/// ```rust
+/// use rs_abieos::PrivateKey;
+///
+/// let key: PrivateKey = "5KQwrPbwdL6PhXujxW37FSSQZ1JiwsST4cqQzDeyXtP79zkvFD3".parse().unwrap();
+/// assert_eq!(key.public_key().to_string(), "PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63");
+/// ```
+#[derive(Clone)]
+pub enum PrivateKey {
+    K1(k256::ecdsa::SigningKey),
+    R1(p256::ecdsa::SigningKey),
+}
+
+fn invalid(message: impl Into<String>) -> AbieosError {
+    AbieosError::InvalidKey(message.into())
+}
+
+/// Whether a compact K1 signature is accepted by nodeos, which rejects `r` and `s` values
+/// with their high bit set or a leading zero byte that could be dropped
+fn is_canonical(compact: &[u8; 65]) -> bool {
+    compact[1] & 0x80 == 0
+        && !(compact[1] == 0 && compact[2] & 0x80 == 0)
+        && compact[33] & 0x80 == 0
+        && !(compact[33] == 0 && compact[34] & 0x80 == 0)
+}
+
+/// Sign a digest in the compact form of nodeos: the recovery ID plus 31, `r` and `s`
+///
+/// Nonces are the successive outputs of the RFC 6979 generator from the second one on, like the
+/// `extended_nonce_function` keosd passes to secp256k1, which increments its attempt counter
+/// before the first call. Signatures have a low `s` and, when `canonical` is set, nonces are
+/// drawn until [`is_canonical`] holds.
+fn sign_compact<C>(key: &SigningKey<C>, digest: &[u8; 32], canonical: bool) -> [u8; 65]
+where
+    C: PrimeCurve + CurveArithmetic,
+    Scalar<C>: Invert<Output = CtOption<Scalar<C>>> + SignPrimitive<C> + Reduce<C::Uint>,
+    AffinePoint<C>: DecompressPoint<C> + FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+    FieldBytesSize<C>: sec1::ModulusSize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    let secret = key.as_nonzero_scalar();
+    let z = FieldBytes::<C>::clone_from_slice(digest);
+    let mut drbg = HmacDrbg::<Sha256>::new(&secret.to_repr(), digest, &[]);
+    drbg.fill_bytes(&mut [0u8; 32]);
+    loop {
+        let mut nonce = [0u8; 32];
+        drbg.fill_bytes(&mut nonce);
+        let Some(k) = Option::<Scalar<C>>::from(Scalar::<C>::from_repr(FieldBytes::<C>::clone_from_slice(&nonce))) else { continue };
+        let Ok((signature, _)) = secret.try_sign_prehashed(k, &z) else { continue };
+        let signature = signature.normalize_s().unwrap_or(signature);
+        let recovery_id = RecoveryId::trial_recovery_from_prehash(key.verifying_key(), digest, &signature)
+            .expect("signature made with this key");
+        let mut compact = [0u8; 65];
+        compact[0] = 31 + recovery_id.to_byte();
+        compact[1..].copy_from_slice(&signature.to_bytes());
+        if !canonical || is_canonical(&compact) {
+            return compact;
+        }
+    }
+}
+
+/// Recover the compressed public key of a compact signature, with a low or high `s`
+fn recover_compact<C>(compact: &[u8; 65], digest: &[u8; 32]) -> Result<[u8; 33], String>
+where
+    C: PrimeCurve + CurveArithmetic,
+    AffinePoint<C>: DecompressPoint<C> + FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+    FieldBytesSize<C>: sec1::ModulusSize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    // 27 to 30 for uncompressed keys and 31 to 34 for compressed keys, both are accepted
+    let recovery_id = compact[0].checked_sub(27).filter(|id| *id < 8).ok_or("invalid recovery ID")? & 3;
+    let signature = ecdsa::Signature::<C>::from_slice(&compact[1..]).map_err(|_| "invalid r or s")?;
+    let (signature, recovery_id) = match signature.normalize_s() {
+        Some(low) => (low, recovery_id ^ 1),
+        None => (signature, recovery_id),
+    };
+    let recovery_id = RecoveryId::from_byte(recovery_id).expect("recovery ID is below 4");
+    let key = VerifyingKey::<C>::recover_from_prehash(digest, &signature, recovery_id).map_err(|_| "public key recovery failed")?;
+    Ok(key.to_encoded_point(true).as_bytes().try_into().expect("compressed points are 33 bytes"))
+}
+
+impl PrivateKey {
+    /// Curve of the key
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            PrivateKey::K1(_) => KeyType::K1,
+            PrivateKey::R1(_) => KeyType::R1,
+        }
+    }
+
+    /// Create a key from its 32 secret bytes
+    pub fn from_bytes(key_type: KeyType, bytes: &[u8; 32]) -> Result<PrivateKey, AbieosError> {
+        match key_type {
+            KeyType::K1 => k256::ecdsa::SigningKey::from_slice(bytes).map(PrivateKey::K1),
+            KeyType::R1 => p256::ecdsa::SigningKey::from_slice(bytes).map(PrivateKey::R1),
+            KeyType::WA => return Err(invalid("WA private keys are not supported")),
+        }
+        .map_err(|_| invalid("key is not a valid scalar"))
+    }
+
+    /// The 32 secret bytes of the key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        match self {
+            PrivateKey::K1(key) => key.to_bytes().into(),
+            PrivateKey::R1(key) => key.to_bytes().into(),
+        }
+    }
+
+    /// Legacy WIF string of a K1 key, `None` for R1 keys
+    pub fn to_wif(&self) -> Option<String> {
+        match self {
+            PrivateKey::K1(_) => Some(keys::wif_encode(&self.to_bytes())),
+            PrivateKey::R1(_) => None,
+        }
+    }
+
+    /// Public key of the key pair
+    pub fn public_key(&self) -> PublicKey {
+        let point = |encoded: &[u8]| encoded.try_into().expect("compressed points are 33 bytes");
+        match self {
+            PrivateKey::K1(key) => PublicKey::K1(point(key.verifying_key().to_encoded_point(true).as_bytes())),
+            PrivateKey::R1(key) => PublicKey::R1(point(key.verifying_key().to_encoded_point(true).as_bytes())),
+        }
+    }
+
+    /// Sign a digest, such as the [`signing_digest`] of a transaction
+    ///
+    /// Signing is deterministic. K1 signatures are canonical, as nodeos requires.
+    pub fn sign_digest(&self, digest: &[u8; 32]) -> Signature {
+        match self {
+            PrivateKey::K1(key) => Signature::K1(sign_compact(key, digest, true)),
+            PrivateKey::R1(key) => Signature::R1(sign_compact(key, digest, false)),
+        }
+    }
+}
+
+/// Parses `PVT_K1_`, `PVT_R1_` and legacy WIF strings
+impl FromStr for PrivateKey {
+    type Err = AbieosError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.starts_with("PVT_") {
+            return PrivateKey::from_bytes(KeyType::K1, &keys::wif_decode(s).map_err(invalid)?);
+        }
+        let bin = keys::private_key_from_string(s).map_err(invalid)?;
+        let key_type = if bin[0] == KeyType::R1 as u8 { KeyType::R1 } else { KeyType::K1 };
+        PrivateKey::from_bytes(key_type, bin[1..].try_into().expect("33 bytes"))
+    }
+}
+
+impl Display for PrivateKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&keys::to_string("PVT", self.key_type(), &self.to_bytes()))
+    }
+}
+
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_tuple("PrivateKey").field(&self.public_key().to_string()).finish()
+    }
+}
+
+impl Signature {
+    /// Recover the public key that signed a digest
+    ///
+    /// K1 signatures must be canonical, as nodeos requires. WebAuthn signatures are not supported.
+    pub fn recover(&self, digest: &[u8; 32]) -> Result<PublicKey, AbieosError> {
+        let invalid = |message: String| AbieosError::InvalidSignature(message);
+        match self {
+            Signature::K1(compact) => {
+                if !is_canonical(compact) {
+                    return Err(invalid("K1 signature is not canonical".to_string()));
+                }
+                recover_compact::<k256::Secp256k1>(compact, digest).map(PublicKey::K1).map_err(|e| invalid(e.to_string()))
+            }
+            Signature::R1(compact) => recover_compact::<p256::NistP256>(compact, digest).map(PublicKey::R1).map_err(|e| invalid(e.to_string())),
+            Signature::WA { .. } => Err(invalid("WA signature recovery is not supported".to_string())),
+        }
+    }
+}
+
+impl PublicKey {
+    /// Whether `signature` is a signature of `digest` by this key
+    ///
+    /// Like nodeos, the key is recovered from the signature and compared, so a signature that
+    /// cannot be recovered does not verify.
+    pub fn verify(&self, digest: &[u8; 32], signature: &Signature) -> bool {
+        signature.recover(digest).is_ok_and(|key| key == *self)
+    }
+}
+
+/// Recover the keys that signed a packed transaction on the chain `chain_id`, in the order of
+/// its signatures
+///
+/// The transaction and its context free data are unpacked to compute the signing digest.
+pub fn recover_transaction_signers(packed: &PackedTransaction, chain_id: &[u8; 32]) -> Result<Vec<PublicKey>, AbieosError> {
+    let digest = packed.signing_digest(chain_id)?;
+    packed.signatures.iter().map(|signature| signature.recover(&digest)).collect()
+}
+
+/// A serialized transaction with its context free data and signatures
+///
+/// ```rust
+/// use rs_abieos::{Abieos, Compression, PrivateKey, SignedTransaction};
+///
+/// let trx = Abieos::new().encode_transaction(r#"{
+///     "expiration": "2024-01-01T00:00:00.000", "ref_block_num": 1, "ref_block_prefix": 2,
+///     "max_net_usage_words": 0, "max_cpu_usage_ms": 0, "delay_sec": 0,
+///     "context_free_actions": [], "actions": [], "transaction_extensions": []
+/// }"#).unwrap();
+/// let key: PrivateKey = "5KQwrPbwdL6PhXujxW37FSSQZ1JiwsST4cqQzDeyXtP79zkvFD3".parse().unwrap();
+///
+/// let mut signed = SignedTransaction::new(trx).unwrap();
+/// signed.sign(&key, &[0; 32]);
+/// let body = signed.pack(Compression::None).unwrap().to_json();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SignedTransaction {
+    /// Serialized `transaction`
+    pub transaction: Vec<u8>,
+    pub context_free_data: Vec<Vec<u8>>,
+    pub signatures: Vec<Signature>,
+}
+
+impl SignedTransaction {
+    /// Wrap a serialized transaction, checking it decodes as a `transaction`
+    pub fn new(transaction: Vec<u8>) -> Result<SignedTransaction, AbieosError> {
+        PackedTransaction::pack(&transaction, Compression::None)?;
+        Ok(SignedTransaction {
+            transaction,
+            ..Default::default()
+        })
+    }
+
+    /// Set the context free data, which is covered by the signatures
+    pub fn with_context_free_data(mut self, context_free_data: Vec<Vec<u8>>) -> SignedTransaction {
+        self.context_free_data = context_free_data;
+        self
+    }
+
+    /// ID of the transaction
+    pub fn id(&self) -> [u8; 32] {
+        transaction_id(&self.transaction)
+    }
+
+    /// Digest signed for the chain `chain_id`
+    pub fn signing_digest(&self, chain_id: &[u8; 32]) -> [u8; 32] {
+        signing_digest(chain_id, &self.transaction, &self.context_free_data)
+    }
+
+    /// Sign for the chain `chain_id` and add the signature, which is returned
+    pub fn sign(&mut self, key: &PrivateKey, chain_id: &[u8; 32]) -> &Signature {
+        let signature = key.sign_digest(&self.signing_digest(chain_id));
+        self.signatures.push(signature);
+        self.signatures.last().expect("signature pushed")
+    }
+
+    /// Pack with the signatures, to push to nodeos
+    pub fn pack(&self, compression: Compression) -> Result<PackedTransaction, AbieosError> {
+        let mut packed = PackedTransaction::pack_with_context_free_data(&self.transaction, &self.context_free_data, compression)?;
+        packed.signatures = self.signatures.clone();
+        Ok(packed)
+    }
+
+    /// Serialize as a `signed_transaction`: the transaction, its signatures and context free data
+    pub fn to_bin(&self) -> Result<Vec<u8>, AbieosError> {
+        let mut out = self.transaction.clone();
+        stream::write_length(&mut out, self.signatures.len()).map_err(AbieosError::Serialize)?;
+        for signature in &self.signatures {
+            out.extend(signature.to_bin());
+        }
+        out.extend(pack_context_free_data(&self.context_free_data).map_err(AbieosError::Serialize)?);
+        Ok(out)
+    }
+}
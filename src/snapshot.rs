@@ -0,0 +1,98 @@
+//! # Snapshots
+//!
+//!  [`AbieosSnapshot`], the ABIs loaded into a context, to copy them into another one
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+
+use crate::{stream, AbiLike, Abieos, AbieosError, Name};
+
+/// ABIs loaded into an [`Abieos`] context, taken with [`Abieos::snapshot`]
+///
+/// ABIs are kept in the format they were loaded in and shared between clones of the
+/// snapshot. A snapshot is `Send` and `Sync`, so it can seed contexts on other threads.
+#[derive(Debug, Clone, Default)]
+pub struct AbieosSnapshot {
+    abis: Vec<(Name, Arc<AbiLike>)>,
+}
+
+impl AbieosSnapshot {
+    /// Contracts in the snapshot, in name order
+    pub fn contracts(&self) -> impl Iterator<Item = Name> + '_ {
+        self.abis.iter().map(|(contract, _)| *contract)
+    }
+
+    /// ABI of a contract in the snapshot
+    pub fn abi(&self, contract: impl Into<Name>) -> Option<&AbiLike> {
+        let contract = contract.into();
+        self.abis.iter().find(|(name, _)| *name == contract).map(|(_, abi)| abi.as_ref())
+    }
+
+    /// Number of contracts in the snapshot
+    pub fn len(&self) -> usize {
+        self.abis.len()
+    }
+
+    /// Returns `true` if the snapshot has no contracts
+    pub fn is_empty(&self) -> bool {
+        self.abis.is_empty()
+    }
+}
+
+impl Abieos {
+    /// SHA-256 of the binary ABI
+    ///
+    /// JSON and HEX ABIs are converted to binary first, the text itself is hashed if that fails.
+    fn abi_hash(&self, abi: &AbiLike) -> [u8; 32] {
+        let bin = match abi {
+            AbiLike::Json(abi_json) => self.abi_json_to_bin(abi_json).map_err(|_| abi_json),
+            AbiLike::Hex(abi_hex) => stream::from_hex(abi_hex).map_err(|_| abi_hex),
+            AbiLike::Bin(abi_bin) => return Sha256::digest(abi_bin).into(),
+        };
+        match bin {
+            Ok(bin) => Sha256::digest(bin).into(),
+            Err(text) => Sha256::digest(text.as_bytes()).into(),
+        }
+    }
+
+    /// Contracts loaded through the `set_abi_*` methods, with the SHA-256 of their binary ABI
+    ///
+    /// Hashes are computed on first use and cached until the ABI changes.
+    pub fn loaded_contracts(&self) -> impl Iterator<Item = (Name, [u8; 32])> {
+        self.abis.borrow().hashes(|abi| self.abi_hash(abi)).into_iter()
+    }
+
+    /// Take a snapshot of the ABIs loaded through the `set_abi_*` methods
+    pub fn snapshot(&self) -> AbieosSnapshot {
+        AbieosSnapshot { abis: self.abis.borrow().sources() }
+    }
+
+    /// Load the ABIs of a snapshot, deleting the contracts it does not have
+    ///
+    /// Contracts already loaded with the same ABI are left untouched.
+    pub fn restore(&self, snapshot: &AbieosSnapshot) -> Result<(), AbieosError> {
+        let current: BTreeMap<Name, Arc<AbiLike>> = self.abis.borrow().sources().into_iter().collect();
+        for contract in current.keys() {
+            if snapshot.abi(*contract).is_none() {
+                self.delete_contract_native(*contract)?;
+            }
+        }
+        for (contract, abi) in &snapshot.abis {
+            if current.get(contract) != Some(abi) {
+                self.set_abi_like(*contract, abi)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a new context with the same ABIs loaded
+    ///
+    /// Only ABIs loaded through the `set_abi_*` methods of this wrapper are copied.
+    pub fn try_clone(&self) -> Result<Abieos, AbieosError> {
+        let abieos = Abieos::new();
+        abieos.restore(&self.snapshot())?;
+        Ok(abieos)
+    }
+}
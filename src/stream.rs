@@ -0,0 +1,184 @@
+//! Binary helpers for the Antelope wire format (little-endian integers, varuint32 prefixes)
+
+/// Cursor over a binary buffer
+#[derive(Clone)]
+pub(crate) struct InputStream<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> InputStream<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> InputStream<'a> {
+        InputStream { data, pos: 0 }
+    }
+
+    /// Number of bytes left to read
+    pub(crate) fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    /// Current read offset
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Unread part of the buffer
+    pub(crate) fn rest(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+
+    pub(crate) fn error(&self, message: &str) -> String {
+        format!("{} at offset {}", message, self.pos)
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.remaining() < len {
+            return Err(self.error("unexpected end of data"));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.read_bytes(N)?);
+        Ok(out)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_varuint32(&mut self) -> Result<u32, String> {
+        let start = self.pos;
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let b = self.read_u8()?;
+            result |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            if shift >= 35 {
+                self.pos = start;
+                return Err(self.error("varuint32 is too long"));
+            }
+        }
+        u32::try_from(result).map_err(|_| {
+            format!("varuint32 overflow at offset {}", start)
+        })
+    }
+
+    pub(crate) fn read_varint32(&mut self) -> Result<i32, String> {
+        let v = self.read_varuint32()?;
+        Ok(((v >> 1) as i32) ^ -((v & 1) as i32))
+    }
+
+    /// Read a varuint32 length, checking it against the remaining data
+    ///
+    /// `min_item_size` is the smallest possible encoding of one item, used to reject
+    /// impossible lengths before allocating.
+    pub(crate) fn read_length(&mut self, min_item_size: usize) -> Result<usize, String> {
+        let start = self.pos;
+        let len = self.read_varuint32()? as usize;
+        if len.saturating_mul(min_item_size) > self.remaining() {
+            return Err(format!("length {} exceeds remaining data at offset {}", len, start));
+        }
+        Ok(len)
+    }
+
+    pub(crate) fn read_string(&mut self) -> Result<String, String> {
+        let len = self.read_length(1)?;
+        let start = self.pos;
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| format!("invalid utf-8 string at offset {}", start))
+    }
+
+    pub(crate) fn read_byte_vec(&mut self) -> Result<Vec<u8>, String> {
+        let len = self.read_length(1)?;
+        Ok(self.read_bytes(len)?.to_vec())
+    }
+}
+
+pub(crate) fn write_varuint32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let mut b = (value & 0x7f) as u8;
+        value >>= 7;
+        if value > 0 {
+            b |= 0x80;
+        }
+        out.push(b);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+pub(crate) fn write_varint32(out: &mut Vec<u8>, value: i32) {
+    write_varuint32(out, ((value as u32) << 1) ^ ((value >> 31) as u32));
+}
+
+/// Write a varuint32 length prefix
+pub(crate) fn write_length(out: &mut Vec<u8>, len: usize) -> Result<(), String> {
+    let len = u32::try_from(len).map_err(|_| format!("length {} does not fit in a varuint32", len))?;
+    write_varuint32(out, len);
+    Ok(())
+}
+
+pub(crate) fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) -> Result<(), String> {
+    write_length(out, bytes.len())?;
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+pub(crate) fn write_string(out: &mut Vec<u8>, s: &str) -> Result<(), String> {
+    write_bytes(out, s.as_bytes())
+}
+
+/// Encode bytes as uppercase hex, like abieos
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Decode a hex string (either case)
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    let bytes = hex.as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return Err("hex string has an odd number of digits".to_string());
+    }
+    bytes
+        .chunks(2)
+        .map(|pair| match (nibble(pair[0]), nibble(pair[1])) {
+            (Some(h), Some(l)) => Ok((h << 4) | l),
+            _ => Err(format!("invalid hex digit in \"{}\"", hex)),
+        })
+        .collect()
+}
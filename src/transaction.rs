@@ -0,0 +1,141 @@
+//! # Transactions
+//!
+//!  Transaction codec decoding the data of every action with the ABI of its contract
+
+use std::sync::OnceLock;
+
+use sha2::{Digest, Sha256};
+
+use crate::json::JsonValue;
+use crate::serializer::AbiSerializer;
+use crate::{builtin_abis, stream, Abieos, AbieosError, Name};
+
+/// Serializer of the transaction ABI, parsed on first use
+pub(crate) fn transaction_abi() -> &'static AbiSerializer {
+    static SERIALIZER: OnceLock<AbiSerializer> = OnceLock::new();
+    SERIALIZER.get_or_init(|| {
+        builtin_abis::TRANSACTION
+            .to_def()
+            .map_err(|e| e.to_string())
+            .and_then(AbiSerializer::new)
+            .expect("embedded transaction ABI is valid")
+    })
+}
+
+/// Serialize context free data as a `vector<bytes>`
+pub(crate) fn pack_context_free_data(context_free_data: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    stream::write_length(&mut out, context_free_data.len())?;
+    for data in context_free_data {
+        stream::write_bytes(&mut out, data)?;
+    }
+    Ok(out)
+}
+
+/// ID of a serialized transaction, the SHA-256 of its bytes
+pub fn transaction_id(transaction: &[u8]) -> [u8; 32] {
+    Sha256::digest(transaction).into()
+}
+
+/// Digest signed by the authorizers of a serialized transaction
+///
+/// SHA-256 of the chain ID, the transaction and the SHA-256 of the serialized context free
+/// data, or 32 zero bytes when there is none.
+pub fn signing_digest(chain_id: &[u8; 32], transaction: &[u8], context_free_data: &[Vec<u8>]) -> [u8; 32] {
+    let cfd_digest: [u8; 32] = if context_free_data.is_empty() {
+        [0; 32]
+    } else {
+        let cfd = pack_context_free_data(context_free_data).expect("context free data lengths fit in a varuint32");
+        Sha256::digest(cfd).into()
+    };
+    Sha256::new()
+        .chain_update(chain_id)
+        .chain_update(transaction)
+        .chain_update(cfd_digest)
+        .finalize()
+        .into()
+}
+
+/// Action lists of a transaction
+const ACTION_LISTS: [&str; 2] = ["context_free_actions", "actions"];
+
+fn actions_mut(trx: &mut JsonValue) -> impl Iterator<Item = &mut JsonValue> {
+    let fields = match trx {
+        JsonValue::Object(fields) => fields.as_mut_slice(),
+        _ => &mut [],
+    };
+    fields
+        .iter_mut()
+        .filter(|(key, _)| ACTION_LISTS.contains(&key.as_str()))
+        .filter_map(|(_, actions)| match actions {
+            JsonValue::Array(actions) => Some(actions.iter_mut()),
+            _ => None,
+        })
+        .flatten()
+}
+
+fn name_field(fields: &[(String, JsonValue)], key: &str) -> Option<Name> {
+    let (_, value) = fields.iter().find(|(k, _)| k == key)?;
+    value.as_str()?.parse().ok()
+}
+
+impl Abieos {
+    /// Deserialize a transaction into JSON, decoding the data of its actions
+    ///
+    /// The data of an action is decoded with the ABI of its `account` when it is loaded in
+    /// this context, and kept in `hex_data` like nodeos `get_transaction` does. Otherwise,
+    /// `data` stays in HEX.
+    pub fn decode_transaction(&self, bin: &[u8]) -> Result<String, AbieosError> {
+        let mut trx: JsonValue = transaction_abi()
+            .bin_to_value_exact("transaction", bin)
+            .map_err(AbieosError::BinToJson)?;
+        for action in actions_mut(&mut trx) {
+            self.decode_action_data(action);
+        }
+        Ok(trx.to_json())
+    }
+
+    fn decode_action_data(&self, action: &mut JsonValue) {
+        let JsonValue::Object(fields) = action else { return };
+        let (Some(account), Some(name)) = (name_field(fields, "account"), name_field(fields, "name")) else { return };
+        let Some((_, data)) = fields.iter_mut().find(|(key, _)| key == "data") else { return };
+        let Some(hex) = data.as_str().map(str::to_string) else { return };
+        let decoded = stream::from_hex(&hex)
+            .ok()
+            .and_then(|bin| self.decode_action(account, name, &bin).ok())
+            .and_then(|json| JsonValue::parse(&json).ok());
+        if let Some(decoded) = decoded {
+            *data = decoded;
+            fields.push(("hex_data".to_string(), JsonValue::String(hex)));
+        }
+    }
+
+    /// Serialize a transaction from JSON
+    ///
+    /// The `data` of an action is either HEX or a JSON object, serialized with the ABI of its
+    /// `account`, which must be loaded in this context. `hex_data` is ignored.
+    pub fn encode_transaction(&self, json: &str) -> Result<Vec<u8>, AbieosError> {
+        let mut trx = JsonValue::parse(json).map_err(AbieosError::JsonToBin)?;
+        for action in actions_mut(&mut trx) {
+            self.encode_action_data(action)?;
+        }
+        transaction_abi()
+            .json_value_to_bin_vec("transaction", &trx)
+            .map_err(AbieosError::JsonToBin)
+    }
+
+    fn encode_action_data(&self, action: &mut JsonValue) -> Result<(), AbieosError> {
+        let JsonValue::Object(fields) = action else { return Ok(()) };
+        let (account, name) = (name_field(fields, "account"), name_field(fields, "name"));
+        let Some((_, data)) = fields.iter_mut().find(|(key, _)| key == "data") else { return Ok(()) };
+        if data.as_object().is_none() {
+            return Ok(());
+        }
+        let (Some(account), Some(name)) = (account, name) else {
+            return Err(AbieosError::JsonToBin("action with JSON data needs a valid account and name".to_string()));
+        };
+        let bin = self.encode_action(account, name, &data.to_json())?;
+        *data = JsonValue::String(stream::to_hex(&bin));
+        Ok(())
+    }
+}
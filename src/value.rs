@@ -0,0 +1,324 @@
+//! # Typed values
+//!
+//!  [`AbiValue`], a value tree decoded with the ABI, keeping the Antelope type of every field
+
+use crate::builtin::{self, Builtin};
+use crate::json::JsonValue;
+use crate::keys;
+use crate::serializer::WireValue;
+use crate::stream::{self, InputStream};
+use crate::{Asset, ExtendedAsset, Name, PublicKey, Signature, Symbol, SymbolCode};
+
+/// A value of an ABI type
+///
+/// Unlike JSON, builtin values keep their exact type and precision: 64-bit integers are not
+/// strings, time points keep their microseconds and checksums are byte arrays.
+///
+/// Struct fields are kept in ABI order. Binary extensions absent from the data are omitted.
+/// When encoding, fields are looked up by name, and missing optional fields are encoded as empty.
+///
+/// `AbiValue::to_json` (and the `serde_json::Value` conversions with the `serde_json` feature)
+/// produce the same JSON as `bin_to_json`, except for time points with sub-millisecond
+/// precision which keep their microseconds, so converting back with `json_to_value` is lossless.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbiValue {
+    Bool(bool),
+    Int8(i8),
+    Uint8(u8),
+    Int16(i16),
+    Uint16(u16),
+    Int32(i32),
+    Uint32(u32),
+    Int64(i64),
+    Uint64(u64),
+    Int128(i128),
+    Uint128(u128),
+    VarUint32(u32),
+    VarInt32(i32),
+    Float32(f32),
+    Float64(f64),
+    /// IEEE 754 binary128, as little-endian bytes
+    Float128([u8; 16]),
+    /// Microseconds since the Unix epoch
+    TimePoint(i64),
+    /// Seconds since the Unix epoch
+    TimePointSec(u32),
+    /// Half-second slots since 2000-01-01
+    BlockTimestamp(u32),
+    Name(Name),
+    Bytes(Vec<u8>),
+    /// Bits of a `bitset`, the first one being the lowest
+    Bitset(Vec<bool>),
+    String(String),
+    Checksum160([u8; 20]),
+    Checksum256([u8; 32]),
+    Checksum512([u8; 64]),
+    PublicKey(PublicKey),
+    /// Private key in the `PVT_K1_`/`PVT_R1_` format, as a string since the `PrivateKey` type
+    /// needs the `signing` feature
+    PrivateKey(String),
+    Signature(Signature),
+    Symbol(Symbol),
+    SymbolCode(SymbolCode),
+    Asset(Asset),
+    ExtendedAsset(ExtendedAsset),
+    /// Struct fields (including base fields) in ABI order
+    Struct(Vec<(String, AbiValue)>),
+    /// Variant alternative type name and value
+    Variant(String, Box<AbiValue>),
+    Array(Vec<AbiValue>),
+    Optional(Option<Box<AbiValue>>),
+}
+
+impl AbiValue {
+    /// Look up a struct field
+    pub fn get(&self, field: &str) -> Option<&AbiValue> {
+        match self {
+            AbiValue::Struct(fields) => fields.iter().find(|(name, _)| name == field).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Convert to JSON text, in the same format as `bin_to_json`
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_json()
+    }
+
+    pub(crate) fn to_json_value(&self) -> JsonValue {
+        match self {
+            AbiValue::Struct(fields) => {
+                JsonValue::Object(fields.iter().map(|(name, value)| (name.clone(), value.to_json_value())).collect())
+            }
+            AbiValue::Variant(alternative, value) => {
+                JsonValue::Array(vec![JsonValue::String(alternative.clone()), value.to_json_value()])
+            }
+            AbiValue::Array(items) => JsonValue::Array(items.iter().map(AbiValue::to_json_value).collect()),
+            AbiValue::Optional(None) => JsonValue::Null,
+            AbiValue::Optional(Some(value)) => value.to_json_value(),
+            // abieos writes milliseconds, microseconds are only added when present
+            AbiValue::TimePoint(us) if us.rem_euclid(1000) != 0 => {
+                let ms = builtin::format_time_ms(us.div_euclid(1000));
+                JsonValue::String(format!("{}{:03}", ms, us.rem_euclid(1000)))
+            }
+            AbiValue::PrivateKey(key) => JsonValue::String(key.clone()),
+            value => {
+                // builtin values are formatted by the JSON codec from their wire encoding
+                let builtin = value.builtin().expect("containers are handled above");
+                let mut bin = Vec::new();
+                value
+                    .builtin_to_bin(builtin, &mut bin)
+                    .and_then(|_| builtin.bin_to_json(&mut InputStream::new(&bin)))
+                    .unwrap_or_else(JsonValue::String)
+            }
+        }
+    }
+
+    /// Builtin type of a leaf value
+    fn builtin(&self) -> Option<Builtin> {
+        Some(match self {
+            AbiValue::Bool(_) => Builtin::Bool,
+            AbiValue::Int8(_) => Builtin::Int8,
+            AbiValue::Uint8(_) => Builtin::Uint8,
+            AbiValue::Int16(_) => Builtin::Int16,
+            AbiValue::Uint16(_) => Builtin::Uint16,
+            AbiValue::Int32(_) => Builtin::Int32,
+            AbiValue::Uint32(_) => Builtin::Uint32,
+            AbiValue::Int64(_) => Builtin::Int64,
+            AbiValue::Uint64(_) => Builtin::Uint64,
+            AbiValue::Int128(_) => Builtin::Int128,
+            AbiValue::Uint128(_) => Builtin::Uint128,
+            AbiValue::VarUint32(_) => Builtin::VarUint32,
+            AbiValue::VarInt32(_) => Builtin::VarInt32,
+            AbiValue::Float32(_) => Builtin::Float32,
+            AbiValue::Float64(_) => Builtin::Float64,
+            AbiValue::Float128(_) => Builtin::Float128,
+            AbiValue::TimePoint(_) => Builtin::TimePoint,
+            AbiValue::TimePointSec(_) => Builtin::TimePointSec,
+            AbiValue::BlockTimestamp(_) => Builtin::BlockTimestamp,
+            AbiValue::Name(_) => Builtin::Name,
+            AbiValue::Bytes(_) => Builtin::Bytes,
+            AbiValue::Bitset(_) => Builtin::Bitset,
+            AbiValue::String(_) => Builtin::String,
+            AbiValue::Checksum160(_) => Builtin::Checksum160,
+            AbiValue::Checksum256(_) => Builtin::Checksum256,
+            AbiValue::Checksum512(_) => Builtin::Checksum512,
+            AbiValue::PublicKey(_) => Builtin::PublicKey,
+            AbiValue::PrivateKey(_) => Builtin::PrivateKey,
+            AbiValue::Signature(_) => Builtin::Signature,
+            AbiValue::Symbol(_) => Builtin::Symbol,
+            AbiValue::SymbolCode(_) => Builtin::SymbolCode,
+            AbiValue::Asset(_) => Builtin::Asset,
+            AbiValue::ExtendedAsset(_) => Builtin::ExtendedAsset,
+            AbiValue::Struct(_) | AbiValue::Variant(..) | AbiValue::Array(_) | AbiValue::Optional(_) => return None,
+        })
+    }
+}
+
+impl WireValue for AbiValue {
+    fn kind(&self) -> &'static str {
+        match self {
+            AbiValue::Struct(_) => "struct",
+            AbiValue::Variant(..) => "variant",
+            AbiValue::Array(_) => "array",
+            AbiValue::Optional(_) => "optional",
+            value => value.builtin().map_or("value", Builtin::type_name),
+        }
+    }
+
+    fn builtin_to_bin(&self, builtin: Builtin, out: &mut Vec<u8>) -> Result<(), String> {
+        match (builtin, self) {
+            (Builtin::Bool, AbiValue::Bool(v)) => out.push(*v as u8),
+            (Builtin::Int8, AbiValue::Int8(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Uint8, AbiValue::Uint8(v)) => out.push(*v),
+            (Builtin::Int16, AbiValue::Int16(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Uint16, AbiValue::Uint16(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Int32, AbiValue::Int32(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Uint32, AbiValue::Uint32(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Int64, AbiValue::Int64(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Uint64, AbiValue::Uint64(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Int128, AbiValue::Int128(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Uint128, AbiValue::Uint128(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::VarUint32, AbiValue::VarUint32(v)) => stream::write_varuint32(out, *v),
+            (Builtin::VarInt32, AbiValue::VarInt32(v)) => stream::write_varint32(out, *v),
+            (Builtin::Float32, AbiValue::Float32(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Float64, AbiValue::Float64(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Float128, AbiValue::Float128(v)) => out.extend_from_slice(v),
+            (Builtin::TimePoint, AbiValue::TimePoint(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::TimePointSec, AbiValue::TimePointSec(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::BlockTimestamp, AbiValue::BlockTimestamp(v)) => out.extend_from_slice(&v.to_le_bytes()),
+            (Builtin::Name, AbiValue::Name(v)) => out.extend_from_slice(&v.as_u64().to_le_bytes()),
+            (Builtin::Bytes, AbiValue::Bytes(v)) => stream::write_bytes(out, v)?,
+            (Builtin::Bitset, AbiValue::Bitset(v)) => builtin::write_bitset(out, v)?,
+            (Builtin::String, AbiValue::String(v)) => stream::write_string(out, v)?,
+            (Builtin::Checksum160, AbiValue::Checksum160(v)) => out.extend_from_slice(v),
+            (Builtin::Checksum256, AbiValue::Checksum256(v)) => out.extend_from_slice(v),
+            (Builtin::Checksum512, AbiValue::Checksum512(v)) => out.extend_from_slice(v),
+            (Builtin::PublicKey, AbiValue::PublicKey(v)) => out.extend(v.to_bin()),
+            (Builtin::PrivateKey, AbiValue::PrivateKey(v)) => out.extend_from_slice(&keys::private_key_from_string(v)?),
+            (Builtin::Signature, AbiValue::Signature(v)) => out.extend(v.to_bin()),
+            (Builtin::Symbol, AbiValue::Symbol(v)) => out.extend_from_slice(&v.as_u64().to_le_bytes()),
+            (Builtin::SymbolCode, AbiValue::SymbolCode(v)) => out.extend_from_slice(&v.as_u64().to_le_bytes()),
+            (Builtin::Asset, AbiValue::Asset(v)) => write_asset(v, out),
+            (Builtin::ExtendedAsset, AbiValue::ExtendedAsset(v)) => {
+                write_asset(&v.quantity, out);
+                out.extend_from_slice(&v.contract.as_u64().to_le_bytes());
+            }
+            (builtin, value) => return Err(format!("expected {}, got {}", builtin.type_name(), value.kind())),
+        }
+        Ok(())
+    }
+
+    fn builtin_from_bin(builtin: Builtin, s: &mut InputStream) -> Result<Self, String> {
+        Ok(match builtin {
+            Builtin::Bool => match s.read_u8()? {
+                0 => AbiValue::Bool(false),
+                1 => AbiValue::Bool(true),
+                _ => return Err(s.error("invalid bool")),
+            },
+            Builtin::Int8 => AbiValue::Int8(s.read_u8()? as i8),
+            Builtin::Uint8 => AbiValue::Uint8(s.read_u8()?),
+            Builtin::Int16 => AbiValue::Int16(s.read_u16()? as i16),
+            Builtin::Uint16 => AbiValue::Uint16(s.read_u16()?),
+            Builtin::Int32 => AbiValue::Int32(s.read_u32()? as i32),
+            Builtin::Uint32 => AbiValue::Uint32(s.read_u32()?),
+            Builtin::Int64 => AbiValue::Int64(s.read_u64()? as i64),
+            Builtin::Uint64 => AbiValue::Uint64(s.read_u64()?),
+            Builtin::Int128 => AbiValue::Int128(i128::from_le_bytes(s.read_array()?)),
+            Builtin::Uint128 => AbiValue::Uint128(u128::from_le_bytes(s.read_array()?)),
+            Builtin::VarUint32 => AbiValue::VarUint32(s.read_varuint32()?),
+            Builtin::VarInt32 => AbiValue::VarInt32(s.read_varint32()?),
+            Builtin::Float32 => AbiValue::Float32(f32::from_le_bytes(s.read_array()?)),
+            Builtin::Float64 => AbiValue::Float64(f64::from_le_bytes(s.read_array()?)),
+            Builtin::Float128 => AbiValue::Float128(s.read_array()?),
+            Builtin::TimePoint => AbiValue::TimePoint(s.read_u64()? as i64),
+            Builtin::TimePointSec => AbiValue::TimePointSec(s.read_u32()?),
+            Builtin::BlockTimestamp => AbiValue::BlockTimestamp(s.read_u32()?),
+            Builtin::Name => AbiValue::Name(Name::new(s.read_u64()?)),
+            Builtin::Bytes => AbiValue::Bytes(s.read_byte_vec()?),
+            Builtin::Bitset => AbiValue::Bitset(builtin::read_bitset(s)?),
+            Builtin::String => AbiValue::String(s.read_string()?),
+            Builtin::Checksum160 => AbiValue::Checksum160(s.read_array()?),
+            Builtin::Checksum256 => AbiValue::Checksum256(s.read_array()?),
+            Builtin::Checksum512 => AbiValue::Checksum512(s.read_array()?),
+            Builtin::PublicKey => AbiValue::PublicKey(PublicKey::read(s)?),
+            Builtin::PrivateKey => AbiValue::PrivateKey(keys::read_private_key(s)?),
+            Builtin::Signature => AbiValue::Signature(Signature::read(s)?),
+            Builtin::Symbol => AbiValue::Symbol(Symbol::from_u64(s.read_u64()?)),
+            Builtin::SymbolCode => AbiValue::SymbolCode(SymbolCode::new(s.read_u64()?)),
+            Builtin::Asset => AbiValue::Asset(read_asset(s)?),
+            Builtin::ExtendedAsset => {
+                let quantity = read_asset(s)?;
+                AbiValue::ExtendedAsset(ExtendedAsset { quantity, contract: Name::new(s.read_u64()?) })
+            }
+        })
+    }
+
+    fn as_optional(&self) -> Option<&Self> {
+        match self {
+            AbiValue::Optional(value) => value.as_deref(),
+            value => Some(value),
+        }
+    }
+
+    fn from_optional(value: Option<Self>) -> Self {
+        AbiValue::Optional(value.map(Box::new))
+    }
+
+    fn as_items(&self) -> Option<&[Self]> {
+        match self {
+            AbiValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn from_items(items: Vec<Self>) -> Self {
+        AbiValue::Array(items)
+    }
+
+    fn is_struct(&self) -> bool {
+        matches!(self, AbiValue::Struct(_))
+    }
+
+    fn field(&self, name: &str) -> Option<&Self> {
+        self.get(name)
+    }
+
+    fn from_fields(fields: Vec<(String, Self)>) -> Self {
+        AbiValue::Struct(fields)
+    }
+
+    fn as_variant(&self) -> Option<(&str, &Self)> {
+        match self {
+            AbiValue::Variant(alternative, value) => Some((alternative, value)),
+            _ => None,
+        }
+    }
+
+    fn from_variant(alternative: String, value: Self) -> Self {
+        AbiValue::Variant(alternative, Box::new(value))
+    }
+}
+
+fn write_asset(asset: &Asset, out: &mut Vec<u8>) {
+    out.extend_from_slice(&asset.amount.to_le_bytes());
+    out.extend_from_slice(&asset.symbol.as_u64().to_le_bytes());
+}
+
+fn read_asset(s: &mut InputStream) -> Result<Asset, String> {
+    let amount = s.read_u64()? as i64;
+    Ok(Asset::new(amount, Symbol::from_u64(s.read_u64()?)))
+}
+
+#[cfg(feature = "serde_json")]
+impl From<&AbiValue> for serde_json::Value {
+    fn from(value: &AbiValue) -> serde_json::Value {
+        serde_json::Value::from(&value.to_json_value())
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<AbiValue> for serde_json::Value {
+    fn from(value: AbiValue) -> serde_json::Value {
+        serde_json::Value::from(&value)
+    }
+}
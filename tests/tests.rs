@@ -1,15 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use rs_abieos::Abieos;
-    use crate::samples::{BIN_ACTION_TRANSFER, EOSIO_TOKEN_HEX_ABI, EOSIO_TOKEN_U64, HEX_ACTION_TRANSFER};
+    use rs_abieos::{builtin_abis, de, ser, AbiDef, AbiRegistry, AbiValue, Abieos, AbieosError, AbieosPool, Asset, Compression, Conformance, Divergence, KeyType, Name, PackedTransaction, PublicKey, SecondaryIndex, SecondaryKey, Signature, Stage, Symbol, SymbolCode, signing_digest, transaction_id};
+    use serde::{Deserialize, Serialize};
+    use crate::samples::{BIN_ACTION_TRANSFER, EOSIO_TOKEN_HEX_ABI, EOSIO_TOKEN_U64, HEX_ACTION_TRANSFER, KITCHEN_SINK_ABI, SHOP_ABI, K1_PUBLIC_KEY, K1_TRANSFER_SIGNATURE, TRANSFER_TRX_HEX, mainnet_chain_id, name, transfer_transaction, transfer_trx};
 
     #[test]
+    #[cfg(not(feature = "pure-rust"))]
     fn new() {
         let abieos: Abieos = Abieos::new();
         assert!(!abieos.as_ptr().is_null(), "new test failed");
     }
 
     #[test]
+    #[cfg(not(feature = "pure-rust"))]
     fn new_from_context() {
         let abieos: Abieos = Abieos::new();
         // from_context creates a non-owning wrapper
@@ -45,8 +48,8 @@ mod tests {
         };
 
         let abieos: Abieos = Abieos::new();
-        match abieos.set_abi_json("eosio", &abi_data) {
-            Ok(x) => assert_eq!(x, true, "load abi test"),
+        match abieos.set_abi_json(name("eosio"), &abi_data) {
+            Ok(x) => assert!(x, "load abi test"),
             Err(e) => panic!("set_abi_json failed: {}", e)
         }
     }
@@ -54,8 +57,8 @@ mod tests {
     #[test]
     fn set_abi_hex() {
         let abieos: Abieos = Abieos::new();
-        match abieos.set_abi_hex("eosio", EOSIO_TOKEN_HEX_ABI) {
-            Ok(x) => assert_eq!(x, true, "load abi test"),
+        match abieos.set_abi_hex(name("eosio"), EOSIO_TOKEN_HEX_ABI) {
+            Ok(x) => assert!(x, "load abi test"),
             Err(e) => panic!("set_abi_hex failed: {}", e)
         }
     }
@@ -70,8 +73,8 @@ mod tests {
             Err(e) => panic!("load abi binary file failed: {}", e)
         };
 
-        match abieos.set_abi_bin("eosio", &abi_data) {
-            Ok(x) => assert_eq!(x, true, "load abi test"),
+        match abieos.set_abi_bin(name("eosio"), &abi_data) {
+            Ok(x) => assert!(x, "load abi test"),
             Err(e) => panic!("set_abi_bin failed: {}", e)
         }
 
@@ -81,7 +84,7 @@ mod tests {
     #[test]
     fn json_to_hex() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let json = r#"
         {
             "from":"alice",
@@ -96,7 +99,7 @@ mod tests {
     #[test]
     fn json_to_bin() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let json = r#"
         {
             "from":"alice",
@@ -111,7 +114,7 @@ mod tests {
     #[test]
     fn hex_to_json() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let bin = HEX_ACTION_TRANSFER;
         let json = abieos.hex_to_json("eosio.token", "transfer", bin).unwrap();
         let reverse_bin = abieos.json_to_hex("eosio.token", "transfer", &json).unwrap();
@@ -121,62 +124,64 @@ mod tests {
     #[test]
     fn get_type_for_action() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let action = "transfer";
-        let type_name = abieos.get_type_for_action("eosio.token", action).unwrap();
+        let type_name = abieos.get_type_for_action(name("eosio.token"), name(action)).unwrap();
         assert_eq!(type_name, "transfer");
     }
 
     #[test]
     fn get_type_for_action_invalid() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let action = "invalid";
-        let type_name = abieos.get_type_for_action("eosio.token", action);
-        assert_eq!(type_name.is_err(), true);
+        let type_name = abieos.get_type_for_action(name("eosio.token"), name(action));
+        assert!(type_name.is_err());
     }
 
     #[test]
     fn get_type_for_action_invalid_contract() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let action = "transfer";
-        let type_name = abieos.get_type_for_action("invalid", action);
-        assert_eq!(type_name.is_err(), true);
+        let type_name = abieos.get_type_for_action(name("invalid"), name(action));
+        assert!(type_name.is_err());
     }
 
     #[test]
     fn get_type_for_table() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let table = "accounts";
-        let type_name = abieos.get_type_for_table("eosio.token", table).unwrap();
+        let type_name = abieos.get_type_for_table(name("eosio.token"), name(table)).unwrap();
         assert_eq!(type_name, "account");
     }
 
     #[test]
     fn get_type_for_table_invalid() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let table = "invalid";
-        let type_name = abieos.get_type_for_table("eosio.token", table);
-        assert_eq!(type_name.is_err(), true);
+        let type_name = abieos.get_type_for_table(name("eosio.token"), name(table));
+        assert!(type_name.is_err());
     }
 
 
     #[test]
     fn get_type_for_table_invalid_contract() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let table = "accounts";
-        let type_name = abieos.get_type_for_table("invalid", table);
-        assert_eq!(type_name.is_err(), true);
+        let type_name = abieos.get_type_for_table(name("invalid"), name(table));
+        assert!(type_name.is_err());
     }
 
-    #[ignore]
     #[test]
     fn get_type_for_action_result() {
-        // todo: Find example for action result
+        let abieos: Abieos = Abieos::new();
+        abieos.set_abi_json(name("shop"), SHOP_ABI).unwrap();
+        assert_eq!(abieos.get_type_for_action_result(name("shop"), name("buy")).unwrap(), "receipt");
+        assert!(abieos.get_type_for_action_result(name("shop"), name("ping")).is_err());
     }
 
     #[test]
@@ -226,6 +231,7 @@ mod tests {
     // --- Default trait ---
 
     #[test]
+    #[cfg(not(feature = "pure-rust"))]
     fn default_trait() {
         let abieos: Abieos = Abieos::default();
         assert!(!abieos.as_ptr().is_null(), "default trait should create a valid context");
@@ -236,7 +242,7 @@ mod tests {
     #[test]
     fn bin_to_json() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let json = abieos.bin_to_json("eosio.token", "transfer", BIN_ACTION_TRANSFER).unwrap();
         // round-trip: bin -> json -> bin
         let bin = abieos.json_to_bin("eosio.token", "transfer", &json).unwrap();
@@ -248,14 +254,14 @@ mod tests {
     #[test]
     fn delete_contract() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
 
         // contract exists, should delete successfully
-        let result = abieos.delete_contract("eosio.token").unwrap();
+        let result = abieos.delete_contract(name("eosio.token")).unwrap();
         assert!(result, "delete_contract should return true for existing contract");
 
         // contract no longer exists, get_type_for_action should fail
-        let type_result = abieos.get_type_for_action("eosio.token", "transfer");
+        let type_result = abieos.get_type_for_action(name("eosio.token"), name("transfer"));
         assert!(type_result.is_err(), "should fail after contract is deleted");
 
     }
@@ -264,7 +270,7 @@ mod tests {
     fn delete_contract_nonexistent() {
         let abieos: Abieos = Abieos::new();
         // deleting a contract that was never loaded
-        let result = abieos.delete_contract("eosio.token").unwrap();
+        let result = abieos.delete_contract(name("eosio.token")).unwrap();
         assert!(!result, "delete_contract should return false for non-existent contract");
     }
 
@@ -272,7 +278,7 @@ mod tests {
     fn delete_contract_native() {
         let abieos: Abieos = Abieos::new();
         let token_u64 = abieos.string_to_name("eosio.token").unwrap();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
 
         let result = abieos.delete_contract_native(token_u64).unwrap();
         assert!(result, "delete_contract_native should return true for existing contract");
@@ -333,9 +339,9 @@ mod tests {
     fn contract_with_string_ref() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio.token";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
-        assert!(contract.abiLoaded);
+        assert!(contract.is_abi_loaded());
 
         let datatype = contract.get_type_for_action("transfer").unwrap();
         assert_eq!(datatype, "transfer");
@@ -344,35 +350,35 @@ mod tests {
     #[test]
     fn contract_with_string() {
         let abieos: Abieos = Abieos::new();
-        let mut contract = abieos.contract(rs_abieos::NameLike::String("eosio.token".to_string()));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from("eosio.token".to_string()).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
-        assert!(contract.abiLoaded);
+        assert!(contract.is_abi_loaded());
     }
 
     #[test]
     fn contract_with_u64() {
         let abieos: Abieos = Abieos::new();
-        let mut contract = abieos.contract(rs_abieos::NameLike::U64(EOSIO_TOKEN_U64));
+        let mut contract = abieos.contract(EOSIO_TOKEN_U64);
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
-        assert!(contract.abiLoaded);
+        assert!(contract.is_abi_loaded());
     }
 
     #[test]
-    fn contract_with_i32() {
+    fn contract_with_name() {
         let abieos: Abieos = Abieos::new();
-        let contract = abieos.contract(rs_abieos::NameLike::I32(1));
-        assert!(!contract.abiLoaded);
-        // i32(1) is a valid (if unusual) contract name
-        let _ = contract;
+        let contract = abieos.contract(rs_abieos::Name::new(1));
+        assert!(!contract.is_abi_loaded());
+        // raw value 1 is a valid (if unusual) contract name
+        assert_eq!(contract.name().as_u64(), 1);
     }
 
     #[test]
     fn contract_load_json_file() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_json_file("abis/eosio.abi").unwrap();
-        assert!(contract.abiLoaded);
+        assert!(contract.is_abi_loaded());
     }
 
     #[test]
@@ -380,9 +386,9 @@ mod tests {
         let abieos: Abieos = Abieos::new();
         let abi_data = std::fs::read_to_string("abis/eosio.abi").unwrap();
         let name = "eosio";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Json(abi_data)).unwrap();
-        assert!(contract.abiLoaded);
+        assert!(contract.is_abi_loaded());
     }
 
     #[test]
@@ -390,16 +396,16 @@ mod tests {
         let abieos: Abieos = Abieos::new();
         let abi_data = std::fs::read("abis/eosio.abi.bin").unwrap();
         let name = "eosio";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Bin(abi_data)).unwrap();
-        assert!(contract.abiLoaded);
+        assert!(contract.is_abi_loaded());
     }
 
     #[test]
     fn contract_json_to_hex() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio.token";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
 
         let json = r#"{"from":"alice","to":"bob","quantity":"1.0000 EOS","memo":"Hello!"}"#;
@@ -411,7 +417,7 @@ mod tests {
     fn contract_hex_to_json() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio.token";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
 
         let json = contract.hex_to_json("transfer", HEX_ACTION_TRANSFER).unwrap();
@@ -424,23 +430,102 @@ mod tests {
     fn contract_get_type_for_table() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio.token";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
 
         let table_type = contract.get_type_for_table("accounts").unwrap();
         assert_eq!(table_type, "account");
     }
 
+    #[test]
+    fn contract_abi_not_loaded() {
+        use rs_abieos::AbieosError;
+        let abieos: Abieos = Abieos::new();
+        let contract = abieos.contract(EOSIO_TOKEN_U64);
+        assert!(!contract.is_abi_loaded());
+        assert!(matches!(contract.get_type_for_action("transfer"), Err(AbieosError::AbiNotLoaded)));
+        assert!(matches!(contract.json_to_hex("transfer", "{}"), Err(AbieosError::AbiNotLoaded)));
+        assert!(matches!(contract.bin_to_json("transfer", BIN_ACTION_TRANSFER), Err(AbieosError::AbiNotLoaded)));
+    }
+
+    #[test]
+    fn contract_sees_abi_loaded_on_context() {
+        let abieos: Abieos = Abieos::new();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
+        let contract = abieos.contract(EOSIO_TOKEN_U64);
+        assert!(contract.is_abi_loaded());
+        assert!(abieos.has_contract(EOSIO_TOKEN_U64));
+        assert!(contract.hex_to_json("transfer", HEX_ACTION_TRANSFER).is_ok());
+    }
+
+    #[test]
+    fn contract_json_to_bin_roundtrip() {
+        let abieos: Abieos = Abieos::new();
+        let mut contract = abieos.contract(EOSIO_TOKEN_U64);
+        contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
+
+        let json = contract.bin_to_json("transfer", BIN_ACTION_TRANSFER).unwrap();
+        let bin = contract.json_to_bin("transfer", &json).unwrap();
+        assert_eq!(bin, BIN_ACTION_TRANSFER);
+    }
+
+    #[test]
+    fn contract_delete() {
+        use rs_abieos::AbieosError;
+        let abieos: Abieos = Abieos::new();
+        let mut contract = abieos.contract(EOSIO_TOKEN_U64);
+        contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
+        assert!(contract.delete().unwrap());
+        assert!(!contract.is_abi_loaded());
+        assert!(!abieos.has_contract(EOSIO_TOKEN_U64));
+        assert!(matches!(contract.json_to_bin("transfer", "{}"), Err(AbieosError::AbiNotLoaded)));
+        // deleting again reports that nothing was loaded
+        assert!(!contract.delete().unwrap());
+    }
+
+    #[test]
+    fn contract_get_type_for_action_result() {
+        use rs_abieos::AbieosError;
+        let abieos: Abieos = Abieos::new();
+        let mut contract = abieos.contract("shop".parse::<Name>().unwrap());
+        assert!(matches!(contract.get_type_for_action_result("buy"), Err(AbieosError::AbiNotLoaded)));
+        contract.load_abi(rs_abieos::AbiLike::Json(SHOP_ABI.to_string())).unwrap();
+
+        assert_eq!(contract.get_type_for_action_result("buy").unwrap(), "receipt");
+        // ping has no result type
+        assert!(matches!(contract.get_type_for_action_result("ping"), Err(AbieosError::GetTypeForActionResult(_))));
+        assert!(matches!(contract.get_type_for_action_result("BUY"), Err(AbieosError::InvalidName(_))));
+    }
+
     // --- C-string variants ---
 
     #[test]
     fn c_string_to_name() {
         let abieos: Abieos = Abieos::new();
-        let name = std::ffi::CStr::from_bytes_with_nul(b"eosio.token\0").unwrap();
+        let name = c"eosio.token";
         let name_u64 = abieos.c_string_to_name(name);
         assert_eq!(name_u64, EOSIO_TOKEN_U64);
     }
 
+    #[test]
+    fn name_to_string_outlives_context_calls() {
+        let abieos = Abieos::new();
+        let first = abieos.name_to_string(EOSIO_TOKEN_U64).unwrap();
+        // further calls must not clobber an earlier result
+        let second = abieos.name_to_string(abieos.string_to_name("alice").unwrap()).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
+        assert_eq!(first, "eosio.token");
+        assert_eq!(second, "alice");
+    }
+
+    #[test]
+    fn hex_to_json_c_invalid() {
+        let mut abieos: Abieos = Abieos::new();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
+        let json = abieos.hex_to_json_c(c"eosio.token", c"transfer", c"ZZZZ");
+        assert!(json.is_empty(), "invalid hex should produce an empty C-string");
+    }
+
     #[test]
     fn name_to_cstr() {
         let abieos: Abieos = Abieos::new();
@@ -455,6 +540,53 @@ mod tests {
         assert!(result.is_err(), "names longer than 13 chars should fail");
     }
 
+    // --- Name ---
+
+    #[test]
+    fn name_parse_display_roundtrip() {
+        use rs_abieos::Name;
+        for s in ["eosio", "eosio.token", "alice", "a.b.c", "1234512345123", "zzzzzzzzzzzzj", ""] {
+            let name: Name = s.parse().unwrap();
+            assert_eq!(name.to_string(), s);
+        }
+        let name = Name::try_from("eosio.token").unwrap();
+        assert_eq!(name.as_u64(), EOSIO_TOKEN_U64);
+        assert_eq!(Name::from(EOSIO_TOKEN_U64), name);
+        assert_eq!(u64::from(name), EOSIO_TOKEN_U64);
+    }
+
+    #[test]
+    fn name_invalid() {
+        use rs_abieos::{AbieosError, Name};
+        assert!(matches!("thisnamewaytolong".parse::<Name>(), Err(AbieosError::NameTooLong)));
+        assert!(matches!("Alice".parse::<Name>(), Err(AbieosError::InvalidName(_))));
+        assert!(matches!("bob6".parse::<Name>(), Err(AbieosError::InvalidName(_))));
+        assert!(matches!("alice.".parse::<Name>(), Err(AbieosError::InvalidName(_))));
+        // the 13th character is restricted to [.1-5a-j]
+        assert!(matches!("zzzzzzzzzzzzk".parse::<Name>(), Err(AbieosError::InvalidName(_))));
+    }
+
+    #[test]
+    fn name_ordering_and_hashing() {
+        use rs_abieos::Name;
+        use std::collections::HashSet;
+        let a: Name = "a".parse().unwrap();
+        let b: Name = "b".parse().unwrap();
+        let eosio: Name = "eosio".parse().unwrap();
+        assert!(a < b && b < eosio);
+        let set: HashSet<Name> = [a, b, a].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn name_display_unnormalized_value() {
+        use rs_abieos::Name;
+        // any u64 is a valid name value, trailing dots are trimmed on display
+        assert_eq!(Name::new(u64::MAX).to_string(), "zzzzzzzzzzzzj");
+        assert_eq!(Name::new(0).to_string(), "");
+        assert_eq!(format!("{:?}", Name::new(EOSIO_TOKEN_U64)), "Name(eosio.token)");
+    }
+
     // --- Drop safety ---
 
     #[test]
@@ -465,6 +597,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "pure-rust"))]
     fn drop_from_context_no_double_free() {
         // from_context creates a non-owning wrapper — dropping both should not double-free
         let abieos = Abieos::new();
@@ -475,6 +608,7 @@ mod tests {
     // --- as_ptr ---
 
     #[test]
+    #[cfg(not(feature = "pure-rust"))]
     fn as_ptr_returns_valid_pointer() {
         let abieos = Abieos::new();
         let ptr = abieos.as_ptr();
@@ -506,7 +640,7 @@ mod tests {
     #[test]
     fn json_to_bin_invalid_json() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         let result = abieos.json_to_bin("eosio.token", "transfer", "not valid json");
         assert!(result.is_err(), "invalid JSON should produce an error");
     }
@@ -522,7 +656,7 @@ mod tests {
     #[test]
     fn hex_to_json_invalid_hex() {
         let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         // "ZZZZ" is not valid hex
         let result = abieos.hex_to_json("eosio.token", "transfer", "ZZZZ");
         assert!(result.is_err(), "invalid hex should produce an error");
@@ -531,7 +665,7 @@ mod tests {
     #[test]
     fn set_abi_json_invalid() {
         let abieos: Abieos = Abieos::new();
-        let result = abieos.set_abi_json("eosio", "not a valid abi json");
+        let result = abieos.set_abi_json(name("eosio"), "not a valid abi json");
         assert!(result.is_err(), "invalid ABI JSON should produce an error");
     }
 
@@ -541,11 +675,11 @@ mod tests {
     fn set_abi_overwrite() {
         let abieos: Abieos = Abieos::new();
         // Load ABI, then overwrite with same ABI — should succeed
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
-        let result = abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
+        let result = abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
         assert!(result, "overwriting an ABI should succeed");
         // verify it still works after overwrite
-        let type_name = abieos.get_type_for_action("eosio.token", "transfer").unwrap();
+        let type_name = abieos.get_type_for_action(name("eosio.token"), name("transfer")).unwrap();
         assert_eq!(type_name, "transfer");
     }
 
@@ -553,18 +687,18 @@ mod tests {
     fn multiple_contracts() {
         let abieos: Abieos = Abieos::new();
         // Load same ABI under two different contract names
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
-        abieos.set_abi_hex("testcontract", EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_hex(name("testcontract"), EOSIO_TOKEN_HEX_ABI).unwrap();
 
         // Both should work independently
-        let t1 = abieos.get_type_for_action("eosio.token", "transfer").unwrap();
-        let t2 = abieos.get_type_for_action("testcontract", "transfer").unwrap();
+        let t1 = abieos.get_type_for_action(name("eosio.token"), name("transfer")).unwrap();
+        let t2 = abieos.get_type_for_action(name("testcontract"), name("transfer")).unwrap();
         assert_eq!(t1, t2);
 
         // Delete one, other should still work
-        abieos.delete_contract("eosio.token").unwrap();
-        assert!(abieos.get_type_for_action("eosio.token", "transfer").is_err());
-        assert!(abieos.get_type_for_action("testcontract", "transfer").is_ok());
+        abieos.delete_contract(name("eosio.token")).unwrap();
+        assert!(abieos.get_type_for_action(name("eosio.token"), name("transfer")).is_err());
+        assert!(abieos.get_type_for_action(name("testcontract"), name("transfer")).is_ok());
     }
 
     #[test]
@@ -588,19 +722,20 @@ mod tests {
 
     #[test]
     fn json_to_hex_c_roundtrip() {
-        use std::ffi::CStr;
-        let abieos: Abieos = Abieos::new();
-        abieos.set_abi_hex("eosio.token", EOSIO_TOKEN_HEX_ABI).unwrap();
+        let mut abieos: Abieos = Abieos::new();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
 
         let account = c"eosio.token";
         let action = c"transfer";
         let json = c"{\"from\":\"alice\",\"to\":\"bob\",\"quantity\":\"1.0000 EOS\",\"memo\":\"Hello!\"}";
 
-        let hex = abieos.json_to_hex_c(account, action, json);
+        // results borrow the context mutably, copy before the next call
+        let hex = abieos.json_to_hex_c(account, action, json).to_owned();
         assert!(!hex.is_empty(), "C-string serialization should produce output");
+        assert_eq!(hex.to_str().unwrap(), HEX_ACTION_TRANSFER);
 
         // roundtrip
-        let json_back = abieos.hex_to_json_c(account, action, hex);
+        let json_back = abieos.hex_to_json_c(account, action, &hex);
         assert!(!json_back.is_empty(), "C-string deserialization should produce output");
     }
 
@@ -609,7 +744,7 @@ mod tests {
         let abieos: Abieos = Abieos::new();
         let abi_content = std::fs::read_to_string("abis/eosio.abi").unwrap();
         let abi_c = std::ffi::CString::new(abi_content).unwrap();
-        let result = abieos.set_abi_json_c("eosio", &abi_c);
+        let result = abieos.set_abi_json_c(name("eosio"), &abi_c);
         assert!(result.is_ok(), "set_abi_json_c should succeed");
         assert!(result.unwrap(), "set_abi_json_c should return true");
     }
@@ -640,7 +775,7 @@ mod tests {
     fn contract_get_type_for_action() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio.token";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
 
         let type_name = contract.get_type_for_action("transfer").unwrap();
@@ -651,15 +786,1681 @@ mod tests {
     fn contract_get_type_for_action_invalid() {
         let abieos: Abieos = Abieos::new();
         let name = "eosio.token";
-        let mut contract = abieos.contract(rs_abieos::NameLike::StringRef(&name));
+        let mut contract = abieos.contract(rs_abieos::Name::try_from(name).unwrap());
         contract.load_abi(rs_abieos::AbiLike::Hex(EOSIO_TOKEN_HEX_ABI.to_string())).unwrap();
 
         let result = contract.get_type_for_action("nonexistent");
         assert!(result.is_err(), "non-existent action should fail");
     }
+
+    // --- AbiDef ---
+
+    fn decode_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn abi_def_json_to_bin_matches_fixture() {
+        let json_data = std::fs::read_to_string("abis/eosio.abi").unwrap();
+        let bin_data = std::fs::read("abis/eosio.abi.bin").unwrap();
+        let abi = rs_abieos::AbiDef::from_json(&json_data).unwrap();
+        assert_eq!(abi.version, "eosio::abi/1.2");
+        assert_eq!(abi.structs.len(), 112);
+        assert_eq!(abi.actions.len(), 66);
+        assert_eq!(abi.tables.len(), 30);
+        assert_eq!(abi.variants.len(), 1);
+        assert_eq!(abi.to_bin().unwrap(), bin_data);
+    }
+
+    #[test]
+    fn abi_def_bin_roundtrip() {
+        let bin_data = std::fs::read("abis/eosio.abi.bin").unwrap();
+        let abi = rs_abieos::AbiDef::from_bin(&bin_data).unwrap();
+        assert_eq!(abi.to_bin().unwrap(), bin_data);
+
+        let json_data = std::fs::read_to_string("abis/eosio.abi").unwrap();
+        assert_eq!(abi, rs_abieos::AbiDef::from_json(&json_data).unwrap());
+        assert_eq!(rs_abieos::AbiDef::from_json(&abi.to_json()).unwrap(), abi);
+    }
+
+    #[test]
+    fn abi_def_lookups() {
+        let abi = rs_abieos::AbiDef::from_bin(&std::fs::read("abis/eosio.abi.bin").unwrap()).unwrap();
+        let action = abi.get_action("buyrambytes".parse().unwrap()).unwrap();
+        assert_eq!(action.type_, "buyrambytes");
+        assert_eq!(abi.get_struct("buyrambytes").unwrap().fields.len(), 3);
+        assert_eq!(abi.get_table("global".parse().unwrap()).unwrap().type_, "eosio_global_state");
+        assert!(abi.get_variant("variant_block_signing_authority_v0").is_some());
+        assert!(abi.get_action("nonexistent".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn abi_def_legacy_binary() {
+        // abi/1.0 binaries end after abi_extensions, the missing sections are written empty
+        let bin_data = decode_hex(EOSIO_TOKEN_HEX_ABI);
+        let abi = rs_abieos::AbiDef::from_bin(&bin_data).unwrap();
+        assert_eq!(abi.version, "eosio::abi/1.0");
+        assert_eq!(abi.get_action("transfer".parse().unwrap()).unwrap().type_, "transfer");
+        assert_eq!(abi.get_table("accounts".parse().unwrap()).unwrap().key_types, vec!["uint64"]);
+
+        let mut expected = bin_data.clone();
+        expected.extend_from_slice(&[0, 0]);
+        assert_eq!(abi.to_bin().unwrap(), expected);
+    }
+
+    #[test]
+    fn abi_def_kv_tables_sorted() {
+        fn kv_abi(tables: &[&str]) -> String {
+            let tables: Vec<String> = tables
+                .iter()
+                .map(|t| format!(r#""{t}":{{"type":"row","primary_index":{{"name":"id","type":"uint64"}},"secondary_indices":{{}}}}"#))
+                .collect();
+            format!(r#"{{"version":"eosio::abi/1.2","kv_tables":{{{}}}}}"#, tables.join(","))
+        }
+        let unordered = rs_abieos::AbiDef::from_json(&kv_abi(&["zeta", "alpha", "mid"])).unwrap();
+        let ordered = rs_abieos::AbiDef::from_json(&kv_abi(&["alpha", "mid", "zeta"])).unwrap();
+        let bin = unordered.to_bin().unwrap();
+        assert_eq!(bin, ordered.to_bin().unwrap());
+        let names: Vec<Name> = rs_abieos::AbiDef::from_bin(&bin).unwrap().kv_tables.iter().map(|t| t.name).collect();
+        assert_eq!(names, vec![name("alpha"), name("mid"), name("zeta")]);
+    }
+
+    #[test]
+    fn abi_def_invalid() {
+        let bin_data = std::fs::read("abis/eosio.abi.bin").unwrap();
+        let result = rs_abieos::AbiDef::from_bin(&bin_data[..bin_data.len() / 2]);
+        assert!(matches!(result, Err(rs_abieos::AbieosError::InvalidAbi(_))));
+
+        // an extra empty kv_tables section is accepted, anything after it is not
+        let mut trailing = bin_data.clone();
+        trailing.extend_from_slice(&[0, 0]);
+        assert!(rs_abieos::AbiDef::from_bin(&trailing).is_err());
+
+        assert!(rs_abieos::AbiDef::from_json("[]").is_err());
+        assert!(rs_abieos::AbiDef::from_json(r#"{"actions":[{"name":"Invalid"}]}"#).is_err());
+        assert!(rs_abieos::AbiDef::from_json(r#"{"structs":{}}"#).is_err());
+    }
+
+    #[test]
+    fn abi_def_matches_abieos() {
+        let abieos: Abieos = Abieos::new();
+        let json_data = std::fs::read_to_string("abis/eosio.abi").unwrap();
+        let abi = rs_abieos::AbiDef::from_json(&json_data).unwrap();
+        assert_eq!(abi.to_bin().unwrap(), abieos.abi_json_to_bin(&json_data).unwrap());
+
+        let json_back = abieos.abi_bin_to_json(&abi.to_bin().unwrap()).unwrap();
+        assert_eq!(rs_abieos::AbiDef::from_json(&json_back).unwrap(), abi);
+
+        abieos.set_abi_def(EOSIO_TOKEN_U64, &abi).unwrap();
+        assert_eq!(abieos.get_type_for_action_native(EOSIO_TOKEN_U64, rs_abieos::Name::try_from("buyrambytes").unwrap()).unwrap(), "buyrambytes");
+    }
+
+    // --- builtin types ---
+
+    fn kitchen_sink() -> Abieos {
+        let abieos: Abieos = Abieos::new();
+        abieos.set_abi_json(name("test"), KITCHEN_SINK_ABI).unwrap();
+        abieos
+    }
+
+    fn roundtrip(abieos: &Abieos, datatype: &str, json: &str, hex: &str) {
+        assert_eq!(abieos.json_to_hex("test", datatype, json).unwrap(), hex, "json_to_hex {}", json);
+        assert_eq!(abieos.hex_to_json("test", datatype, hex).unwrap(), json, "hex_to_json {}", hex);
+    }
+
+    #[test]
+    fn builtin_integers() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "int8", "-1", "FF");
+        roundtrip(&abieos, "uint16", "65535", "FFFF");
+        roundtrip(&abieos, "int32", "-2", "FEFFFFFF");
+        roundtrip(&abieos, "int64", r#""-1""#, "FFFFFFFFFFFFFFFF");
+        roundtrip(&abieos, "uint64", r#""18446744073709551615""#, "FFFFFFFFFFFFFFFF");
+        roundtrip(&abieos, "uint128", r#""1""#, "01000000000000000000000000000000");
+        roundtrip(&abieos, "int128", r#""-2""#, "FEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF");
+        roundtrip(&abieos, "varuint32", "300", "AC02");
+        roundtrip(&abieos, "varint32", "-1", "01");
+        roundtrip(&abieos, "bool", "true", "01");
+        // quoted numbers are accepted as input
+        assert_eq!(abieos.json_to_hex("test", "uint32", r#""7""#).unwrap(), "07000000");
+        assert!(abieos.json_to_hex("test", "uint8", "256").is_err());
+        assert!(abieos.json_to_hex("test", "uint32", "-1").is_err());
+        assert!(abieos.json_to_hex("test", "int32", "1.5").is_err());
+    }
+
+    #[test]
+    fn builtin_floats() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "float64", "1.5", "000000000000F83F");
+        roundtrip(&abieos, "float64", "-0.001", "FCA9F1D24D6250BF");
+        roundtrip(&abieos, "float64", "10000000", "00000000D0126341");
+        roundtrip(&abieos, "float64", "1e+300", "9C7500883CE4377E");
+        roundtrip(&abieos, "float64", "1.5e-7", "76830DF4F521843E");
+        roundtrip(&abieos, "float64", r#""NaN""#, "000000000000F87F");
+        roundtrip(&abieos, "float64", r#""-Infinity""#, "000000000000F0FF");
+        roundtrip(&abieos, "float32", "0.125", "0000003E");
+        // float32 values are written as doubles
+        assert_eq!(abieos.json_to_hex("test", "float32", "0.1").unwrap(), "CDCCCC3D");
+        assert_eq!(abieos.hex_to_json("test", "float32", "CDCCCC3D").unwrap(), "0.10000000149011612");
+        // finite values beyond the float32 range are rejected rather than written as infinity
+        let test = name("test");
+        assert!(abieos.json_to_value(test, "float32", "1e39").is_err());
+        assert!(abieos.json_to_value(test, "float32", "-1e39").is_err());
+        assert_eq!(abieos.json_to_value(test, "float32", "3.4028234663852886e+38").unwrap(), AbiValue::Float32(f32::MAX));
+        assert_eq!(abieos.json_to_value(test, "float32", r#""Infinity""#).unwrap(), AbiValue::Float32(f32::INFINITY));
+        roundtrip(&abieos, "float128", r#""0x000102030405060708090A0B0C0D0E0F""#, "000102030405060708090A0B0C0D0E0F");
+    }
+
+    #[test]
+    fn builtin_bitset() {
+        let abieos = kitchen_sink();
+        // the string starts with the highest bit, the binary with the number of bits
+        roundtrip(&abieos, "bitset", r#""1011""#, "040B");
+        roundtrip(&abieos, "bitset", r#""1000000001""#, "0A0102");
+        roundtrip(&abieos, "bitset", r#""""#, "00");
+        assert!(abieos.json_to_hex("test", "bitset", r#""102""#).is_err());
+
+        let test = "test".parse::<Name>().unwrap();
+        let value = abieos.json_to_value(test, "bitset", r#""011""#).unwrap();
+        assert_eq!(value, AbiValue::Bitset(vec![true, true, false]));
+        assert_eq!(value.to_json(), r#""011""#);
+        // bits past the size must be zero
+        assert!(abieos.bin_to_value(test, "bitset", &[0x01, 0x03]).is_err());
+        assert!(abieos.bin_to_value(test, "bitset", &[0x09, 0xff]).is_err());
+    }
+
+    #[test]
+    fn builtin_time() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "time_point", r#""2018-06-27T20:33:54.000""#, "80108188A56F0500");
+        roundtrip(&abieos, "time_point_sec", r#""2018-06-27T20:33:54.000""#, "B2F4335B");
+        roundtrip(&abieos, "block_timestamp_type", r#""2000-01-01T00:00:00.500""#, "01000000");
+        roundtrip(&abieos, "time_point", r#""1969-12-31T23:59:59.999""#, "18FCFFFFFFFFFFFF");
+        assert_eq!(abieos.json_to_hex("test", "time_point_sec", r#""2018-06-27T20:33:54""#).unwrap(), "B2F4335B");
+        assert!(abieos.json_to_hex("test", "time_point", r#""2018-13-27T20:33:54""#).is_err());
+
+        // days are checked against the length of the month
+        let test = "test".parse::<Name>().unwrap();
+        for date in ["2024-02-29", "2000-02-29", "2023-04-30", "2023-12-31"] {
+            assert!(abieos.json_to_value(test, "time_point", &format!(r#""{}T00:00:00""#, date)).is_ok(), "{}", date);
+        }
+        for date in ["2024-02-31", "2023-02-29", "1900-02-29", "2023-04-31", "2023-06-31"] {
+            assert!(abieos.json_to_value(test, "time_point", &format!(r#""{}T00:00:00""#, date)).is_err(), "{}", date);
+            assert!(abieos.json_to_value(test, "time_point_sec", &format!(r#""{}T00:00:00""#, date)).is_err(), "{}", date);
+        }
+    }
+
+    #[test]
+    fn builtin_asset_symbol() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "symbol", r#""4,EOS""#, "04454F5300000000");
+        roundtrip(&abieos, "symbol_code", r#""EOS""#, "454F530000000000");
+        roundtrip(&abieos, "asset", r#""-1.0000 EOS""#, "F0D8FFFFFFFFFFFF04454F5300000000");
+        roundtrip(&abieos, "asset", r#""100 SYS""#, "64000000000000000053595300000000");
+        roundtrip(&abieos, "extended_asset", r#"{"quantity":"0.0001 EOS","contract":"eosio.token"}"#, "010000000000000004454F530000000000A6823403EA3055");
+        assert!(abieos.json_to_hex("test", "asset", r#""1.0000 eos""#).is_err());
+        assert!(abieos.json_to_hex("test", "symbol", r#""19,EOS""#).is_err());
+    }
+
+    #[test]
+    fn builtin_bytes_checksum() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "bytes", r#""00FF""#, "0200FF");
+        roundtrip(&abieos, "string", r#""héllo\n""#, "0768C3A96C6C6F0A");
+        let checksum = "AB".repeat(32);
+        roundtrip(&abieos, "checksum256", &format!("\"{}\"", checksum), &checksum);
+        assert!(abieos.json_to_hex("test", "checksum160", r#""ABAB""#).is_err());
+    }
+
+    #[test]
+    fn builtin_keys() {
+        let abieos = kitchen_sink();
+        let hex = "000270A7E3F8D0E2C5A42A4A70C4B2E6C5D0E4F1D6F0E0C1B2A3948576A5B4C3D2E1";
+        let key = abieos.hex_to_json("test", "public_key", hex).unwrap();
+        assert!(key.starts_with("\"PUB_K1_"), "{}", key);
+        assert_eq!(abieos.json_to_hex("test", "public_key", &key).unwrap(), hex);
+
+        // legacy format is accepted and normalized
+        let legacy = r#""EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV""#;
+        let bin = abieos.json_to_hex("test", "public_key", legacy).unwrap();
+        assert_eq!(abieos.hex_to_json("test", "public_key", &bin).unwrap(), r#""PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63""#);
+
+        // checksums are verified
+        assert!(abieos.json_to_hex("test", "public_key", r#""EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CW""#).is_err());
+
+        let private_hex = format!("01{}", "11".repeat(32));
+        let private_key = abieos.hex_to_json("test", "private_key", &private_hex).unwrap();
+        assert!(private_key.starts_with("\"PVT_R1_"), "{}", private_key);
+        assert_eq!(abieos.json_to_hex("test", "private_key", &private_key).unwrap(), private_hex);
+
+        let signature_hex = format!("00{}", "1F".repeat(65));
+        let signature = abieos.hex_to_json("test", "signature", &signature_hex).unwrap();
+        assert!(signature.starts_with("\"SIG_K1_"), "{}", signature);
+        assert_eq!(abieos.json_to_hex("test", "signature", &signature).unwrap(), signature_hex);
+    }
+
+    #[test]
+    fn public_key_codec() {
+        let abieos = kitchen_sink();
+        let legacy = "EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV";
+        let key: PublicKey = legacy.parse().unwrap();
+        assert_eq!(key.key_type(), KeyType::K1);
+        assert_eq!(key.to_string(), K1_PUBLIC_KEY);
+        assert_eq!(key.to_legacy_string().unwrap(), legacy);
+        assert_eq!(K1_PUBLIC_KEY.parse::<PublicKey>().unwrap(), key);
+        assert_eq!(key.to_bin(), abieos.json_to_bin("test", "public_key", &format!("\"{}\"", legacy)).unwrap());
+        assert_eq!(PublicKey::from_bin(&key.to_bin()).unwrap(), key);
+
+        // WebAuthn keys carry the user presence and relying party ID
+        // type, point, user presence and "example.com"
+        let wa_hex = format!("0202{}010B{}", "AB".repeat(32), "6578616D706C652E636F6D");
+        let wa_json = abieos.hex_to_json("test", "public_key", &wa_hex).unwrap();
+        let wa: PublicKey = wa_json.trim_matches('"').parse().unwrap();
+        assert_eq!(wa, PublicKey::WA { key: [[0x02].as_slice(), &[0xAB; 32]].concat().try_into().unwrap(), user_presence: 1, rpid: "example.com".to_string() });
+        assert_eq!(format!("\"{}\"", wa), wa_json);
+        assert_eq!(abieos.bin_to_json("test", "public_key", &wa.to_bin()).unwrap(), wa_json);
+        assert!(wa.to_legacy_string().is_none());
+
+        // binary serde uses the same layout
+        for key in [key, wa] {
+            let bin = ser::to_bytes(&key).unwrap();
+            assert_eq!(bin, key.to_bin());
+            assert_eq!(de::from_bytes::<PublicKey>(&bin).unwrap(), key);
+        }
+
+        assert!(matches!("EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CW".parse::<PublicKey>(), Err(AbieosError::InvalidKey(_))));
+        assert!("PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq64".parse::<PublicKey>().is_err());
+        // the checksum of modern keys covers the key type
+        assert!("PUB_R1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63".parse::<PublicKey>().is_err());
+        assert!(PublicKey::from_bin(&[0; 33]).is_err());
+        assert!(PublicKey::from_bin(&[0; 35]).is_err());
+        assert!(PublicKey::from_bin(&[[3].as_slice(), &[0; 33]].concat()).is_err());
+    }
+
+    #[test]
+    fn signature_codec() {
+        let abieos = kitchen_sink();
+        let signature: Signature = K1_TRANSFER_SIGNATURE.parse().unwrap();
+        assert_eq!(signature.key_type(), KeyType::K1);
+        assert_eq!(signature.to_string(), K1_TRANSFER_SIGNATURE);
+        assert_eq!(signature.to_bin(), abieos.json_to_bin("test", "signature", &format!("\"{}\"", K1_TRANSFER_SIGNATURE)).unwrap());
+        assert_eq!(Signature::from_bin(&signature.to_bin()).unwrap(), signature);
+
+        let wa = Signature::WA { compact: [0x1F; 65], auth_data: vec![1, 2, 3], client_json: "{}".to_string() };
+        let wa_json = abieos.bin_to_json("test", "signature", &wa.to_bin()).unwrap();
+        assert_eq!(wa_json, format!("\"{}\"", wa));
+        assert_eq!(wa_json.trim_matches('"').parse::<Signature>().unwrap(), wa);
+
+        for signature in [signature, wa] {
+            let bin = ser::to_bytes(&signature).unwrap();
+            assert_eq!(bin, signature.to_bin());
+            assert_eq!(de::from_bytes::<Signature>(&bin).unwrap(), signature);
+        }
+
+        assert!(matches!(K1_TRANSFER_SIGNATURE.replace("SIG_K1_", "SIG_R1_").parse::<Signature>(), Err(AbieosError::InvalidSignature(_))));
+        assert!(Signature::from_bin(&[0; 65]).is_err());
+    }
+
+    #[test]
+    fn struct_variant_optional_extension() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "derived", r#"{"a":"1","b":"alice","c":null,"d":["uint8",7]}"#, "01000000000000000000000000855C34000007");
+        roundtrip(&abieos, "derived", r#"{"a":"1","b":"alice","c":"x","d":["string","y"],"e":[1,2]}"#, "01000000000000000000000000855C340101780101790201000200");
+        // fields may be given in any order
+        assert_eq!(
+            abieos.json_to_hex("test", "derived", r#"{"d":["uint8",7],"c":null,"b":"alice","a":1}"#).unwrap(),
+            "01000000000000000000000000855C34000007"
+        );
+        assert!(abieos.json_to_hex("test", "derived", r#"{"a":1,"b":"alice","c":null}"#).is_err());
+        assert!(abieos.json_to_hex("test", "derived", r#"{"a":1,"b":"alice","c":null,"d":["int64",1]}"#).is_err());
+        assert!(abieos.hex_to_json("test", "derived", "01000000000000000000000000855C34000207").is_err());
+        // trailing data is rejected
+        assert!(abieos.hex_to_json("test", "uint8", "0101").is_err());
+    }
+
+    #[test]
+    fn typedefs_and_arrays() {
+        let abieos = kitchen_sink();
+        roundtrip(&abieos, "names", r#"["alice","bob"]"#, "020000000000855C340000000000000E3D");
+        roundtrip(&abieos, "uint16[]?", "[1,2]", "010201000200");
+        let error = abieos.json_to_hex("test", "names", r#"["alice","Bob"]"#).unwrap_err().to_string();
+        assert!(error.contains("names[1]"), "{}", error);
+    }
+
+    #[test]
+    fn fixed_arrays() {
+        let abieos: Abieos = Abieos::new();
+        let abi = r#"{"version":"eosio::abi/1.1","structs":[{"name":"fixed","base":"","fields":[{"name":"v","type":"uint8[2]"}]}]}"#;
+        abieos.set_abi_json(name("test"), abi).unwrap();
+        // fixed arrays keep the length prefix, and the size is validated
+        roundtrip(&abieos, "fixed", r#"{"v":[1,2]}"#, "020102");
+        assert!(abieos.json_to_hex("test", "fixed", r#"{"v":[1,2,3]}"#).is_err());
+        assert!(abieos.hex_to_json("test", "fixed", "03010203").is_err());
+    }
+
+    #[test]
+    fn set_abi_invalid_types() {
+        let abieos: Abieos = Abieos::new();
+        let unknown = r#"{"version":"eosio::abi/1.1","structs":[{"name":"s","base":"","fields":[{"name":"a","type":"nope"}]}]}"#;
+        assert!(abieos.set_abi_json(name("test"), unknown).is_err());
+        let version = r#"{"version":"eosio::abi/2.0"}"#;
+        assert!(abieos.set_abi_json(name("test"), version).is_err());
+        assert!(!abieos.has_contract(rs_abieos::Name::try_from("test").unwrap()));
+    }
+
+    #[test]
+    fn eosio_newaccount_roundtrip() {
+        let abieos: Abieos = Abieos::new();
+        abieos.set_abi_json(name("eosio"), &std::fs::read_to_string("abis/eosio.abi").unwrap()).unwrap();
+        let json = r#"{"creator":"eosio","name":"alice","owner":{"threshold":1,"keys":[{"key":"PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63","weight":1}],"accounts":[],"waits":[]},"active":{"threshold":1,"keys":[],"accounts":[{"permission":{"actor":"bob","permission":"active"},"weight":1}],"waits":[{"wait_sec":10,"weight":1}]}}"#;
+        let hex = abieos.json_to_hex("eosio", "newaccount", json).unwrap();
+        assert_eq!(abieos.hex_to_json("eosio", "newaccount", &hex).unwrap(), json);
+
+        // updateauth.authorized_by is a binary extension, omitted when absent
+        let json = r#"{"account":"alice","permission":"active","parent":"owner","auth":{"threshold":1,"keys":[],"accounts":[],"waits":[]}}"#;
+        let hex = abieos.json_to_hex("eosio", "updateauth", json).unwrap();
+        assert_eq!(abieos.hex_to_json("eosio", "updateauth", &hex).unwrap(), json);
+        let with_extension = r#"{"account":"alice","permission":"active","parent":"owner","auth":{"threshold":1,"keys":[],"accounts":[],"waits":[]},"authorized_by":"bob"}"#;
+        let hex_ext = abieos.json_to_hex("eosio", "updateauth", with_extension).unwrap();
+        assert_eq!(hex_ext, format!("{}{}", hex, "0000000000000E3D"));
+        assert_eq!(abieos.hex_to_json("eosio", "updateauth", &hex_ext).unwrap(), with_extension);
+    }
+
+    // --- Conformance against the reference encoder ---
+
+    #[test]
+    fn conformance_token_transfer() {
+        let abi = AbiDef::from_bin(&decode_hex(EOSIO_TOKEN_HEX_ABI)).unwrap();
+        let conformance = Conformance::new(&abi).unwrap();
+        assert_eq!(conformance.is_differential(), cfg!(not(feature = "pure-rust")));
+        let json = r#"{"from":"alice","to":"bob","quantity":"1.0000 EOS","memo":"Hello!"}"#;
+        assert_eq!(conformance.check_json("transfer", json), vec![]);
+        assert_eq!(conformance.check_hex("transfer", HEX_ACTION_TRANSFER).unwrap(), vec![]);
+        assert_eq!(conformance.check_bin("transfer", BIN_ACTION_TRANSFER), vec![]);
+    }
+
+    #[test]
+    fn conformance_kitchen_sink() {
+        let abi = AbiDef::from_json(KITCHEN_SINK_ABI).unwrap();
+        let conformance = Conformance::new(&abi).unwrap();
+        for (datatype, json) in [
+            ("derived", r#"{"a":"1","b":"alice","c":null,"d":["uint8",7]}"#),
+            ("derived", r#"{"d":["string","y"],"c":"x","b":"alice","a":1,"e":[1,2]}"#),
+            ("names", r#"["alice","bob"]"#),
+            ("uint16[]?", "[1,2]"),
+            ("asset", r#""1.0000 EOS""#),
+            ("time_point", r#""2021-01-01T00:00:00.500""#),
+        ] {
+            assert_eq!(conformance.check_json(datatype, json), vec![], "{} {}", datatype, json);
+        }
+        // payloads rejected by both sides do not diverge
+        assert_eq!(conformance.check_json("names", r#"["alice","Bob"]"#), vec![]);
+        assert_eq!(conformance.check_hex("uint8", "0101").unwrap(), vec![]);
+        assert!(conformance.check_hex("uint8", "0").is_err());
+    }
+
+    #[test]
+    fn conformance_eosio_abi() {
+        let abi = AbiDef::from_json(&std::fs::read_to_string("abis/eosio.abi").unwrap()).unwrap();
+        let conformance = Conformance::new(&abi).unwrap();
+        let sample = std::fs::read_to_string("abis/sample.json").unwrap();
+        let unordered = std::fs::read_to_string("abis/sample_unordered.json").unwrap();
+        assert_eq!(conformance.check_json("delegatebw", &sample), vec![]);
+        assert_eq!(conformance.check_json("delegatebw", &unordered), vec![]);
+    }
+
+    #[test]
+    fn conformance_cli_payload_flags() {
+        let abi = std::env::temp_dir().join(format!("rs_abieos_conformance_{}.abi.json", std::process::id()));
+        std::fs::write(&abi, r#"{"version":"eosio::abi/1.1"}"#).unwrap();
+        let run = |datatype: &str, flag: &str, payload: &str| {
+            std::process::Command::new(env!("CARGO_BIN_EXE_rs_abieos"))
+                .args(["conformance", abi.to_str().unwrap(), datatype, flag, payload])
+                .output()
+                .unwrap()
+        };
+        // JSON scalars are not mistaken for hex, and pure-rust builds do not report a pass
+        let (code, summary) = if cfg!(feature = "pure-rust") { (3, "not differential") } else { (0, "0 diverging") };
+        for (datatype, flag, payload) in [("uint64", "--json", "123"), ("bool", "--json", "true"), ("uint64", "--hex", "7B00000000000000")] {
+            let output = run(datatype, flag, payload);
+            assert_eq!(output.status.code(), Some(code), "{} {}", flag, payload);
+            assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), format!("1 payloads checked, {}", summary));
+        }
+        assert_eq!(run("uint64", "--hex", "123").status.code(), Some(2));
+        std::fs::remove_file(&abi).unwrap();
+    }
+
+    #[test]
+    fn conformance_invalid_abi() {
+        let abi = AbiDef::from_json(r#"{"version":"eosio::abi/1.1","types":[{"new_type_name":"a","type":"missing"}]}"#).unwrap();
+        assert!(matches!(Conformance::new(&abi), Err(AbieosError::InvalidAbi(_))));
+    }
+
+    #[test]
+    fn divergence_display() {
+        let divergence = Divergence {
+            stage: Stage::JsonToBin,
+            offset: Some(8),
+            path: "derived.b".to_string(),
+            abieos: "0100".to_string(),
+            reference: "0101".to_string(),
+        };
+        assert_eq!(divergence.to_string(), "json_to_bin divergence at derived.b (offset 8): abieos 0100, reference 0101");
+    }
+
+    // --- serde binary serializer ---
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Transfer {
+        from: Name,
+        to: Name,
+        quantity: Asset,
+        memo: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Choice {
+        Uint8(u8),
+        String(String),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Derived {
+        a: u64,
+        b: Name,
+        c: Option<String>,
+        d: Choice,
+    }
+
+    fn transfer() -> Transfer {
+        Transfer {
+            from: "alice".parse().unwrap(),
+            to: "bob".parse().unwrap(),
+            quantity: "1.0000 EOS".parse().unwrap(),
+            memo: "Hello!".to_string(),
+        }
+    }
+
+    #[test]
+    fn ser_transfer_matches_json_to_bin() {
+        let abieos: Abieos = Abieos::new();
+        abieos.set_abi_hex(name("eosio.token"), EOSIO_TOKEN_HEX_ABI).unwrap();
+        let json = r#"{"from":"alice","to":"bob","quantity":"1.0000 EOS","memo":"Hello!"}"#;
+        let bin = ser::to_bytes(&transfer()).unwrap();
+        assert_eq!(bin, BIN_ACTION_TRANSFER);
+        assert_eq!(bin, abieos.json_to_bin("eosio.token", "transfer", json).unwrap());
+        assert_eq!(ser::to_hex(&transfer()).unwrap(), HEX_ACTION_TRANSFER);
+    }
+
+    #[test]
+    fn ser_structs_variants_optionals() {
+        let abieos = kitchen_sink();
+        let derived = Derived { a: 1, b: "alice".parse().unwrap(), c: None, d: Choice::Uint8(7) };
+        let json = r#"{"a":"1","b":"alice","c":null,"d":["uint8",7]}"#;
+        assert_eq!(ser::to_bytes(&derived).unwrap(), abieos.json_to_bin("test", "derived", json).unwrap());
+
+        let derived = Derived { a: u64::MAX, b: Name::default(), c: Some("x".to_string()), d: Choice::String("y".to_string()) };
+        let json = r#"{"a":"18446744073709551615","b":"","c":"x","d":["string","y"]}"#;
+        assert_eq!(ser::to_bytes(&derived).unwrap(), abieos.json_to_bin("test", "derived", json).unwrap());
+
+        let names: Vec<Name> = vec!["alice".parse().unwrap(), "bob".parse().unwrap()];
+        assert_eq!(ser::to_hex(&names).unwrap(), "020000000000855C340000000000000E3D");
+        assert_eq!(ser::to_hex(&Some(vec![1u16, 2])).unwrap(), "010201000200");
+    }
+
+    #[test]
+    fn ser_builtins() {
+        assert_eq!(ser::to_hex(&(true, -1i8, 0x0102u16, -2i32)).unwrap(), "01FF0201FEFFFFFF");
+        assert_eq!(ser::to_hex(&1u128).unwrap(), "01000000000000000000000000000000");
+        assert_eq!(ser::to_hex(&1.5f64).unwrap(), "000000000000F83F");
+        // fixed-size arrays have no length prefix, like checksums
+        assert_eq!(ser::to_hex(&[0xABu8; 4]).unwrap(), "ABABABAB");
+        assert_eq!(ser::to_hex(&vec![0xABu8; 4]).unwrap(), "04ABABABAB");
+        assert_eq!(ser::to_hex(&"a".repeat(200)).unwrap(), format!("C801{}", "61".repeat(200)));
+        assert_eq!(ser::to_hex(&()).unwrap(), "");
+        let map: std::collections::BTreeMap<u8, String> = [(1, "a".to_string()), (2, "b".to_string())].into();
+        assert_eq!(ser::to_hex(&map).unwrap(), "02010161020162");
+    }
+
+    #[test]
+    fn ser_unknown_length_sequence() {
+        struct Evens(u16);
+        impl Serialize for Evens {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq((0..self.0).filter(|i| i % 2 == 0))
+            }
+        }
+        assert_eq!(ser::to_hex(&Evens(5)).unwrap(), "03000002000400");
+        assert_eq!(ser::to_hex(&Evens(300)).unwrap().len(), (2 + 150 * 2) * 2);
+        assert_eq!(&ser::to_hex(&Evens(300)).unwrap()[..4], "9601");
+    }
+
+    // --- serde binary deserializer ---
+
+    #[test]
+    fn de_transfer_from_bytes() {
+        let decoded: Transfer = de::from_bytes(BIN_ACTION_TRANSFER).unwrap();
+        assert_eq!(decoded, transfer());
+        assert_eq!(de::from_hex::<Transfer>(HEX_ACTION_TRANSFER).unwrap(), transfer());
+
+        // strings and bytes can borrow from the input
+        #[derive(Deserialize)]
+        struct Borrowed<'a> {
+            from: Name,
+            to: Name,
+            quantity: (i64, u64),
+            memo: &'a str,
+        }
+        let borrowed: Borrowed = de::from_bytes(BIN_ACTION_TRANSFER).unwrap();
+        assert_eq!((borrowed.from, borrowed.to), (transfer().from, transfer().to));
+        assert_eq!(borrowed.quantity, (10000, 0x534f4504));
+        assert_eq!(borrowed.memo, "Hello!");
+    }
+
+    #[test]
+    fn de_matches_hex_to_json() {
+        let abieos = kitchen_sink();
+        let hex = "01000000000000000000000000855C340101780101790201000200";
+        let derived: Derived = de::from_bytes(&decode_hex(hex)[..22]).unwrap();
+        assert_eq!(derived, Derived { a: 1, b: "alice".parse().unwrap(), c: Some("x".to_string()), d: Choice::String("y".to_string()) });
+        assert_eq!(
+            abieos.bin_to_json("test", "derived", &ser::to_bytes(&derived).unwrap()).unwrap(),
+            r#"{"a":"1","b":"alice","c":"x","d":["string","y"]}"#
+        );
+
+        let names: Vec<Name> = de::from_hex("020000000000855C340000000000000E3D").unwrap();
+        assert_eq!(names, vec![Name::try_from("alice").unwrap(), Name::try_from("bob").unwrap()]);
+        assert_eq!(de::from_hex::<Option<Vec<u16>>>("010201000200").unwrap(), Some(vec![1, 2]));
+        assert_eq!(de::from_hex::<[u8; 4]>("ABABABAB").unwrap(), [0xAB; 4]);
+        assert_eq!(de::from_hex::<(bool, i8, u16, i32)>("01FF0201FEFFFFFF").unwrap(), (true, -1, 0x0102, -2));
+        let map: std::collections::BTreeMap<u8, String> = de::from_hex("02010161020162").unwrap();
+        assert_eq!(map, [(1, "a".to_string()), (2, "b".to_string())].into());
+    }
+
+    #[test]
+    fn de_errors_report_offset() {
+        let error = |hex: &str| de::from_bytes::<Derived>(&decode_hex(hex)).unwrap_err().to_string();
+        assert_eq!(error("0100000000000000000000"), "Failed to deserialize value: unexpected end of data at offset 8");
+        assert_eq!(error("01000000000000000000000000855C340207"), "Failed to deserialize value: invalid optional flag 2 at offset 16");
+        assert!(error("01000000000000000000000000855C340005").ends_with("at offset 17"), "{}", error("01000000000000000000000000855C340005"));
+        assert_eq!(error("01000000000000000000000000855C3400000700"), "Failed to deserialize value: extra data at offset 19");
+        assert!(de::from_hex::<bool>("02").unwrap_err().to_string().ends_with("invalid bool 2 at offset 0"));
+        assert!(matches!(de::from_hex::<String>("05414243"), Err(AbieosError::Deserialize(_))));
+        assert!(de::from_hex::<serde::de::IgnoredAny>("00").is_err());
+    }
+
+    // --- typed values ---
+
+    #[test]
+    fn value_transfer() {
+        let abieos = Abieos::new();
+        let token = abieos.contract(Name::new(EOSIO_TOKEN_U64));
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let value = token.bin_to_value("transfer", BIN_ACTION_TRANSFER).unwrap();
+        assert_eq!(value.get("from"), Some(&AbiValue::Name("alice".parse().unwrap())));
+        assert_eq!(value.get("quantity"), Some(&AbiValue::Asset("1.0000 EOS".parse().unwrap())));
+        assert_eq!(value.get("memo"), Some(&AbiValue::String("Hello!".to_string())));
+        assert_eq!(value.to_json(), token.bin_to_json("transfer", BIN_ACTION_TRANSFER).unwrap());
+        assert_eq!(token.value_to_bin("transfer", &value).unwrap(), BIN_ACTION_TRANSFER);
+        assert_eq!(token.json_to_value("transfer", &value.to_json()).unwrap(), value);
+        assert!(matches!(abieos.contract(Name::new(1)).bin_to_value("transfer", BIN_ACTION_TRANSFER), Err(AbieosError::AbiNotLoaded)));
+    }
+
+    #[test]
+    fn value_kitchen_sink() {
+        let abieos = kitchen_sink();
+        let test = Name::try_from("test").unwrap();
+        let bin = decode_hex("01000000000000000000000000855C340101780101790201000200");
+        let value = abieos.bin_to_value(test, "derived", &bin).unwrap();
+        assert_eq!(
+            value,
+            AbiValue::Struct(vec![
+                ("a".to_string(), AbiValue::Uint64(1)),
+                ("b".to_string(), AbiValue::Name("alice".parse().unwrap())),
+                ("c".to_string(), AbiValue::Optional(Some(Box::new(AbiValue::String("x".to_string()))))),
+                ("d".to_string(), AbiValue::Variant("string".to_string(), Box::new(AbiValue::String("y".to_string())))),
+                ("e".to_string(), AbiValue::Array(vec![AbiValue::Uint16(1), AbiValue::Uint16(2)])),
+            ])
+        );
+        assert_eq!(abieos.value_to_bin(test, "derived", &value).unwrap(), bin);
+
+        // missing optionals and extensions may be omitted, optional values may be given bare
+        let value = AbiValue::Struct(vec![
+            ("d".to_string(), AbiValue::Variant("uint8".to_string(), Box::new(AbiValue::Uint8(7)))),
+            ("b".to_string(), AbiValue::Name("alice".parse().unwrap())),
+            ("a".to_string(), AbiValue::Uint64(1)),
+        ]);
+        assert_eq!(abieos.value_to_bin(test, "derived", &value).unwrap(), decode_hex("01000000000000000000000000855C34000007"));
+        let decoded = abieos.bin_to_value(test, "derived", &decode_hex("01000000000000000000000000855C34000007")).unwrap();
+        assert_eq!(decoded.get("c"), Some(&AbiValue::Optional(None)));
+        assert_eq!(decoded.get("e"), None);
+        assert_eq!(abieos.value_to_bin(test, "uint16[]?", &AbiValue::Array(vec![AbiValue::Uint16(1)])).unwrap(), decode_hex("01010100"));
+    }
+
+    #[test]
+    fn value_builtins_keep_precision() {
+        let abieos = kitchen_sink();
+        let test = Name::try_from("test").unwrap();
+        let value = abieos.json_to_value(test, "time_point", r#""2018-06-27T20:33:54.000123""#).unwrap();
+        assert_eq!(value, AbiValue::TimePoint(1_530_131_634_000_123));
+        assert_eq!(value.to_json(), r#""2018-06-27T20:33:54.000123""#);
+        assert_eq!(abieos.json_to_value(test, "time_point", &value.to_json()).unwrap(), value);
+        assert_eq!(AbiValue::TimePoint(1_530_131_634_000_000).to_json(), r#""2018-06-27T20:33:54.000""#);
+
+        assert_eq!(abieos.json_to_value(test, "uint64", r#""18446744073709551615""#).unwrap(), AbiValue::Uint64(u64::MAX));
+        assert_eq!(abieos.json_to_value(test, "checksum160", &format!("\"{}\"", "AB".repeat(20))).unwrap(), AbiValue::Checksum160([0xab; 20]));
+        assert_eq!(
+            abieos.json_to_value(test, "extended_asset", r#"{"quantity":"0.0001 EOS","contract":"eosio.token"}"#).unwrap(),
+            AbiValue::ExtendedAsset(rs_abieos::ExtendedAsset { quantity: "0.0001 EOS".parse().unwrap(), contract: Name::new(EOSIO_TOKEN_U64) })
+        );
+        let key = abieos.json_to_value(test, "public_key", r#""EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV""#).unwrap();
+        assert_eq!(key, AbiValue::PublicKey(K1_PUBLIC_KEY.parse().unwrap()));
+        assert_eq!(key.to_json(), format!("\"{}\"", K1_PUBLIC_KEY));
+        let signature = abieos.json_to_value(test, "signature", &format!("\"{}\"", K1_TRANSFER_SIGNATURE)).unwrap();
+        let typed: Signature = K1_TRANSFER_SIGNATURE.parse().unwrap();
+        assert_eq!(signature, AbiValue::Signature(typed.clone()));
+        assert_eq!(abieos.value_to_bin(test, "signature", &signature).unwrap(), typed.to_bin());
+    }
+
+    #[test]
+    fn value_errors() {
+        let abieos = kitchen_sink();
+        let test = Name::try_from("test").unwrap();
+        let error = abieos.value_to_bin(test, "names", &AbiValue::Array(vec![AbiValue::String("bob".to_string())])).unwrap_err();
+        assert!(matches!(error, AbieosError::Serialize(_)));
+        assert!(error.to_string().contains("expected name, got string"), "{}", error);
+        let error = abieos.value_to_bin(test, "derived", &AbiValue::Uint8(1)).unwrap_err().to_string();
+        assert!(error.contains("expected object, got uint8"), "{}", error);
+        assert!(matches!(abieos.bin_to_value(test, "uint8", &[1, 1]), Err(AbieosError::Deserialize(_))));
+        assert!(abieos.bin_to_value(Name::new(1), "uint8", &[1]).is_err());
+    }
+
+    #[test]
+    fn symbols_and_assets() {
+        let asset: Asset = "1.0000 EOS".parse().unwrap();
+        assert_eq!(asset, Asset::new(10000, Symbol::new(4, "EOS".parse().unwrap())));
+        assert_eq!(asset.symbol.code(), SymbolCode::new(0x534f45));
+        assert_eq!(format!("{:?}", asset), "Asset(1.0000 EOS)");
+        assert_eq!("100 SYS".parse::<Asset>().unwrap().to_string(), "100 SYS");
+        assert!(matches!("1.0000 eos".parse::<Asset>(), Err(AbieosError::InvalidAsset(_))));
+        assert!(matches!("19,EOS".parse::<Symbol>(), Err(AbieosError::InvalidSymbol(_))));
+        assert_eq!(ser::to_bytes(&asset).unwrap(), decode_hex("102700000000000004454F5300000000"));
+        assert_eq!(de::from_hex::<Asset>("102700000000000004454F5300000000").unwrap(), asset);
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn value_serde_json() {
+        let abieos = kitchen_sink();
+        let test = Name::try_from("test").unwrap();
+        let bin = decode_hex("01000000000000000000000000855C340101780101790201000200");
+        let value = abieos.bin_to_value(test, "derived", &bin).unwrap();
+        let json = serde_json::Value::from(&value);
+        assert_eq!(json, serde_json::json!({"a": "1", "b": "alice", "c": "x", "d": ["string", "y"], "e": [1, 2]}));
+        assert_eq!(abieos.json_value_to_value(test, "derived", &json).unwrap(), value);
+
+        let time = abieos.json_to_value(test, "time_point", r#""2018-06-27T20:33:54.000123""#).unwrap();
+        assert_eq!(abieos.json_value_to_value(test, "time_point", &serde_json::Value::from(time.clone())).unwrap(), time);
+        let float = AbiValue::Float64(0.1);
+        assert_eq!(abieos.json_value_to_value(test, "float64", &serde_json::Value::from(&float)).unwrap(), float);
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn serde_json_overloads() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let transfer = serde_json::json!({"from": "alice", "to": "bob", "quantity": "1.0000 EOS", "memo": "Hello!"});
+        assert_eq!(abieos.json_to_hex_value(EOSIO_TOKEN_U64, "transfer", &transfer).unwrap(), HEX_ACTION_TRANSFER);
+        assert_eq!(abieos.json_to_bin_value(EOSIO_TOKEN_U64, "transfer", &transfer).unwrap(), BIN_ACTION_TRANSFER);
+        assert_eq!(abieos.hex_to_json_value(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap(), transfer);
+        assert_eq!(abieos.bin_to_json_value(EOSIO_TOKEN_U64, "transfer", BIN_ACTION_TRANSFER).unwrap(), transfer);
+
+        let token = abieos.contract(Name::new(EOSIO_TOKEN_U64));
+        assert_eq!(token.json_to_hex_value("transfer", &transfer).unwrap(), HEX_ACTION_TRANSFER);
+        assert_eq!(token.json_to_bin_value("transfer", &transfer).unwrap(), BIN_ACTION_TRANSFER);
+        assert_eq!(token.hex_to_json_value("transfer", HEX_ACTION_TRANSFER).unwrap(), transfer);
+        assert_eq!(token.bin_to_json_value("transfer", BIN_ACTION_TRANSFER).unwrap(), transfer);
+
+        // nul bytes in strings are escaped instead of panicking
+        let memo = serde_json::json!({"from": "alice", "to": "bob", "quantity": "1.0000 EOS", "memo": "a\u{0}b"});
+        let bin = token.json_to_bin_value("transfer", &memo).unwrap();
+        assert_eq!(token.bin_to_json_value("transfer", &bin).unwrap(), memo);
+
+        assert!(matches!(token.json_to_hex_value("transfer", &serde_json::json!({"from": "alice"})), Err(AbieosError::JsonToHex(_))));
+        assert!(matches!(token.hex_to_json_value("transfer", "00"), Err(AbieosError::HexToJson(_))));
+        assert!(matches!(abieos.contract(Name::new(1)).bin_to_json_value("transfer", &[]), Err(AbieosError::AbiNotLoaded)));
+    }
+
+    #[test]
+    fn nul_bytes_are_errors() {
+        let abieos = kitchen_sink();
+        assert!(matches!(abieos.json_to_hex("test", "string", "\"a\u{0}\""), Err(AbieosError::JsonToHex(_))));
+        assert!(matches!(abieos.json_to_bin("test", "string\u{0}", "\"a\""), Err(AbieosError::JsonToBin(_))));
+        assert!(matches!(abieos.hex_to_json("test", "uint8", "01\u{0}"), Err(AbieosError::HexToJson(_))));
+    }
+
+    // --- context pool ---
+
+    #[test]
+    fn pool_replicates_abis() {
+        let pool = AbieosPool::new(3);
+        assert_eq!(pool.size(), 3);
+        pool.set_abi_hex(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        assert!(pool.has_contract(EOSIO_TOKEN_U64));
+
+        // every context sees the ABI, including the ones created before it was set
+        let leases: Vec<_> = (0..3).map(|_| pool.lease()).collect();
+        assert!(pool.try_lease().is_none());
+        for abieos in &leases {
+            assert_eq!(abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap(), abieos.bin_to_json_native(EOSIO_TOKEN_U64, "transfer", BIN_ACTION_TRANSFER).unwrap());
+        }
+        drop(leases);
+        assert_eq!(pool.available(), 3);
+
+        assert!(pool.delete_contract(EOSIO_TOKEN_U64).unwrap());
+        assert!(!pool.delete_contract(EOSIO_TOKEN_U64).unwrap());
+        assert!(!pool.has_contract(EOSIO_TOKEN_U64));
+        let leases: Vec<_> = (0..3).map(|_| pool.lease()).collect();
+        assert!(leases.iter().all(|abieos| !abieos.has_contract(EOSIO_TOKEN_U64)));
+    }
+
+    #[test]
+    fn pool_rejects_invalid_abi() {
+        let pool = AbieosPool::new(2);
+        assert!(matches!(pool.set_abi_json(EOSIO_TOKEN_U64, "{"), Err(AbieosError::SetAbi(_))));
+        assert!(!pool.has_contract(EOSIO_TOKEN_U64));
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[test]
+    fn pool_set_abi_while_leased() {
+        let pool = AbieosPool::new(1);
+        let abieos = pool.lease();
+        // the only context is leased, loading an ABI must not wait for it
+        pool.set_abi_hex(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        assert!(pool.has_contract(EOSIO_TOKEN_U64));
+        assert!(!abieos.has_contract(EOSIO_TOKEN_U64));
+        drop(abieos);
+        assert!(pool.lease().has_contract(EOSIO_TOKEN_U64));
+    }
+
+    #[test]
+    fn pool_across_threads() {
+        let pool = std::sync::Arc::new(AbieosPool::new(2));
+        pool.set_abi_json(name("test"), KITCHEN_SINK_ABI).unwrap();
+        let handles: Vec<_> = (0..8u8)
+            .map(|i| {
+                let pool = pool.clone();
+                std::thread::spawn(move || {
+                    let abieos = pool.lease();
+                    abieos.json_to_hex_native(name("test"), "uint8", &i.to_string()).unwrap()
+                })
+            })
+            .collect();
+        let hex: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(hex, (0..8u8).map(|i| format!("{:02X}", i)).collect::<Vec<_>>());
+    }
+
+    // --- ABI registry ---
+
+    #[test]
+    fn registry_across_threads() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<AbiRegistry>();
+        assert_sync::<AbieosPool>();
+
+        let registry = AbiRegistry::new();
+        registry.set_abi_hex(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        assert_eq!(registry.generation(), 1);
+        let expected = registry.bin_to_json("eosio.token", "transfer", BIN_ACTION_TRANSFER).unwrap();
+        assert_eq!(registry.hex_to_json("eosio.token", "transfer", HEX_ACTION_TRANSFER).unwrap(), expected);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let registry = registry.clone();
+                std::thread::spawn(move || registry.bin_to_json("eosio.token", "transfer", BIN_ACTION_TRANSFER).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn registry_propagates_changes() {
+        let registry = AbiRegistry::new();
+        let test: Name = "test".parse().unwrap();
+        let (to_worker, from_main) = std::sync::mpsc::channel::<()>();
+        let (to_main, from_worker) = std::sync::mpsc::channel();
+        let worker = registry.clone();
+        let handle = std::thread::spawn(move || {
+            for _ in from_main {
+                to_main.send(worker.json_to_hex("test", "uint8", "1").is_ok()).unwrap();
+            }
+        });
+
+        to_worker.send(()).unwrap();
+        assert!(!from_worker.recv().unwrap());
+        registry.set_abi_json(test, KITCHEN_SINK_ABI).unwrap();
+        to_worker.send(()).unwrap();
+        assert!(from_worker.recv().unwrap());
+        assert!(registry.delete_contract(test).unwrap());
+        assert!(!registry.delete_contract(test).unwrap());
+        to_worker.send(()).unwrap();
+        assert!(!from_worker.recv().unwrap());
+        drop(to_worker);
+        handle.join().unwrap();
+
+        assert!(matches!(registry.set_abi_json(test, "{"), Err(AbieosError::SetAbi(_))));
+        assert!(!registry.has_contract(test));
+        assert!(registry.with_context(|abieos| !abieos.has_contract(test)));
+    }
+
+    #[test]
+    fn encode_decode_action() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let transfer: Name = "transfer".parse().unwrap();
+        let json = abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap();
+        assert_eq!(abieos.encode_action(EOSIO_TOKEN_U64, transfer, &json).unwrap(), BIN_ACTION_TRANSFER);
+        assert_eq!(abieos.decode_action(EOSIO_TOKEN_U64, transfer, BIN_ACTION_TRANSFER).unwrap(), json);
+
+        // the action type is resolved from the ABI, not from the action name
+        let shop: Name = "shop".parse().unwrap();
+        abieos.set_abi_json_native(shop, SHOP_ABI).unwrap();
+        let buy: Name = "buy".parse().unwrap();
+        let data = abieos.encode_action(shop, buy, r#"{"buyer":"alice","amount":3}"#).unwrap();
+        assert_eq!(data, abieos.json_to_bin_native(shop, "buy_args", r#"{"buyer":"alice","amount":3}"#).unwrap());
+        assert_eq!(abieos.decode_action(shop, buy, &data).unwrap(), r#"{"buyer":"alice","amount":3}"#);
+        assert!(abieos.json_to_bin_native(shop, "buy", "{}").is_err());
+
+        let contract = abieos.contract(shop);
+        assert_eq!(contract.encode_action("buy", r#"{"buyer":"alice","amount":3}"#).unwrap(), data);
+        assert_eq!(contract.decode_action("ping", &[]).unwrap(), "{}");
+    }
+
+    #[test]
+    fn decode_action_result() {
+        let abieos = Abieos::new();
+        let shop: Name = "shop".parse().unwrap();
+        abieos.set_abi_json_native(shop, SHOP_ABI).unwrap();
+        let receipt = r#"{"id":"7","paid":"1.0000 EOS"}"#;
+        let bin = abieos.json_to_bin_native(shop, "receipt", receipt).unwrap();
+        assert_eq!(abieos.decode_action_result(shop, "buy".parse::<Name>().unwrap(), &bin).unwrap(), receipt);
+        assert_eq!(abieos.contract(shop).decode_action_result("buy", &bin).unwrap(), receipt);
+    }
+
+    #[test]
+    fn unknown_action_errors() {
+        let abieos = Abieos::new();
+        let shop: Name = "shop".parse().unwrap();
+        let sell: Name = "sell".parse().unwrap();
+        let ping: Name = "ping".parse().unwrap();
+        assert!(matches!(abieos.decode_action(shop, sell, &[]), Err(AbieosError::AbiNotLoaded)));
+
+        abieos.set_abi_json_native(shop, SHOP_ABI).unwrap();
+        let err = abieos.encode_action(shop, sell, "{}").unwrap_err();
+        assert!(matches!(err, AbieosError::UnknownAction(ref e) if e == "shop::sell"));
+        assert_eq!(err.to_string(), "Unknown action: shop::sell");
+        assert!(matches!(abieos.decode_action(shop, sell, &[]), Err(AbieosError::UnknownAction(_))));
+        assert!(matches!(abieos.decode_action_result(shop, ping, &[]), Err(AbieosError::UnknownActionResult(e)) if e == "shop::ping"));
+        assert!(matches!(abieos.contract(shop).decode_action("sell", &[]), Err(AbieosError::UnknownAction(_))));
+    }
+
+    #[test]
+    fn encode_decode_table_row() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let accounts: Name = "accounts".parse().unwrap();
+        let row = abieos.encode_table_row(EOSIO_TOKEN_U64, accounts, r#"{"balance":"10.0000 EOS"}"#).unwrap();
+        assert_eq!(row, abieos.json_to_bin_native(EOSIO_TOKEN_U64, "account", r#"{"balance":"10.0000 EOS"}"#).unwrap());
+        assert_eq!(abieos.decode_table_row(EOSIO_TOKEN_U64, accounts, &row).unwrap(), r#"{"balance":"10.0000 EOS"}"#);
+        // keyed by the symbol code of the balance, which is not a field
+        assert_eq!(abieos.table_row_primary_key(EOSIO_TOKEN_U64, accounts, &row).unwrap(), None);
+
+        let contract = abieos.contract(EOSIO_TOKEN_U64);
+        assert_eq!(contract.decode_table_row("accounts", &row).unwrap(), r#"{"balance":"10.0000 EOS"}"#);
+        assert!(matches!(contract.decode_table_row("balances", &row), Err(AbieosError::UnknownTable(e)) if e == "eosio.token::balances"));
+    }
+
+    #[test]
+    fn table_row_primary_key() {
+        let abieos = Abieos::new();
+        let shop: Name = "shop".parse().unwrap();
+        abieos.set_abi_json_native(shop, SHOP_ABI).unwrap();
+        let contract = abieos.contract(shop);
+        let order = contract.encode_table_row("orders", r#"{"id":42,"paid":"1.0000 EOS"}"#).unwrap();
+        assert_eq!(contract.table_row_primary_key("orders", &order).unwrap(), Some(42));
+        assert_eq!(contract.decode_table_row("orders", &order).unwrap(), r#"{"id":"42","paid":"1.0000 EOS"}"#);
+
+        // no key declared
+        let buyer = contract.encode_table_row("buyers", r#"{"buyer":"alice","amount":1}"#).unwrap();
+        assert_eq!(contract.table_row_primary_key("buyers", &buyer).unwrap(), None);
+        assert!(matches!(contract.table_row_primary_key("orders", &buyer), Err(AbieosError::Deserialize(_))));
+    }
+
+    #[test]
+    fn secondary_keys() {
+        let key = SecondaryKey::from_bin(SecondaryIndex::Idx64, &6138663591592764928u64.to_le_bytes()).unwrap();
+        assert_eq!(key, SecondaryKey::Idx64(6138663591592764928));
+        assert_eq!(key.to_string(), "6138663591592764928");
+
+        let key = SecondaryKey::from_bin(SecondaryIndex::Idx128, &u128::MAX.to_le_bytes()).unwrap();
+        assert_eq!(key.to_string(), u128::MAX.to_string());
+        assert_eq!(SecondaryKey::parse(SecondaryIndex::Idx128, "0xffffffffffffffffffffffffffffffff").unwrap(), key);
+
+        let key = SecondaryKey::from_hex(SecondaryIndex::IdxDouble, "000000000000F83F").unwrap();
+        assert_eq!(key, SecondaryKey::IdxDouble(1.5));
+        assert_eq!(key.to_string(), "1.5");
+        // doubles are formatted like the float64 values of the ABI codec
+        for (value, text) in [(1e300, "1e+300"), (-1e-300, "-1e-300"), (1.5e-7, "1.5e-7"), (123456.75, "123456.75")] {
+            assert_eq!(SecondaryKey::IdxDouble(value).to_string(), text);
+            assert_eq!(SecondaryKey::parse(SecondaryIndex::IdxDouble, text).unwrap(), SecondaryKey::IdxDouble(value));
+        }
+        assert_eq!(SecondaryKey::IdxDouble(f64::NAN).to_string(), "NaN");
+        assert_eq!(SecondaryKey::IdxDouble(f64::INFINITY).to_string(), "Infinity");
+        assert_eq!(SecondaryKey::IdxDouble(f64::NEG_INFINITY).to_string(), "-Infinity");
+        let key = SecondaryKey::parse(SecondaryIndex::IdxDouble, "-Infinity").unwrap();
+        assert_eq!(key, SecondaryKey::IdxDouble(f64::NEG_INFINITY));
+
+        let key = SecondaryKey::from_hex(SecondaryIndex::IdxLongDouble, "0000000000000000000000000000FF3F").unwrap();
+        assert_eq!(key.to_string(), "0x0000000000000000000000000000ff3f");
+
+        for key in ["42", "340282366920938463463374607431768211455", "1.5", "0x0000000000000000000000000000ff3f"]
+            .iter()
+            .zip([SecondaryIndex::Idx64, SecondaryIndex::Idx128, SecondaryIndex::IdxDouble, SecondaryIndex::IdxLongDouble])
+            .map(|(text, index)| SecondaryKey::parse(index, text).unwrap())
+        {
+            assert_eq!(SecondaryKey::from_bin(key.index(), &key.to_bin()).unwrap(), key);
+            assert_eq!(SecondaryKey::parse(key.index(), &key.to_string()).unwrap(), key);
+        }
+    }
+
+    #[test]
+    fn secondary_key_idx256_word_order() {
+        let checksum: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let key = SecondaryKey::from_checksum256(checksum);
+        // each 16-byte half of the checksum is stored as a little-endian word
+        let mut stored: Vec<u8> = (0..16u8).rev().collect();
+        stored.extend((16..32u8).rev());
+        assert_eq!(key.to_bin(), stored);
+        assert_eq!(SecondaryKey::from_bin(SecondaryIndex::Idx256, &stored).unwrap(), key);
+        assert_eq!(key.to_checksum256(), Some(checksum));
+        assert_eq!(key.to_string(), "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+        assert_eq!(SecondaryKey::parse(SecondaryIndex::Idx256, &key.to_string()).unwrap(), key);
+        assert_eq!(SecondaryKey::Idx64(1).to_checksum256(), None);
+    }
+
+    #[test]
+    fn secondary_key_errors() {
+        assert_eq!("sha256".parse::<SecondaryIndex>().unwrap(), SecondaryIndex::Idx256);
+        assert_eq!("idx_long_double".parse::<SecondaryIndex>().unwrap().key_size(), 16);
+        assert!(matches!("idx512".parse::<SecondaryIndex>(), Err(AbieosError::InvalidSecondaryKey(_))));
+        let err = SecondaryKey::from_bin(SecondaryIndex::Idx128, &[0; 8]).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid secondary key: idx128 key must be 16 bytes, got 8");
+        assert!(SecondaryKey::from_hex(SecondaryIndex::Idx64, "zz").is_err());
+        assert!(SecondaryKey::parse(SecondaryIndex::Idx64, "-1").is_err());
+        assert!(SecondaryKey::parse(SecondaryIndex::Idx256, "00").is_err());
+        assert!(SecondaryKey::parse(SecondaryIndex::IdxLongDouble, "1.5").is_err());
+    }
+
+    #[test]
+    fn encode_transaction_with_json_data() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let transfer = abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap();
+        let from_hex = abieos.encode_transaction(&transfer_transaction(&format!("\"{}\"", HEX_ACTION_TRANSFER))).unwrap();
+        let from_json = abieos.encode_transaction(&transfer_transaction(&transfer)).unwrap();
+        assert_eq!(from_hex, from_json);
+
+        // the data is needed in hex without the ABI
+        assert!(matches!(Abieos::new().encode_transaction(&transfer_transaction(&transfer)), Err(AbieosError::AbiNotLoaded)));
+        assert_eq!(transfer_trx(), from_hex);
+    }
+
+    #[test]
+    fn decode_transaction_with_action_data() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let transfer = abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap();
+        let bin = abieos.encode_transaction(&transfer_transaction(&transfer)).unwrap();
+
+        let decoded = abieos.decode_transaction(&bin).unwrap();
+        assert_eq!(decoded, transfer_transaction(&format!(r#"{},"hex_data":"{}""#, transfer, HEX_ACTION_TRANSFER)));
+        assert_eq!(abieos.encode_transaction(&decoded).unwrap(), bin);
+
+        // unknown contracts keep their data in hex
+        let decoded = Abieos::new().decode_transaction(&bin).unwrap();
+        assert_eq!(decoded, transfer_transaction(&format!("\"{}\"", HEX_ACTION_TRANSFER)));
+
+        assert!(matches!(abieos.decode_transaction(&bin[..bin.len() - 1]), Err(AbieosError::BinToJson(_))));
+        assert!(matches!(abieos.decode_transaction(&[bin.as_slice(), &[0]].concat()), Err(AbieosError::BinToJson(_))));
+    }
+
+    #[test]
+    fn pack_unpack_transaction() {
+        let trx = transfer_trx();
+
+        let plain = PackedTransaction::pack(&trx, Compression::None).unwrap();
+        assert_eq!(plain.packed_trx, trx);
+        assert!(plain.packed_context_free_data.is_empty());
+        assert_eq!(plain.unpack().unwrap(), trx);
+
+        let zlib = PackedTransaction::pack(&trx, Compression::Zlib).unwrap();
+        assert_eq!(&zlib.packed_trx[..1], &[0x78]);
+        assert_eq!(zlib.unpack().unwrap(), trx);
+        assert!(zlib.unpack_context_free_data().unwrap().is_empty());
+
+        let cfd = vec![vec![1, 2, 3], vec![]];
+        let with_cfd = PackedTransaction::pack_with_context_free_data(&trx, &cfd, Compression::Zlib).unwrap();
+        assert_eq!(with_cfd.unpack_context_free_data().unwrap(), cfd);
+
+        assert!(matches!(PackedTransaction::pack(&trx[1..], Compression::None), Err(AbieosError::InvalidPackedTransaction(_))));
+        let corrupted = PackedTransaction { packed_trx: vec![0x78, 0x9c, 0xff], ..zlib };
+        assert!(matches!(corrupted.unpack(), Err(AbieosError::InvalidPackedTransaction(_))));
+    }
+
+    #[test]
+    fn packed_transaction_bin_and_json() {
+        let trx = transfer_trx();
+        let packed = PackedTransaction::pack(&trx, Compression::Zlib).unwrap();
+
+        // a K1 signature, to exercise the signature codec
+        let mut bin = vec![1, 0];
+        bin.extend([7u8; 65]);
+        bin.extend(&packed.to_bin().unwrap()[1..]);
+        let signed = PackedTransaction::from_bin(&bin).unwrap();
+        assert_eq!(signed.signatures.len(), 1);
+        assert_eq!(signed.signatures[0].key_type(), KeyType::K1);
+        assert_eq!(signed.to_bin().unwrap(), bin);
+        assert!(PackedTransaction::from_bin(&[bin.as_slice(), &[0]].concat()).is_err());
+
+        let json = signed.to_json();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"signatures":["{}"],"compression":"zlib","packed_context_free_data":"","packed_trx":"{}"}}"#,
+                signed.signatures[0],
+                packed.packed_trx.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+            )
+        );
+        assert_eq!(PackedTransaction::from_json(&json).unwrap(), signed);
+
+        // numeric compression and defaults, as accepted by nodeos
+        let minimal = format!(r#"{{"compression":0,"packed_trx":"{}"}}"#, trx.iter().map(|b| format!("{:02X}", b)).collect::<String>());
+        let parsed = PackedTransaction::from_json(&minimal).unwrap();
+        assert_eq!(parsed, PackedTransaction::pack(&trx, Compression::None).unwrap());
+
+        assert!(PackedTransaction::from_json(r#"{"compression":"lzma","packed_trx":""}"#).is_err());
+        assert!(PackedTransaction::from_json(r#"{"signatures":["SIG_K1_bad"],"packed_trx":""}"#).is_err());
+        assert!(PackedTransaction::from_json(r#"{"signatures":[]}"#).is_err());
+    }
+
+    fn to_lower_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn transaction_id_and_signing_digest() {
+        let trx = transfer_trx();
+        assert_eq!(to_lower_hex(&trx).to_uppercase(), TRANSFER_TRX_HEX);
+        let chain_id = mainnet_chain_id();
+
+        assert_eq!(to_lower_hex(&transaction_id(&trx)), "49734370dd1881d35db5b48c3e1422d06bb3ac9f5143bf0a9eeb4dfe0d83ec97");
+        assert_eq!(
+            to_lower_hex(&signing_digest(&chain_id, &trx, &[])),
+            "8414e91435285488176fdc6a7fe457d03a33d6d666b9a87fc9f76676718b1c55"
+        );
+        let cfd = vec![vec![1, 2, 3], vec![]];
+        assert_eq!(
+            to_lower_hex(&signing_digest(&chain_id, &trx, &cfd)),
+            "ff501bf8485b2590dff9df5a8b9380edec7c0c8568840bc720f26a55741491bc"
+        );
+
+        // the ID does not depend on compression, the digest covers the context free data
+        let packed = PackedTransaction::pack_with_context_free_data(&trx, &cfd, Compression::Zlib).unwrap();
+        assert_eq!(packed.id().unwrap(), transaction_id(&trx));
+        assert_eq!(packed.signing_digest(&chain_id).unwrap(), signing_digest(&chain_id, &trx, &cfd));
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn private_key_formats() {
+        use rs_abieos::PrivateKey;
+        use crate::samples::{K1_PRIVATE_KEY, K1_WIF, R1_PRIVATE_KEY, R1_PUBLIC_KEY};
+
+        let k1: PrivateKey = K1_WIF.parse().unwrap();
+        assert_eq!(k1.key_type(), KeyType::K1);
+        assert_eq!(k1.to_string(), K1_PRIVATE_KEY);
+        assert_eq!(k1.to_wif().unwrap(), K1_WIF);
+        assert_eq!(k1.public_key().to_string(), K1_PUBLIC_KEY);
+        assert_eq!(K1_PRIVATE_KEY.parse::<PrivateKey>().unwrap().to_bytes(), k1.to_bytes());
+        assert_eq!(format!("{:?}", k1), format!("PrivateKey(\"{}\")", K1_PUBLIC_KEY));
+
+        let r1: PrivateKey = R1_PRIVATE_KEY.parse().unwrap();
+        assert_eq!(r1.key_type(), KeyType::R1);
+        assert_eq!(r1.to_bytes(), [0x42; 32]);
+        assert_eq!(r1.to_string(), R1_PRIVATE_KEY);
+        assert_eq!(r1.public_key().to_string(), R1_PUBLIC_KEY);
+        assert!(r1.to_wif().is_none());
+
+        // checksums, curve order and WA keys
+        assert!(matches!("5KQwrPbwdL6PhXujxW37FSSQZ1JiwsST4cqQzDeyXtP79zkvFD4".parse::<PrivateKey>(), Err(AbieosError::InvalidKey(_))));
+        assert!("PVT_K1_2bfGi9rYsXQSXXTvJbDAPhHLQUojjaNLomdm3cEJ1XTzMqUt3W".parse::<PrivateKey>().is_err());
+        assert!(PrivateKey::from_bytes(KeyType::K1, &[0xff; 32]).is_err());
+        assert!(PrivateKey::from_bytes(KeyType::K1, &[0; 32]).is_err());
+        assert!(PrivateKey::from_bytes(KeyType::WA, &[1; 32]).is_err());
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn sign_transaction() {
+        use rs_abieos::{PrivateKey, SignedTransaction};
+        use crate::samples::{K1_WIF, R1_PRIVATE_KEY, R1_TRANSFER_SIGNATURE};
+
+        let trx = transfer_trx();
+        let chain_id = mainnet_chain_id();
+        let k1: PrivateKey = K1_WIF.parse().unwrap();
+        let r1: PrivateKey = R1_PRIVATE_KEY.parse().unwrap();
+
+        let mut signed = SignedTransaction::new(trx.clone()).unwrap();
+        assert_eq!(signed.sign(&k1, &chain_id).to_string(), K1_TRANSFER_SIGNATURE);
+        assert_eq!(signed.sign(&r1, &chain_id).to_string(), R1_TRANSFER_SIGNATURE);
+        assert_eq!(signed.id(), transaction_id(&trx));
+        // signing is deterministic
+        assert_eq!(k1.sign_digest(&signed.signing_digest(&chain_id)).to_string(), K1_TRANSFER_SIGNATURE);
+
+        let packed = signed.pack(Compression::Zlib).unwrap();
+        assert_eq!(packed.signatures, signed.signatures);
+        assert_eq!(packed.unpack().unwrap(), trx);
+
+        // signed_transaction: transaction, signatures, then empty context free data
+        let bin = signed.to_bin().unwrap();
+        assert_eq!(&bin[..trx.len()], trx.as_slice());
+        assert_eq!(bin[trx.len()], 2);
+        assert_eq!(bin.last(), Some(&0));
+        assert_eq!(bin.len(), trx.len() + 1 + 2 * 66 + 1);
+
+        assert!(SignedTransaction::new(trx[1..].to_vec()).is_err());
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn recover_and_verify() {
+        use crate::samples::{R1_PUBLIC_KEY, R1_TRANSFER_SIGNATURE};
+
+        let trx = transfer_trx();
+        let chain_id = mainnet_chain_id();
+        let digest = signing_digest(&chain_id, &trx, &[]);
+
+        for (key, signature) in [(K1_PUBLIC_KEY, K1_TRANSFER_SIGNATURE), (R1_PUBLIC_KEY, R1_TRANSFER_SIGNATURE)] {
+            let key: PublicKey = key.parse().unwrap();
+            let signature: Signature = signature.parse().unwrap();
+            assert_eq!(signature.recover(&digest).unwrap(), key);
+            assert!(key.verify(&digest, &signature));
+            assert!(!key.verify(&[0; 32], &signature));
+        }
+        let k1: PublicKey = K1_PUBLIC_KEY.parse().unwrap();
+        assert!(!k1.verify(&digest, &R1_TRANSFER_SIGNATURE.parse().unwrap()));
+
+        let Signature::K1(compact) = K1_TRANSFER_SIGNATURE.parse().unwrap() else { unreachable!() };
+        // uncompressed key recovery IDs are accepted
+        let mut uncompressed = compact;
+        uncompressed[0] -= 4;
+        assert_eq!(Signature::K1(uncompressed).recover(&digest).unwrap(), k1);
+        let mut bad_id = compact;
+        bad_id[0] = 0;
+        assert!(matches!(Signature::K1(bad_id).recover(&digest), Err(AbieosError::InvalidSignature(_))));
+        let mut non_canonical = compact;
+        non_canonical[1] |= 0x80;
+        assert!(Signature::K1(non_canonical).recover(&digest).is_err());
+        assert!(!k1.verify(&digest, &Signature::K1(non_canonical)));
+
+        let wa = Signature::WA { compact, auth_data: vec![], client_json: String::new() };
+        assert!(wa.recover(&digest).is_err());
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn recover_signers_of_packed_transaction() {
+        use rs_abieos::recover_transaction_signers;
+        use crate::samples::{R1_PUBLIC_KEY, SIGNED_TRANSFER_JSON};
+
+        let chain_id = mainnet_chain_id();
+        let packed = PackedTransaction::from_json(SIGNED_TRANSFER_JSON).unwrap();
+        let signers = recover_transaction_signers(&packed, &chain_id).unwrap();
+        assert_eq!(signers, [K1_PUBLIC_KEY.parse::<PublicKey>().unwrap(), R1_PUBLIC_KEY.parse().unwrap()]);
+        assert_eq!(signers[0].to_legacy_string().unwrap(), "EOS6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5GDW5CV");
+
+        // the digest covers the chain and the context free data
+        let other_chain = recover_transaction_signers(&packed, &[0; 32]).unwrap();
+        assert!(other_chain.iter().all(|key| !signers.contains(key)));
+        let without_cfd = PackedTransaction { packed_context_free_data: vec![], ..packed.clone() };
+        assert_ne!(recover_transaction_signers(&without_cfd, &chain_id).unwrap(), signers);
+
+        let corrupted = PackedTransaction { packed_trx: vec![0x78, 0x9c], ..packed };
+        assert!(matches!(recover_transaction_signers(&corrupted, &chain_id), Err(AbieosError::InvalidPackedTransaction(_))));
+    }
+
+    /// K1 signatures are drawn until canonical, checked against an independent implementation of the
+    /// nonces of fc's `extended_nonce_function`, which start at the second RFC 6979 output
+    #[cfg(feature = "signing")]
+    #[test]
+    fn canonical_k1_signatures() {
+        use rs_abieos::PrivateKey;
+        use crate::samples::K1_WIF;
+
+        let k1: PrivateKey = K1_WIF.parse().unwrap();
+        let mut digest = [7u8; 32];
+        digest[0] = 1;
+        // the first nonce of keosd gives a non-canonical signature for this digest, while the first
+        // RFC 6979 output, which keosd skips, gives a canonical one
+        assert_eq!(
+            k1.sign_digest(&digest).to_string(),
+            "SIG_K1_K564sbsTe9yHZd2MYv6mA4jrLwtzjT7peXXPB9zm7t81MZDp1u5FXLfQgZG8QWyak6CUfNkb1iJGgsHiRsD5QnJgRtG78m"
+        );
+
+        for i in 0..32 {
+            digest[0] = i;
+            let signature = k1.sign_digest(&digest);
+            let compact = signature.compact();
+            assert!((31..35).contains(&compact[0]));
+            for half in [&compact[1..33], &compact[33..65]] {
+                assert_eq!(half[0] & 0x80, 0);
+                assert!(half[0] != 0 || half[1] & 0x80 != 0);
+            }
+        }
+    }
+
+    #[test]
+    fn bls_key_and_signature_codecs() {
+        use rs_abieos::{BlsPublicKey, BlsSignature};
+        use crate::samples::BLS_PUBLIC_KEY;
+
+        let key: BlsPublicKey = BLS_PUBLIC_KEY.parse().unwrap();
+        assert_eq!(key.to_string(), BLS_PUBLIC_KEY);
+        // points are prefixed with their size, like Spring serializes them
+        assert_eq!(key.to_bin(), [&[0x60], key.as_bytes().as_slice()].concat());
+        assert_eq!(BlsPublicKey::from_bin(&key.to_bin()).unwrap(), key);
+        assert_eq!(ser::to_bytes(&key).unwrap(), key.to_bin());
+        assert_eq!(de::from_bytes::<BlsPublicKey>(&key.to_bin()).unwrap(), key);
+        assert!(matches!(BlsPublicKey::from_bin(&key.to_bin()[1..]), Err(AbieosError::InvalidKey(_))));
+        assert!(matches!(BlsPublicKey::from_bin(&key.to_bin()[..96]), Err(AbieosError::InvalidKey(_))));
+        assert!(BlsPublicKey::from_bin(&[key.to_bin().as_slice(), &[0]].concat()).is_err());
+        assert!(de::from_bytes::<BlsPublicKey>(&[&[0x5f], &key.as_bytes()[..95]].concat()).is_err());
+
+        // the checksum covers the key data
+        let mut corrupted = BLS_PUBLIC_KEY.to_string();
+        corrupted.replace_range(20..21, if &corrupted[20..21] == "A" { "B" } else { "A" });
+        assert!(matches!(corrupted.parse::<BlsPublicKey>(), Err(AbieosError::InvalidKey(_))));
+        assert!(BLS_PUBLIC_KEY.replace("PUB_BLS_", "PUB_K1_").parse::<BlsPublicKey>().is_err());
+        assert!(BLS_PUBLIC_KEY[..BLS_PUBLIC_KEY.len() - 4].parse::<BlsPublicKey>().is_err());
+        assert!(K1_PUBLIC_KEY.parse::<BlsPublicKey>().is_err());
+
+        let signature = BlsSignature::from_bytes(std::array::from_fn(|i| i as u8));
+        let text = signature.to_string();
+        assert!(text.starts_with("SIG_BLS_"));
+        assert_eq!(text.len(), 8 + 262);
+        assert_eq!(text.parse::<BlsSignature>().unwrap(), signature);
+        assert_eq!(&signature.to_bin()[..2], &[0xc0, 0x01]);
+        assert_eq!(BlsSignature::from_bin(&signature.to_bin()).unwrap(), signature);
+        assert_eq!(de::from_bytes::<BlsSignature>(&ser::to_bytes(&signature).unwrap()).unwrap(), signature);
+        assert!(matches!(text.replace("SIG_", "PUB_").parse::<BlsPublicKey>(), Err(AbieosError::InvalidKey(_))));
+        assert!(matches!(BLS_PUBLIC_KEY.replace("PUB_", "SIG_").parse::<BlsSignature>(), Err(AbieosError::InvalidSignature(_))));
+    }
+
+    fn bls_finalizer(description: &str, weight: u64, key: u8) -> rs_abieos::FinalizerAuthority {
+        rs_abieos::FinalizerAuthority {
+            description: description.to_string(),
+            weight,
+            public_key: rs_abieos::BlsPublicKey::from_bytes([key; 96]),
+        }
+    }
+
+    #[test]
+    fn finalizer_policy_and_diff() {
+        use rs_abieos::{FinalizerPolicy, FinalizerPolicyDiff, OrderedDiff};
+
+        let policy = FinalizerPolicy {
+            generation: 1,
+            threshold: 2,
+            finalizers: vec![bls_finalizer("a", 1, 0xaa), bls_finalizer("b", 1, 0xbb), bls_finalizer("c", 1, 0xcc)],
+        };
+        let mut bin = vec![1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 3];
+        for (description, key) in [(b'a', 0xaa), (b'b', 0xbb), (b'c', 0xcc)] {
+            bin.extend([1, description, 1, 0, 0, 0, 0, 0, 0, 0, 0x60]);
+            bin.extend([key; 96]);
+        }
+        assert_eq!(policy.to_bin().unwrap(), bin);
+        assert_eq!(FinalizerPolicy::from_bin(&bin).unwrap(), policy);
+        assert!(matches!(FinalizerPolicy::from_bin(&bin[..bin.len() - 1]), Err(AbieosError::Deserialize(_))));
+
+        // removals index the original list, insertions the new one
+        let diff = FinalizerPolicyDiff {
+            generation: 2,
+            threshold: 3,
+            finalizers_diff: OrderedDiff { remove_indexes: vec![0, 2], insert_indexes: vec![(0, bls_finalizer("d", 2, 0xdd)), (2, bls_finalizer("e", 2, 0xee))] },
+        };
+        let next = policy.apply_diff(&diff).unwrap();
+        assert_eq!((next.generation, next.threshold), (2, 3));
+        assert_eq!(next.finalizers.iter().map(|f| f.description.as_str()).collect::<Vec<_>>(), ["d", "b", "e"]);
+        assert_eq!(policy.apply_diff(&FinalizerPolicyDiff { generation: 1, threshold: 2, ..Default::default() }).unwrap(), policy);
+
+        let bad_remove = OrderedDiff::<u8> { remove_indexes: vec![3], insert_indexes: vec![] };
+        assert!(matches!(bad_remove.apply(&[1, 2, 3]), Err(AbieosError::InvalidPolicyDiff(_))));
+        let bad_insert = OrderedDiff { remove_indexes: vec![], insert_indexes: vec![(4, 4)] };
+        assert!(matches!(bad_insert.apply(&[1, 2, 3]), Err(AbieosError::InvalidPolicyDiff(_))));
+        // removing [2, 1] or [1, 1] would otherwise drop the wrong items without error
+        for remove_indexes in [vec![2, 1], vec![1, 1]] {
+            let unordered = OrderedDiff::<u8> { remove_indexes, insert_indexes: vec![] };
+            assert!(matches!(unordered.apply(&[1, 2, 3]), Err(AbieosError::InvalidPolicyDiff(_))));
+        }
+        let unordered = OrderedDiff { remove_indexes: vec![], insert_indexes: vec![(1, 5), (0, 4)] };
+        assert!(matches!(unordered.apply(&[1, 2, 3]), Err(AbieosError::InvalidPolicyDiff(_))));
+        let ordered = OrderedDiff { remove_indexes: vec![0, 2], insert_indexes: vec![(0, 4), (2, 5)] };
+        assert_eq!(ordered.apply(&[1, 2, 3]).unwrap(), vec![4, 2, 5]);
+    }
+
+    #[test]
+    fn finality_extension() {
+        use rs_abieos::{BlockSigningAuthority, FinalityExtension};
+
+        let mut bin = vec![0x40, 0xe2, 0x01, 0x00, 0x00];
+        // finalizer policy diff: generation 2, threshold 1, remove [0], insert [(0, finalizer)]
+        bin.extend([1, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0]);
+        bin.extend([1, b'd', 1, 0, 0, 0, 0, 0, 0, 0, 0x60]);
+        bin.extend([0xdd; 96]);
+        // proposer policy diff: version 5, proposal time, insert [(0, producer)]
+        bin.extend([1, 5, 0, 0, 0, 0x10, 0x27, 0, 0, 0, 1, 0, 0]);
+        bin.extend("alice".parse::<Name>().unwrap().as_u64().to_le_bytes());
+        bin.extend([0, 1, 0, 0, 0, 1]);
+        bin.extend(K1_PUBLIC_KEY.parse::<PublicKey>().unwrap().to_bin());
+        bin.extend([1, 0]);
+
+        let extension = FinalityExtension::from_bin(&bin).unwrap();
+        assert_eq!(extension.qc_claim.block_num, 123456);
+        assert!(!extension.qc_claim.is_strong_qc);
+        let finalizers = extension.new_finalizer_policy_diff.as_ref().unwrap();
+        assert_eq!((finalizers.generation, finalizers.threshold), (2, 1));
+        assert_eq!(finalizers.finalizers_diff.remove_indexes, [0]);
+        assert_eq!(finalizers.finalizers_diff.insert_indexes, [(0, bls_finalizer("d", 1, 0xdd))]);
+        let proposers = extension.new_proposer_policy_diff.as_ref().unwrap();
+        assert_eq!((proposers.version, proposers.proposal_time), (5, 10000));
+        let (index, producer) = &proposers.producer_auth_diff.insert_indexes[0];
+        assert_eq!((*index, producer.producer_name.to_string().as_str()), (0, "alice"));
+        let BlockSigningAuthority::V0 { threshold, keys } = &producer.authority;
+        assert_eq!((*threshold, keys[0].key.to_string().as_str(), keys[0].weight), (1, K1_PUBLIC_KEY, 1));
+        assert_eq!(extension.to_bin().unwrap(), bin);
+
+        let extensions = [(1, vec![0]), (FinalityExtension::EXTENSION_ID, bin.clone())];
+        assert_eq!(FinalityExtension::from_header_extensions(&extensions).unwrap(), Some(extension));
+        assert_eq!(FinalityExtension::from_header_extensions(&extensions[..1]).unwrap(), None);
+        assert!(FinalityExtension::from_header_extensions(&[(2, bin[..5].to_vec())]).is_err());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn finalizer_policy_json() {
+        use rs_abieos::{BlsPublicKey, FinalizerPolicy};
+        use crate::samples::BLS_PUBLIC_KEY;
+
+        let policy = FinalizerPolicy {
+            generation: 1,
+            threshold: 1,
+            finalizers: vec![rs_abieos::FinalizerAuthority { description: "a".to_string(), weight: 1, public_key: BLS_PUBLIC_KEY.parse::<BlsPublicKey>().unwrap() }],
+        };
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(json, format!(r#"{{"generation":1,"threshold":1,"finalizers":[{{"description":"a","weight":1,"public_key":"{}"}}]}}"#, BLS_PUBLIC_KEY));
+        assert_eq!(serde_json::from_str::<FinalizerPolicy>(&json).unwrap(), policy);
+    }
+
+    #[cfg(feature = "bls")]
+    #[test]
+    fn bls_sign_and_verify() {
+        use rs_abieos::{BlsPrivateKey, BlsPublicKey, BlsSignature};
+        use crate::samples::{BLS_PUBLIC_KEY, BLS_PUBLIC_KEY_2};
+
+        // keys of Spring are points of G1 once converted from their little-endian layout
+        for text in [BLS_PUBLIC_KEY, BLS_PUBLIC_KEY_2] {
+            assert!(text.parse::<BlsPublicKey>().unwrap().is_valid());
+        }
+        let mut off_curve = *BLS_PUBLIC_KEY.parse::<BlsPublicKey>().unwrap().as_bytes();
+        off_curve[0] ^= 1;
+        assert!(!BlsPublicKey::from_bytes(off_curve).is_valid());
+
+        let key = BlsPrivateKey::from_seed(&[7; 32]).unwrap();
+        assert!(BlsPrivateKey::from_seed(&[7; 31]).is_err());
+        let text = key.to_string();
+        assert!(text.starts_with("PVT_BLS_"));
+        let parsed: BlsPrivateKey = text.parse().unwrap();
+        assert_eq!(parsed.to_bytes(), key.to_bytes());
+        assert_eq!(format!("{:?}", key), format!("BlsPrivateKey({:?})", key.public_key().to_string()));
+        assert!(BlsPrivateKey::from_bytes(&[0xff; 32]).is_err());
+
+        let public_key = key.public_key();
+        assert!(public_key.is_valid());
+        let signature = key.sign(b"finality digest");
+        assert_eq!(signature.to_string().parse::<BlsSignature>().unwrap(), signature);
+        assert!(public_key.verify(b"finality digest", &signature));
+        assert!(!public_key.verify(b"other digest", &signature));
+        assert!(!BlsPrivateKey::from_seed(&[8; 32]).unwrap().public_key().verify(b"finality digest", &signature));
+        assert!(!public_key.verify(b"finality digest", &BlsSignature::from_bytes([0; 192])));
+
+        // proofs of possession use their own domain
+        let proof = key.proof_of_possession();
+        assert!(public_key.verify_proof_of_possession(&proof));
+        assert!(!public_key.verify(public_key.as_bytes(), &proof));
+        assert!(!public_key.verify_proof_of_possession(&key.sign(public_key.as_bytes())));
+        assert!(!BlsPublicKey::from_bytes(off_curve).verify_proof_of_possession(&proof));
+    }
+
+    #[test]
+    fn builtin_abis_are_valid() {
+        let abieos = Abieos::new();
+        for abi in builtin_abis::ALL {
+            let def = abi.to_def().unwrap();
+            let contract = abi.account().unwrap_or_else(|| "builtin".parse().unwrap());
+            abieos.set_abi_json_native(contract, abi.json()).unwrap();
+            assert_eq!(abieos.abi_json_to_bin(abi.json()).unwrap(), def.to_bin().unwrap(), "{}", abi.name());
+            assert_eq!(builtin_abis::get(abi.name()), Some(abi));
+        }
+        assert_eq!(builtin_abis::get("eosio.evm"), None);
+    }
+
+    #[test]
+    fn with_system_abis() {
+        let abieos = Abieos::with_system_abis().unwrap();
+        let accounts: Vec<String> = abieos.loaded_contracts().map(|(contract, _)| contract.to_string()).collect();
+        assert_eq!(accounts, ["eosio", "eosio.msig", "eosio.token", "eosio.wrap"]);
+
+        assert_eq!(abieos.decode_action(EOSIO_TOKEN_U64, "transfer".parse::<Name>().unwrap(), BIN_ACTION_TRANSFER).unwrap(),
+            abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap());
+        assert_eq!(abieos.get_type_for_action(name("eosio.msig"), name("propose")).unwrap(), "propose");
+        assert_eq!(abieos.get_type_for_table(name("eosio.msig"), name("approvals2")).unwrap(), "approvals_info");
+        assert_eq!(abieos.get_type_for_action(name("eosio.wrap"), name("exec")).unwrap(), "exec");
+        assert_eq!(abieos.get_type_for_action(name("eosio"), name("buyram")).unwrap(), "buyram");
+    }
+
+    #[test]
+    fn builtin_state_history_abi() {
+        let abieos = Abieos::new();
+        let ship: Name = "ship".parse().unwrap();
+        abieos.set_abi_json_native(ship, builtin_abis::STATE_HISTORY.json()).unwrap();
+        let request = r#"["get_blocks_request_v0",{"start_block_num":1,"end_block_num":4294967295,"max_messages_in_flight":4,"have_positions":[],"irreversible_only":false,"fetch_block":true,"fetch_traces":true,"fetch_deltas":true}]"#;
+        let bin = abieos.json_to_bin_native(ship, "request", request).unwrap();
+        assert_eq!(bin.len(), 1 + 4 * 3 + 1 + 4);
+        assert_eq!(abieos.bin_to_json_native(ship, "request", &bin).unwrap(), request);
+
+        let row = r#"["contract_index256_v0",{"code":"eosio.token","scope":"alice","table":"accounts","primary_key":"5459781","payer":"alice","secondary_key":"000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F"}]"#;
+        let bin = abieos.json_to_bin_native(ship, "contract_index256", row).unwrap();
+        assert_eq!(abieos.bin_to_json_native(ship, "contract_index256", &bin).unwrap(), row);
+    }
+
+    #[test]
+    fn try_clone_copies_abis() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        abieos.set_abi_json(name("test"), KITCHEN_SINK_ABI).unwrap();
+
+        let clone = abieos.try_clone().unwrap();
+        assert!(clone.has_contract(EOSIO_TOKEN_U64));
+        assert_eq!(clone.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap(), abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).unwrap());
+        assert_eq!(clone.json_to_hex("test", "uint8", "7").unwrap(), "07");
+        assert_eq!(clone.loaded_contracts().collect::<Vec<_>>(), abieos.loaded_contracts().collect::<Vec<_>>());
+
+        // the clone is independent
+        clone.delete_contract_native(EOSIO_TOKEN_U64).unwrap();
+        assert!(abieos.has_contract(EOSIO_TOKEN_U64));
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let abieos = Abieos::new();
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let snapshot = abieos.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot.contracts().collect::<Vec<_>>(), vec![Name::new(EOSIO_TOKEN_U64)]);
+
+        abieos.set_abi_json(name("test"), KITCHEN_SINK_ABI).unwrap();
+        abieos.delete_contract_native(EOSIO_TOKEN_U64).unwrap();
+        abieos.restore(&snapshot).unwrap();
+        assert!(abieos.has_contract(EOSIO_TOKEN_U64));
+        assert!(!abieos.has_contract("test".parse::<Name>().unwrap()));
+        assert!(abieos.hex_to_json_native(EOSIO_TOKEN_U64, "transfer", HEX_ACTION_TRANSFER).is_ok());
+
+        abieos.restore(&Default::default()).unwrap();
+        assert_eq!(abieos.loaded_contracts().count(), 0);
+    }
+
+    #[test]
+    fn loaded_contracts_hash_binary_abi() {
+        let abieos = Abieos::new();
+        let abi_bin = decode_hex(EOSIO_TOKEN_HEX_ABI);
+        abieos.set_abi_hex_native(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).unwrap();
+        let (contract, hash) = abieos.loaded_contracts().next().unwrap();
+        assert_eq!(contract, Name::new(EOSIO_TOKEN_U64));
+
+        // the hash does not depend on the format the ABI was loaded in
+        abieos.set_abi_bin_native(EOSIO_TOKEN_U64, &abi_bin).unwrap();
+        assert_eq!(abieos.loaded_contracts().next().unwrap().1, hash);
+
+        // JSON ABIs are hashed in binary form
+        let abi_json = abieos.abi_bin_to_json(&abi_bin).unwrap();
+        abieos.set_abi_json_native(EOSIO_TOKEN_U64, &abi_json).unwrap();
+        let json_hash = abieos.loaded_contracts().next().unwrap().1;
+        abieos.set_abi_bin_native(EOSIO_TOKEN_U64, &abieos.abi_json_to_bin(&abi_json).unwrap()).unwrap();
+        assert_eq!(abieos.loaded_contracts().next().unwrap().1, json_hash);
+
+        abieos.set_abi_json(name("test"), KITCHEN_SINK_ABI).unwrap();
+        let hashes: Vec<_> = abieos.loaded_contracts().collect();
+        assert_eq!(hashes.len(), 2);
+        assert_ne!(hashes[0].1, hashes[1].1);
+    }
+
+    // --- async facade ---
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_codecs() {
+        let abieos = rs_abieos::AsyncAbieos::new(2);
+        abieos.set_abi_hex(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI).await.unwrap();
+        assert!(abieos.has_contract(EOSIO_TOKEN_U64).await.unwrap());
+        let json = abieos.hex_to_json("eosio.token", "transfer", HEX_ACTION_TRANSFER).await.unwrap();
+        assert_eq!(abieos.bin_to_json("eosio.token", "transfer", BIN_ACTION_TRANSFER).await.unwrap(), json);
+        assert_eq!(abieos.json_to_hex("eosio.token", "transfer", &json).await.unwrap(), HEX_ACTION_TRANSFER);
+        assert_eq!(abieos.json_to_bin("eosio.token", "transfer", &json).await.unwrap(), BIN_ACTION_TRANSFER);
+        assert_eq!(abieos.get_type_for_action(name("eosio.token"), name("transfer")).await.unwrap(), "transfer");
+
+        // concurrent requests are spread over the workers
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let abieos = abieos.clone();
+                tokio::spawn(async move { abieos.bin_to_json_owned("eosio.token", "transfer", BIN_ACTION_TRANSFER.to_vec()).await })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap(), json);
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_errors() {
+        let abieos = rs_abieos::AsyncAbieos::new(1);
+        assert!(matches!(abieos.set_abi_json(EOSIO_TOKEN_U64, "{").await, Err(AbieosError::SetAbi(_))));
+        assert!(matches!(abieos.json_to_hex("eosio.token", "transfer", "{}").await, Err(AbieosError::JsonToHex(_))));
+        assert!(matches!(abieos.hex_to_json("Invalid", "transfer", "00").await, Err(AbieosError::InvalidName(_))));
+        assert!(matches!(abieos.run(|_| -> Result<(), AbieosError> { panic!("worker panic") }).await, Err(AbieosError::Unknown)));
+        // the worker survives a panicking job
+        assert_eq!(abieos.run(|abieos| abieos.name_to_string(EOSIO_TOKEN_U64)).await.unwrap(), "eosio.token");
+        assert!(!abieos.delete_contract(EOSIO_TOKEN_U64).await.unwrap());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_contract_changes_in_order() {
+        let abieos = rs_abieos::AsyncAbieos::new(1);
+        // queued without waiting, each job runs after the ones sent before it
+        let set = abieos.set_abi_hex(EOSIO_TOKEN_U64, EOSIO_TOKEN_HEX_ABI);
+        let delete = abieos.delete_contract(EOSIO_TOKEN_U64);
+        let has = abieos.has_contract(EOSIO_TOKEN_U64);
+        let (set, delete, has) = tokio::join!(set, delete, has);
+        assert!(set.unwrap());
+        assert!(delete.unwrap());
+        assert!(!has.unwrap());
+        assert!(!abieos.delete_contract(EOSIO_TOKEN_U64).await.unwrap());
+    }
 }
 
 mod samples {
+    #[cfg(feature = "signing")]
+    pub const K1_WIF: &str = "5KQwrPbwdL6PhXujxW37FSSQZ1JiwsST4cqQzDeyXtP79zkvFD3";
+    #[cfg(feature = "signing")]
+    pub const K1_PRIVATE_KEY: &str = "PVT_K1_2bfGi9rYsXQSXXTvJbDAPhHLQUojjaNLomdm3cEJ1XTzMqUt3V";
+    pub const K1_PUBLIC_KEY: &str = "PUB_K1_6MRyAjQq8ud7hVNYcfnVPJqcVpscN5So8BhtHuGYqET5BoDq63";
+    /// Secret bytes `[0x42; 32]`
+    #[cfg(feature = "signing")]
+    pub const R1_PRIVATE_KEY: &str = "PVT_R1_WBVaWFoundqRbet928VeZtChcB6fYkrLq9SVruMP7vb1HN1jF";
+    #[cfg(feature = "signing")]
+    pub const R1_PUBLIC_KEY: &str = "PUB_R1_7H9AhXvgNbTpDgwskrCe2q6vvtzzSA1H4w4j9soBeX4WWE8UuE";
+    /// Signatures of `TRANSFER_TRX_HEX` on `EOS_MAINNET_CHAIN_ID`
+    pub const K1_TRANSFER_SIGNATURE: &str = "SIG_K1_KX7XyBK1NG2nV3Kz8vFu9hKNhBbuWfrNHTwaynweSfLncxre5VTxDgGiLy1kxk1BHoPaijPsoyTKRKkTGZ5S9TkoM93vm5";
+    #[cfg(feature = "signing")]
+    pub const R1_TRANSFER_SIGNATURE: &str = "SIG_R1_KFTd8fvvtDjReaKmU22n7UFeYV6v9j7o1FVf9UTMi1SU97ieV8BKj2w1X3aDhA7fWNMz5dtLU4pHG82J45BkrRrZNanA8k";
+    /// `TRANSFER_TRX_HEX` with context free data `[[1, 2, 3]]`, signed by the K1 and R1 keys on `EOS_MAINNET_CHAIN_ID`
+    #[cfg(feature = "signing")]
+    pub const SIGNED_TRANSFER_JSON: &str = r#"{"signatures":["SIG_K1_JzDujEtH6zy8jcDYBLHMt6sjv5tGznSzfdsKvbV86aVhH1k2ucR4xuTKFw5atRZvdQjr6cET64ikQQhvHiD94te1Nvy4Jj","SIG_R1_Kvm5rcnkTXLFYW3kCYTjDvuBTVEn5UghUV4CSsAjtSVYjnQVwJwaGm6N2vcoxzPidGo9oEdk9TcUZUivC1FyDLpjYLNUDo"],"compression":"zlib","packed_context_free_data":"789c636466646206000020000b","packed_trx":"789c4dc8bd0980301884e1fb542cc515acac040929b514ec2c446c6cd3055c410417700871992c12c80ef96b72cd7b3c175e41c81047f86e9eeb7e737fef06a528f073709fdf30d6a60054631d05c5b4acbee52ca43c1b58b1ed0e77"}"#;
+    pub const BLS_PUBLIC_KEY: &str = "PUB_BLS_qVbh4IjYZpRGo8U_0spBUM-u-r_G0fMo4MzLZRsKWmm5uyeQTp74YFaMN9IDWPoVVT5rj_Tw1gvps6K9_OZ6sabkJJzug3uGfjA6qiaLbLh5Fnafwv-nVgzzzBlU2kwRrcHc8Q";
+    #[cfg(feature = "bls")]
+    pub const BLS_PUBLIC_KEY_2: &str = "PUB_BLS_Uf3df_EqPpR31ZkenPtwgGUtd69cahyuY2lc9jPwEta7Q6t7REV-Hd35hUIDel4N7pQdCGZdnVZzs_UmJghEjGhVHN1QVVAQjOca8Fs10D_jqTiUzffzqyBAvTHyZtoEEPyXkg";
+    pub const EOS_MAINNET_CHAIN_ID: &str = "aca376f206b8fc25a6ed44dbdc66547c36c6c33e3a119ffbeaef943642f0e906";
+
+    /// `EOS_MAINNET_CHAIN_ID` as bytes
+    /// Parse a name known to be valid
+    pub fn name(name: &str) -> rs_abieos::Name {
+        name.parse().unwrap()
+    }
+
+    pub fn mainnet_chain_id() -> [u8; 32] {
+        std::array::from_fn(|i| u8::from_str_radix(&EOS_MAINNET_CHAIN_ID[i * 2..i * 2 + 2], 16).unwrap())
+    }
+
+    /// Transaction JSON transferring from alice with the given action data
+    pub fn transfer_transaction(data: &str) -> String {
+        format!(
+            r#"{{"expiration":"2024-01-01T00:00:00.000","ref_block_num":1,"ref_block_prefix":2,"max_net_usage_words":0,"max_cpu_usage_ms":0,"delay_sec":0,"context_free_actions":[],"actions":[{{"account":"eosio.token","name":"transfer","authorization":[{{"actor":"alice","permission":"active"}}],"data":{}}}],"transaction_extensions":[]}}"#,
+            data
+        )
+    }
+
+    /// `transfer_transaction` with the `HEX_ACTION_TRANSFER` data, encoded without ABI
+    pub fn transfer_trx() -> Vec<u8> {
+        rs_abieos::Abieos::new().encode_transaction(&transfer_transaction(&format!("\"{}\"", HEX_ACTION_TRANSFER))).unwrap()
+    }
+
+    /// `transfer_transaction` with the `HEX_ACTION_TRANSFER` data
+    pub const TRANSFER_TRX_HEX: &str = "80009265010002000000000000000100A6823403EA3055000000572D3CCDCD010000000000855C3400000000A8ED3232270000000000855C340000000000000E3D102700000000000004454F53000000000648656C6C6F2100";
     pub const EOSIO_TOKEN_U64: u64 = 6138663591592764928;
     pub const EOSIO_TOKEN_HEX_ABI: &str = "0e656f73696f3a3a6162692f312e30010c6163636f756e745f6e616d65046e616d6505087472616e7366657200040466726f6d0c6163636f756e745f6e616d6502746f0c6163636f756e745f6e616d65087175616e74697479056173736574046d656d6f06737472696e67066372656174650002066973737565720c6163636f756e745f6e616d650e6d6178696d756d5f737570706c79056173736574056973737565000302746f0c6163636f756e745f6e616d65087175616e74697479056173736574046d656d6f06737472696e67076163636f756e7400010762616c616e63650561737365740e63757272656e63795f7374617473000306737570706c790561737365740a6d61785f737570706c79056173736574066973737565720c6163636f756e745f6e616d6503000000572d3ccdcd087472616e73666572000000000000a531760569737375650000000000a86cd445066372656174650002000000384f4d113203693634010863757272656e6379010675696e743634076163636f756e740000000000904dc603693634010863757272656e6379010675696e7436340e63757272656e63795f7374617473000000";
 
@@ -711,4 +2512,36 @@ mod samples {
                 }
             ]
         }"#;
-}
\ No newline at end of file
+
+    pub const KITCHEN_SINK_ABI: &str = r#"{
+        "version": "eosio::abi/1.2",
+        "types": [{"new_type_name": "account", "type": "name"}, {"new_type_name": "names", "type": "account[]"}],
+        "structs": [
+            {"name": "base", "base": "", "fields": [{"name": "a", "type": "uint64"}, {"name": "b", "type": "account"}]},
+            {"name": "derived", "base": "base", "fields": [
+                {"name": "c", "type": "string?"},
+                {"name": "d", "type": "choice"},
+                {"name": "e", "type": "uint16[]$"}
+            ]}
+        ],
+        "variants": [{"name": "choice", "types": ["uint8", "string"]}]
+    }"#;
+
+    pub const SHOP_ABI: &str = r#"{
+        "version": "eosio::abi/1.2",
+        "structs": [
+            {"name": "buy_args", "base": "", "fields": [{"name": "buyer", "type": "name"}, {"name": "amount", "type": "uint32"}]},
+            {"name": "ping_args", "base": "", "fields": []},
+            {"name": "receipt", "base": "", "fields": [{"name": "id", "type": "uint64"}, {"name": "paid", "type": "asset"}]}
+        ],
+        "actions": [
+            {"name": "buy", "type": "buy_args", "ricardian_contract": ""},
+            {"name": "ping", "type": "ping_args", "ricardian_contract": ""}
+        ],
+        "tables": [
+            {"name": "orders", "index_type": "i64", "key_names": ["id"], "key_types": ["uint64"], "type": "receipt"},
+            {"name": "buyers", "index_type": "i64", "key_names": [], "key_types": [], "type": "buy_args"}
+        ],
+        "action_results": [{"name": "buy", "result_type": "receipt"}]
+    }"#;
+}